@@ -5,6 +5,7 @@
 # @flag --fa3 $FA
 # @flag --fc* $$
 # @flag --fd $$
+# @flag --[no-]fe $$
 flags() {
     _debug "$@"
 }
@@ -33,6 +33,7 @@ options() {
 # @flag  -c              short only
 # @flag     --fd*        multi-occurs
 # @flag  -e --fe*        short + multi-occurs
+# @flag     --[no-]ff    negatable
 flags() {
     _debug "$@"
 }
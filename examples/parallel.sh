@@ -4,6 +4,9 @@ set -e
 # @describe How to use `--argc-parallel`
 #
 # Compared with GNU parallel, the biggest advantage of argc-parallel is that it preserves `argc_*` variables.
+#
+# Output lines are prefixed with the job name, e.g. `[job1] ...`, and a summary is printed when all jobs finish.
+# Use `-j, --jobs <N>` (or `ARGC_PARALLEL_JOBS`) to limit concurrent jobs, `--fail-fast` to kill remaining jobs on the first failure.
 
 # @cmd
 cmd1() {
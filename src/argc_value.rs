@@ -2,8 +2,8 @@ use indexmap::IndexMap;
 
 #[cfg(feature = "eval-bash")]
 use crate::utils::{
-    argc_var_name, escape_shell_words, AFTER_HOOK, ARGC_LOAD_DOTENV, ARGC_REQUIRE_TOOLS,
-    BEFORE_HOOK, VARIABLE_PREFIX,
+    argc_var_name, escape_shell_words, AFTER_HOOK, ARGC_LOAD_DOTENV, ARGC_PRINT_HELP,
+    ARGC_REQUIRE_TOOLS, BEFORE_HOOK, VARIABLE_PREFIX,
 };
 
 #[derive(Debug, PartialEq, Eq)]
@@ -21,10 +21,16 @@ pub enum ArgcValue {
     Hook((bool, bool)),
     Dotenv(String),
     RequireTools(Vec<String>),
+    CommandDeps(Vec<String>),
+    /// Name, `@sources` and `@generates` of the matched command, used to skip it when up to date
+    CommandRecipe(String, Vec<String>, Vec<String>),
     CommandFn(String),
     ParamFn(String),
     ExternalSubcommand(String, Vec<String>, usize),
+    Warning(String),
     Error((String, i32)),
+    /// Help to print with colors and a pager
+    Help(String),
 }
 
 #[cfg(feature = "eval-bash")]
@@ -35,6 +41,7 @@ impl ArgcValue {
         let mut exit = false;
         let mut positional_args = vec![];
         let mut require_tools = vec![];
+        let mut deps = vec![];
         let mut exist_external_subcommand = false;
         let (mut before_hook, mut after_hook) = (false, false);
         for value in values {
@@ -118,6 +125,10 @@ impl ArgcValue {
                 ArgcValue::RequireTools(tools) => {
                     require_tools = tools.to_vec();
                 }
+                ArgcValue::CommandDeps(names) => {
+                    deps = names.to_vec();
+                }
+                ArgcValue::CommandRecipe(..) => {}
                 ArgcValue::CommandFn(name) => {
                     if positional_args.is_empty() {
                         last = name.to_string();
@@ -154,9 +165,20 @@ impl ArgcValue {
                     );
                     exist_external_subcommand = true;
                 }
+                ArgcValue::Warning(message) => {
+                    list.push(format!(
+                        "printf '%s\\n' {} >&2",
+                        escape_shell_words(message)
+                    ));
+                }
                 ArgcValue::Error((error, exit)) => {
                     return format!("command cat >&2 <<-'EOF' \n{error}\nEOF\nexit {exit}")
                 }
+                ArgcValue::Help(help) => {
+                    return format!(
+                        "{ARGC_PRINT_HELP}\n_argc_print_help 2 <<-'EOF'\n{help}\nEOF\nexit 0"
+                    )
+                }
             }
         }
 
@@ -182,6 +204,7 @@ impl ArgcValue {
         if before_hook {
             list.push(BEFORE_HOOK.to_string())
         }
+        list.extend(deps);
         if !last.is_empty() {
             list.push(last);
             if after_hook {
@@ -194,3 +217,536 @@ impl ArgcValue {
         list.join("\n")
     }
 }
+
+#[cfg(feature = "eval")]
+const ZSH_LOAD_DOTENV: &str = include_str!("template/load_dotenv.zsh");
+#[cfg(feature = "eval")]
+const FISH_LOAD_DOTENV: &str = include_str!("template/load_dotenv.fish");
+#[cfg(feature = "eval")]
+const FISH_REQUIRE_TOOLS: &str = include_str!("template/require_tools.fish");
+#[cfg(feature = "eval")]
+const POWERSHELL_LOAD_DOTENV: &str = include_str!("template/load_dotenv.ps1");
+#[cfg(feature = "eval")]
+const POWERSHELL_REQUIRE_TOOLS: &str = include_str!("template/require_tools.ps1");
+
+#[cfg(feature = "eval")]
+impl ArgcValue {
+    pub fn to_zsh(values: &[Self]) -> String {
+        Self::to_script::<Zsh>(values)
+    }
+
+    pub fn to_fish(values: &[Self]) -> String {
+        Self::to_script::<Fish>(values)
+    }
+
+    pub fn to_powershell(values: &[Self]) -> String {
+        Self::to_script::<Powershell>(values)
+    }
+
+    /// Nushell cannot evaluate generated code, so emit a NUON record to be consumed with `from nuon`
+    pub fn to_nushell(values: &[Self]) -> String {
+        use crate::utils::{argc_var_name, escape_shell_words, VARIABLE_PREFIX};
+
+        let mut fields: Vec<(String, String)> = vec![];
+        let mut envs = vec![];
+        let mut fns = vec![];
+        let mut positional_args = vec![];
+        let mut warnings = vec![];
+        let mut hooks = None;
+        for value in values {
+            match value {
+                ArgcValue::Single(id, value) => {
+                    fields.push((argc_var_name(id), nuon_string(value)));
+                }
+                ArgcValue::SingleFn(id, fn_name) | ArgcValue::PositionalSingleFn(id, fn_name) => {
+                    fns.push((argc_var_name(id), nuon_string(fn_name)));
+                }
+                ArgcValue::Multiple(id, values) => {
+                    fields.push((argc_var_name(id), nuon_list(values)));
+                }
+                ArgcValue::Map(id, map) => {
+                    let map: Vec<(String, String)> = map
+                        .iter()
+                        .map(|(k, v)| (k.to_string(), nuon_list(v)))
+                        .collect();
+                    fields.push((argc_var_name(id), nuon_record(&map)));
+                }
+                ArgcValue::PositionalSingle(id, value) => {
+                    fields.push((argc_var_name(id), nuon_string(value)));
+                    positional_args.push(value.to_string());
+                }
+                ArgcValue::PositionalMultiple(id, values) => {
+                    fields.push((argc_var_name(id), nuon_list(values)));
+                    positional_args.extend(values.iter().cloned());
+                }
+                ArgcValue::ExtraPositionalMultiple(values) => {
+                    positional_args.extend(values.iter().cloned());
+                }
+                ArgcValue::Env(name, value) => {
+                    envs.push((name.to_string(), nuon_string(value)));
+                }
+                ArgcValue::EnvFn(name, fn_name) => {
+                    fns.push((name.to_string(), nuon_string(fn_name)));
+                }
+                ArgcValue::Hook((before, after)) => {
+                    hooks = Some(nuon_record(&[
+                        ("before".into(), before.to_string()),
+                        ("after".into(), after.to_string()),
+                    ]));
+                }
+                ArgcValue::Dotenv(value) => {
+                    let files: Vec<String> = value.split_whitespace().map(|v| v.into()).collect();
+                    fields.push((format!("{VARIABLE_PREFIX}_dotenv"), nuon_list(&files)));
+                }
+                ArgcValue::RequireTools(tools) => {
+                    fields.push((format!("{VARIABLE_PREFIX}_require_tools"), nuon_list(tools)));
+                }
+                ArgcValue::CommandDeps(names) => {
+                    fields.push((format!("{VARIABLE_PREFIX}_deps"), nuon_list(names)));
+                }
+                ArgcValue::CommandRecipe(..) => {}
+                ArgcValue::CommandFn(name) | ArgcValue::ParamFn(name) => {
+                    fields.push((format!("{VARIABLE_PREFIX}_fn"), nuon_string(name)));
+                }
+                ArgcValue::ExternalSubcommand(script_path, args, subcommand_args_index) => {
+                    let parent_args = args[0..*subcommand_args_index]
+                        .iter()
+                        .map(|v| escape_shell_words(v))
+                        .collect::<Vec<_>>()
+                        .join(" ");
+                    let cmd: Vec<String> = ["bash".to_string(), script_path.clone()]
+                        .into_iter()
+                        .chain(args[*subcommand_args_index + 1..].iter().cloned())
+                        .collect();
+                    envs.push(("ARGC_PARENT_ARGS".into(), nuon_string(&parent_args)));
+                    return nuon_record(&[
+                        (format!("{VARIABLE_PREFIX}_env"), nuon_record(&envs)),
+                        (format!("{VARIABLE_PREFIX}_exec"), nuon_list(&cmd)),
+                    ]);
+                }
+                ArgcValue::Warning(message) => {
+                    warnings.push(message.to_string());
+                }
+                ArgcValue::Error((error, exit)) => {
+                    return nuon_record(&[
+                        (format!("{VARIABLE_PREFIX}_error"), nuon_string(error)),
+                        (format!("{VARIABLE_PREFIX}_exit"), exit.to_string()),
+                    ]);
+                }
+                ArgcValue::Help(help) => {
+                    return nuon_record(&[
+                        (format!("{VARIABLE_PREFIX}_error"), nuon_string(help)),
+                        (format!("{VARIABLE_PREFIX}_exit"), "0".to_string()),
+                    ]);
+                }
+            }
+        }
+
+        fields.push((
+            format!("{VARIABLE_PREFIX}_positionals"),
+            nuon_list(&positional_args),
+        ));
+        if !envs.is_empty() {
+            fields.push((format!("{VARIABLE_PREFIX}_env"), nuon_record(&envs)));
+        }
+        if !fns.is_empty() {
+            fields.push((format!("{VARIABLE_PREFIX}_fns"), nuon_record(&fns)));
+        }
+        if let Some(hooks) = hooks {
+            fields.push((format!("{VARIABLE_PREFIX}_hooks"), hooks));
+        }
+        if !warnings.is_empty() {
+            fields.push((format!("{VARIABLE_PREFIX}_warnings"), nuon_list(&warnings)));
+        }
+        nuon_record(&fields)
+    }
+
+    fn to_script<S: ShellSyntax>(values: &[Self]) -> String {
+        use crate::utils::{
+            argc_var_name, escape_shell_words, AFTER_HOOK, BEFORE_HOOK, VARIABLE_PREFIX,
+        };
+
+        let mut list = vec![];
+        let mut last = String::new();
+        let mut exit = false;
+        let mut positional_args = vec![];
+        let mut require_tools = vec![];
+        let mut deps = vec![];
+        let (mut before_hook, mut after_hook) = (false, false);
+        for value in values {
+            match value {
+                ArgcValue::Single(id, value) => {
+                    list.push(S::assign(&argc_var_name(id), &S::quote(value)));
+                }
+                ArgcValue::SingleFn(id, fn_name) => {
+                    list.push(S::assign(&argc_var_name(id), &S::capture(fn_name)));
+                }
+                ArgcValue::Multiple(id, values) => {
+                    let values: Vec<String> = values.iter().map(|v| S::quote(v)).collect();
+                    list.push(S::assign_list(&argc_var_name(id), &values));
+                }
+                ArgcValue::Map(id, map) => {
+                    list.push(S::assign_map(&argc_var_name(id), map));
+                }
+                ArgcValue::PositionalSingle(id, value) => {
+                    let value = S::quote(value);
+                    list.push(S::assign(&argc_var_name(id), &value));
+                    positional_args.push(value);
+                }
+                ArgcValue::PositionalSingleFn(id, fn_name) => {
+                    let value = S::capture(fn_name);
+                    list.push(S::assign(&argc_var_name(id), &value));
+                    positional_args.push(value);
+                }
+                ArgcValue::PositionalMultiple(id, values) => {
+                    let values: Vec<String> = values.iter().map(|v| S::quote(v)).collect();
+                    list.push(S::assign_list(&argc_var_name(id), &values));
+                    positional_args.extend(values);
+                }
+                ArgcValue::ExtraPositionalMultiple(values) => {
+                    positional_args.extend(values.iter().map(|v| S::quote(v)));
+                }
+                ArgcValue::Env(name, value) => {
+                    list.push(S::export(name, &S::quote(value)));
+                }
+                ArgcValue::EnvFn(name, fn_name) => {
+                    list.push(S::export(name, &S::capture(fn_name)));
+                }
+                ArgcValue::Hook((before, after)) => {
+                    before_hook |= *before;
+                    after_hook |= *after;
+                }
+                ArgcValue::Dotenv(value) => {
+                    list.push(S::load_dotenv(value));
+                }
+                ArgcValue::RequireTools(tools) => {
+                    require_tools = tools.to_vec();
+                }
+                ArgcValue::CommandDeps(names) => {
+                    deps = names.iter().map(|v| S::call(v, &[])).collect();
+                }
+                ArgcValue::CommandRecipe(..) => {}
+                ArgcValue::CommandFn(name) => {
+                    last = S::call(name, &positional_args);
+                    list.push(S::assign(&format!("{VARIABLE_PREFIX}_fn"), &S::quote(name)));
+                }
+                ArgcValue::ParamFn(name) => {
+                    last = S::call(name, &positional_args);
+                    exit = true;
+                }
+                ArgcValue::ExternalSubcommand(script_path, args, subcommand_args_index) => {
+                    let parent_args = args[0..*subcommand_args_index]
+                        .iter()
+                        .map(|v| escape_shell_words(v))
+                        .collect::<Vec<_>>()
+                        .join(" ");
+                    let remaining_args: Vec<String> = std::iter::once(script_path)
+                        .chain(args[*subcommand_args_index + 1..].iter())
+                        .map(|v| S::quote(v))
+                        .collect();
+                    return [
+                        S::export("ARGC_PARENT_ARGS", &S::quote(&parent_args)),
+                        S::exec("bash", &remaining_args),
+                    ]
+                    .join("\n");
+                }
+                ArgcValue::Warning(message) => list.push(S::warn(message)),
+                ArgcValue::Error((error, exit)) => return S::error(error, *exit),
+                ArgcValue::Help(help) => return S::error(help, 0),
+            }
+        }
+
+        list.push(S::assign_list(
+            &format!("{VARIABLE_PREFIX}_positionals"),
+            &positional_args,
+        ));
+        if !require_tools.is_empty() {
+            list.push(S::require_tools(&require_tools));
+        }
+        if before_hook {
+            list.push(S::call(BEFORE_HOOK, &[]));
+        }
+        list.extend(deps);
+        if !last.is_empty() {
+            list.push(last);
+            if after_hook {
+                list.push(S::call(AFTER_HOOK, &[]));
+            }
+        }
+        if exit {
+            list.push("exit".to_string());
+        }
+        list.join("\n")
+    }
+}
+
+/// How a shell spells the statements emitted by `ArgcValue::to_script`
+#[cfg(feature = "eval")]
+trait ShellSyntax {
+    fn quote(value: &str) -> String;
+    fn assign(name: &str, value: &str) -> String;
+    fn assign_list(name: &str, values: &[String]) -> String;
+    fn assign_map(name: &str, map: &IndexMap<String, Vec<String>>) -> String;
+    fn export(name: &str, value: &str) -> String;
+    fn capture(fn_name: &str) -> String;
+    fn call(fn_name: &str, args: &[String]) -> String;
+    fn exec(prog: &str, args: &[String]) -> String;
+    fn load_dotenv(files: &str) -> String;
+    fn require_tools(tools: &[String]) -> String;
+    fn warn(message: &str) -> String;
+    fn error(message: &str, exit: i32) -> String;
+}
+
+#[cfg(feature = "eval")]
+struct Zsh;
+
+#[cfg(feature = "eval")]
+impl ShellSyntax for Zsh {
+    fn quote(value: &str) -> String {
+        crate::utils::escape_shell_words(value)
+    }
+
+    fn assign(name: &str, value: &str) -> String {
+        format!("{name}={value}")
+    }
+
+    fn assign_list(name: &str, values: &[String]) -> String {
+        format!("{name}=( {} )", values.join(" "))
+    }
+
+    fn assign_map(name: &str, map: &IndexMap<String, Vec<String>>) -> String {
+        let pairs = map
+            .iter()
+            .map(|(k, v)| format!("{} {}", Self::quote(k), Self::quote(&v.join("|"))))
+            .collect::<Vec<String>>()
+            .join(" ");
+        format!("typeset -gA {name}\n{name}=( {pairs} )")
+    }
+
+    fn export(name: &str, value: &str) -> String {
+        format!("export {name}={value}")
+    }
+
+    fn capture(fn_name: &str) -> String {
+        format!("$({fn_name})")
+    }
+
+    fn call(fn_name: &str, args: &[String]) -> String {
+        std::iter::once(fn_name.to_string())
+            .chain(args.iter().cloned())
+            .collect::<Vec<String>>()
+            .join(" ")
+    }
+
+    fn exec(prog: &str, args: &[String]) -> String {
+        Self::call(prog, args)
+    }
+
+    fn load_dotenv(files: &str) -> String {
+        format!("{ZSH_LOAD_DOTENV}_argc_load_dotenv {files}")
+    }
+
+    fn require_tools(tools: &[String]) -> String {
+        let tools: Vec<String> = tools.iter().map(|v| Self::quote(v)).collect();
+        format!(
+            "\n{}\n_argc_require_tools {}\n",
+            crate::utils::ARGC_REQUIRE_TOOLS,
+            tools.join(" ")
+        )
+    }
+
+    fn warn(message: &str) -> String {
+        format!("print -ru2 -- {}", Self::quote(message))
+    }
+
+    fn error(message: &str, exit: i32) -> String {
+        format!("command cat >&2 <<-'EOF' \n{message}\nEOF\nexit {exit}")
+    }
+}
+
+#[cfg(feature = "eval")]
+struct Fish;
+
+#[cfg(feature = "eval")]
+impl ShellSyntax for Fish {
+    fn quote(value: &str) -> String {
+        if !value.is_empty()
+            && value
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.' | '/' | '='))
+        {
+            return value.to_string();
+        }
+        format!("'{}'", value.replace('\\', "\\\\").replace('\'', "\\'"))
+    }
+
+    fn assign(name: &str, value: &str) -> String {
+        format!("set -g {name} {value}")
+    }
+
+    fn assign_list(name: &str, values: &[String]) -> String {
+        std::iter::once(format!("set -g {name}"))
+            .chain(values.iter().cloned())
+            .collect::<Vec<String>>()
+            .join(" ")
+    }
+
+    fn assign_map(name: &str, map: &IndexMap<String, Vec<String>>) -> String {
+        let entries: Vec<String> = map
+            .iter()
+            .map(|(k, v)| Self::quote(&format!("{k}={}", v.join("|"))))
+            .collect();
+        Self::assign_list(name, &entries)
+    }
+
+    fn export(name: &str, value: &str) -> String {
+        format!("set -gx {name} {value}")
+    }
+
+    fn capture(fn_name: &str) -> String {
+        format!("({fn_name})")
+    }
+
+    fn call(fn_name: &str, args: &[String]) -> String {
+        Zsh::call(fn_name, args)
+    }
+
+    fn exec(prog: &str, args: &[String]) -> String {
+        Self::call(prog, args)
+    }
+
+    fn load_dotenv(files: &str) -> String {
+        let files: Vec<String> = files.split_whitespace().map(Self::quote).collect();
+        format!("{FISH_LOAD_DOTENV}_argc_load_dotenv {}", files.join(" "))
+    }
+
+    fn require_tools(tools: &[String]) -> String {
+        let tools: Vec<String> = tools.iter().map(|v| Self::quote(v)).collect();
+        format!(
+            "{FISH_REQUIRE_TOOLS}_argc_require_tools {}; or exit 1",
+            tools.join(" ")
+        )
+    }
+
+    fn warn(message: &str) -> String {
+        format!("printf '%s\\n' {} >&2", Self::quote(message))
+    }
+
+    fn error(message: &str, exit: i32) -> String {
+        format!("printf '%s\\n' {} >&2\nexit {exit}", Self::quote(message))
+    }
+}
+
+#[cfg(feature = "eval")]
+struct Powershell;
+
+#[cfg(feature = "eval")]
+impl ShellSyntax for Powershell {
+    fn quote(value: &str) -> String {
+        let mut output = String::from("'");
+        for c in value.chars() {
+            if matches!(c, '\'' | '\u{2018}' | '\u{2019}' | '\u{201a}' | '\u{201b}') {
+                output.push(c);
+            }
+            output.push(c);
+        }
+        output.push('\'');
+        output
+    }
+
+    fn assign(name: &str, value: &str) -> String {
+        format!("${name} = {value}")
+    }
+
+    fn assign_list(name: &str, values: &[String]) -> String {
+        Self::assign(name, &format!("@({})", values.join(", ")))
+    }
+
+    fn assign_map(name: &str, map: &IndexMap<String, Vec<String>>) -> String {
+        let pairs = map
+            .iter()
+            .map(|(k, v)| {
+                let v: Vec<String> = v.iter().map(|x| Self::quote(x)).collect();
+                format!("{} = @({})", Self::quote(k), v.join(", "))
+            })
+            .collect::<Vec<String>>()
+            .join("; ");
+        Self::assign(name, &format!("[ordered]@{{ {pairs} }}"))
+    }
+
+    fn export(name: &str, value: &str) -> String {
+        format!("$env:{name} = {value}")
+    }
+
+    fn capture(fn_name: &str) -> String {
+        format!("(& {})", Self::quote(fn_name))
+    }
+
+    fn call(fn_name: &str, args: &[String]) -> String {
+        Zsh::call(&format!("& {}", Self::quote(fn_name)), args)
+    }
+
+    fn exec(prog: &str, args: &[String]) -> String {
+        format!("{}\nexit $LASTEXITCODE", Self::call(prog, args))
+    }
+
+    fn load_dotenv(files: &str) -> String {
+        let files: Vec<String> = files.split_whitespace().map(Self::quote).collect();
+        format!(
+            "{POWERSHELL_LOAD_DOTENV}_argc_load_dotenv {}",
+            files.join(" ")
+        )
+    }
+
+    fn require_tools(tools: &[String]) -> String {
+        let tools: Vec<String> = tools.iter().map(|v| Self::quote(v)).collect();
+        format!(
+            "{POWERSHELL_REQUIRE_TOOLS}_argc_require_tools {}",
+            tools.join(" ")
+        )
+    }
+
+    fn warn(message: &str) -> String {
+        format!("[Console]::Error.WriteLine({})", Self::quote(message))
+    }
+
+    fn error(message: &str, exit: i32) -> String {
+        format!(
+            "[Console]::Error.WriteLine({})\nexit {exit}",
+            Self::quote(message)
+        )
+    }
+}
+
+#[cfg(feature = "eval")]
+fn nuon_string(value: &str) -> String {
+    let mut output = String::from("\"");
+    for c in value.chars() {
+        match c {
+            '"' => output.push_str("\\\""),
+            '\\' => output.push_str("\\\\"),
+            '\n' => output.push_str("\\n"),
+            '\r' => output.push_str("\\r"),
+            '\t' => output.push_str("\\t"),
+            c if c.is_control() => output.push_str(&format!("\\u{{{:x}}}", c as u32)),
+            c => output.push(c),
+        }
+    }
+    output.push('"');
+    output
+}
+
+#[cfg(feature = "eval")]
+fn nuon_list(values: &[String]) -> String {
+    let values: Vec<String> = values.iter().map(|v| nuon_string(v)).collect();
+    format!("[{}]", values.join(", "))
+}
+
+#[cfg(feature = "eval")]
+fn nuon_record(fields: &[(String, String)]) -> String {
+    let fields: Vec<String> = fields
+        .iter()
+        .map(|(k, v)| format!("{}: {v}", nuon_string(k)))
+        .collect();
+    format!("{{{}}}", fields.join(", "))
+}
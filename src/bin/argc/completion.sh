@@ -3,10 +3,17 @@
 # @option --argc-run~ <FILE> <ARGS>                 Run an argc-based script
 # @option --argc-build <FILE> <OUTPATH?>            Generate bashscript without argc dependency
 # @option --argc-mangen <FILE> <OUTDIR>             Generate man pages
+# @option --argc-docgen~ <FILE> <OUTDIR> <OPTIONS>  Generate markdown or html reference docs
+# @option --argc-lint~ <FILE> <OPTIONS>             Check a script and report problems as text or json
+# @option --argc-fmt~ <FILE> <OPTIONS>              Format the comment tags of a script
+# @flag --argc-lsp                                  Start a language server over stdio
 # @option --argc-completions <SHELL> <CMDS>         Generate shell completion scripts
+# @option --argc-build-completions~ <SHELL> <FILE> <CMDS>  Generate shell completion scripts without argc dependency
 # @option --argc-compgen <SHELL> <FILE> <ARGS>      Generate completion candidates
 # @option --argc-export <FILE>                      Export command line definitions as json
+# @option --argc-parse-json~ <FILE> <ARGS>          Parse arguments and print matched values as json
 # @option --argc-parallel~ <FILE> <ARGS>            Run functions in parallel
+# @option --argc-force~ <ARGS>                     Run the Argcfile, ignoring up-to-date checks
 # @flag --argc-script-path                          Print current argcfile path
 # @flag --argc-shell-path                           Print current shell path
 # @flag --argc-help                                 Print help information
@@ -1,4 +1,5 @@
 mod parallel;
+mod uptodate;
 
 use anyhow::{anyhow, bail, Context, Result};
 use argc::{compgen_kind, utils::*, CompKind, NativeRuntime, Runtime, Shell, COMPGEN_KIND_SYMBOL};
@@ -50,17 +51,25 @@ fn run() -> Result<i32> {
 
     if let Some(argc_cmd) = argc_cmd {
         match argc_cmd {
-            "--argc-eval" => match run_eval(runtime, args) {
-                Ok(output) => {
-                    println!("{output}");
-                }
-                Err(err) => {
-                    println!(
-                        "echo {}\nexit 1",
-                        escape_shell_words(&format!("Error: {err}"))
-                    );
+            "--argc-eval" => {
+                let mut args = args;
+                let shell = take_eval_shell(&mut args)?;
+                match run_eval(runtime, shell, args) {
+                    Ok(output) => {
+                        println!("{output}");
+                    }
+                    Err(err) if shell == Shell::Bash => {
+                        println!(
+                            "echo {}\nexit 1",
+                            escape_shell_words(&format!("Error: {err}"))
+                        );
+                    }
+                    Err(err) => {
+                        let values = [argc::ArgcValue::Error((format!("Error: {err}"), 1))];
+                        println!("{}", eval_values_to_shell(shell, &values));
+                    }
                 }
-            },
+            }
             "--argc-run" => {
                 if args.len() < 3 {
                     bail!("No script file provided");
@@ -157,6 +166,95 @@ fn run() -> Result<i32> {
                     println!("saved {}", outfile.display());
                 }
             }
+            "--argc-docgen" => {
+                let (source, _script_path, cmd_args) = parse_script_args(&args[2..])?;
+                let outdir = cmd_args.get(1).ok_or_else(|| anyhow!("No output dir"))?;
+                let mut format = argc::DocFormat::Markdown;
+                let mut single_page = false;
+                let mut rest = cmd_args[2..].iter();
+                while let Some(arg) = rest.next() {
+                    match arg.as_str() {
+                        "--format" => match rest.next() {
+                            Some(v) => format = v.parse()?,
+                            None => bail!(
+                                "The --format requires a value, must be one of {}",
+                                argc::DocFormat::list_names()
+                            ),
+                        },
+                        "--single-page" => single_page = true,
+                        _ => bail!("Unknown argument `{arg}` for --argc-docgen"),
+                    }
+                }
+                let pages = argc::docgen(&source, &cmd_args[0], format, single_page)?;
+                let outdir = ensure_outdir(outdir).with_context(|| "Invalid output dir")?;
+                for (filename, page) in pages {
+                    let outfile = outdir.join(filename);
+                    fs::write(&outfile, page)
+                        .with_context(|| format!("Failed to write '{}'", outfile.display()))?;
+                    println!("saved {}", outfile.display());
+                }
+            }
+            "--argc-lint" => {
+                let script_file = match args.get(2) {
+                    Some(v) => normalize_script_path(v),
+                    None => bail!("Usage: argc --argc-lint <SCRIPT> [--format <text|json>]"),
+                };
+                let json = match args.get(3).map(|v| v.as_str()) {
+                    None => false,
+                    Some("--format") => match args.get(4).map(|v| v.as_str()) {
+                        Some("text") => false,
+                        Some("json") => true,
+                        _ => bail!("The --format requires a value, must be one of text,json"),
+                    },
+                    Some(arg) => bail!("Unknown argument `{arg}` for --argc-lint"),
+                };
+                let source = fs::read_to_string(&script_file)
+                    .with_context(|| format!("Failed to load script at '{script_file}'"))?;
+                let diagnostics = argc::lint_with_includes(&source, &script_file)?;
+                if json {
+                    println!("{}", serde_json::to_string_pretty(&diagnostics)?);
+                } else {
+                    for diagnostic in &diagnostics {
+                        let file = diagnostic.file.as_deref().unwrap_or(&script_file);
+                        println!("{file}:{diagnostic}");
+                    }
+                }
+                if diagnostics
+                    .iter()
+                    .any(|v| v.severity == argc::Severity::Error)
+                {
+                    return Ok(1);
+                }
+            }
+            "--argc-fmt" => {
+                let (script_file, check) = match (args.get(2), args.get(3).map(|v| v.as_str())) {
+                    (Some(v), None) => (normalize_script_path(v), false),
+                    (Some(v), Some("--check")) => (normalize_script_path(v), true),
+                    _ => bail!("Usage: argc --argc-fmt <SCRIPT> [--check]"),
+                };
+                let source = fs::read_to_string(&script_file)
+                    .with_context(|| format!("Failed to load script at '{script_file}'"))?;
+                let formatted = argc::fmt(&source)?;
+                if check {
+                    if formatted != source {
+                        // Also reached when only line endings or the line count differ
+                        let line = source
+                            .split_inclusive('\n')
+                            .zip(formatted.split_inclusive('\n'))
+                            .take_while(|(old, new)| old == new)
+                            .count()
+                            + 1;
+                        println!("{script_file}:{line}: not formatted");
+                        return Ok(1);
+                    }
+                } else if formatted != source {
+                    fs::write(&script_file, formatted)
+                        .with_context(|| format!("Failed to write '{script_file}'"))?;
+                }
+            }
+            "--argc-lsp" => {
+                argc::serve_lsp(std::io::stdin().lock(), std::io::stdout().lock())?;
+            }
             "--argc-completions" => {
                 let shell: Shell = match args.get(2) {
                     Some(v) => v.parse()?,
@@ -166,6 +264,28 @@ fn run() -> Result<i32> {
                 let script = argc::generate_completions(shell, &commands);
                 print!("{script}");
             }
+            "--argc-build-completions" => {
+                let shell: Shell = match args.get(2) {
+                    Some(v) => v.parse()?,
+                    None => {
+                        bail!("Usage: argc --argc-build-completions <SHELL> <SCRIPT> [CMDS]...")
+                    }
+                };
+                let (source, script_path, cmd_args) = parse_script_args(&args[3..])?;
+                let commands = if cmd_args.len() > 1 {
+                    cmd_args[1..].to_vec()
+                } else {
+                    let script_name = get_script_name(&script_path)?;
+                    let mut commands = vec![cmd_args[0].clone()];
+                    if script_name != cmd_args[0] {
+                        commands.push(script_name.to_string());
+                    }
+                    commands
+                };
+                let script =
+                    argc::generate_standalone_completions(shell, &source, &cmd_args[0], &commands)?;
+                print!("{script}");
+            }
             "--argc-compgen" => {
                 run_compgen(runtime, args.to_vec());
             }
@@ -174,16 +294,36 @@ fn run() -> Result<i32> {
                 let value = argc::export(&source, &cmd_args[0])?;
                 println!("{}", serde_json::to_string_pretty(&value)?);
             }
+            "--argc-parse-json" => {
+                let (source, script_path, cmd_args) = parse_script_args(&args[2..])?;
+                let value = argc::eval_json(
+                    runtime,
+                    &source,
+                    &cmd_args,
+                    Some(&script_path),
+                    get_term_width(),
+                )?;
+                println!("{}", serde_json::to_string_pretty(&value)?);
+                if let Some(exit) = value["error"]["exit"].as_i64() {
+                    return Ok(exit as i32);
+                }
+            }
             "--argc-parallel" => {
-                if args.len() <= 3 {
-                    bail!("Usage: argc --argc-parallel <SCRIPT> <ARGS>...");
+                let mut options = parallel::ParallelOptions::default();
+                let script_idx = 2 + options.parse(&args[2..])?;
+                if args.len() <= script_idx + 1 {
+                    bail!("Usage: argc --argc-parallel [-j, --jobs <N>] [--fail-fast] <SCRIPT> <ARGS>...");
                 }
                 let shell = runtime.shell_path()?;
-                let (source, script_path, cmd_args) = parse_script_args(&args[2..])?;
+                let (source, script_path, cmd_args) = parse_script_args(&args[script_idx..])?;
                 if !source.contains("--argc-eval") {
                     bail!("Parallel only available for argc based scripts")
                 }
-                parallel::parallel(runtime, &shell, &script_path, &cmd_args[1..])?;
+                parallel::parallel(runtime, &shell, &script_path, &cmd_args[1..], &options)?;
+            }
+            "--argc-force" => {
+                let envs = HashMap::from([("ARGC_FORCE".to_string(), "1".to_string())]);
+                return run_argcfile(runtime, &args[2..], envs);
             }
             "--argc-script-path" => {
                 let (_, script_file) =
@@ -206,34 +346,55 @@ fn run() -> Result<i32> {
         }
         Ok(0)
     } else {
-        let shell = runtime.shell_path()?;
-        let (script_dir, script_file) = get_script_path(true)
-            .ok_or_else(|| anyhow!("Argcfile not found, try `argc --argc-help` for help"))?;
-        let mut envs = HashMap::new();
-        if let Some(cwd) = runtime.current_dir() {
-            if env::var("ARGC_PWD").is_err() {
-                envs.insert("ARGC_PWD".to_string(), cwd);
-            }
+        run_argcfile(runtime, &args[1..], HashMap::new())
+    }
+}
+
+fn run_argcfile(
+    runtime: NativeRuntime,
+    args: &[String],
+    mut envs: HashMap<String, String>,
+) -> Result<i32> {
+    let shell = runtime.shell_path()?;
+    let (script_dir, script_file) = get_script_path(true)
+        .ok_or_else(|| anyhow!("Argcfile not found, try `argc --argc-help` for help"))?;
+    if let Some(cwd) = runtime.current_dir() {
+        if env::var("ARGC_PWD").is_err() {
+            envs.insert("ARGC_PWD".to_string(), cwd);
         }
-        let script_file = script_file.display().to_string();
-        let args = [vec![&script_file], args[1..].iter().collect()].concat();
-        run_command(&script_file, &shell, &args, envs, Some(&script_dir))
     }
+    let script_file = script_file.display().to_string();
+    let args = [vec![&script_file], args.iter().collect()].concat();
+    run_command(&script_file, &shell, &args, envs, Some(&script_dir))
 }
 
-fn run_eval(runtime: NativeRuntime, args: Vec<String>) -> Result<String> {
-    let (source, _script_path, cmd_args) = parse_script_args(&args[2..])?;
-    let output = if cmd_args
+fn run_eval(runtime: NativeRuntime, shell: Shell, args: Vec<String>) -> Result<String> {
+    let (source, _script_path, cmd_args, includes) = parse_script_args_with_includes(&args[2..])?;
+    let is_parallel = cmd_args
         .get(1)
         .map(|v| v == parallel::PARALLEL_SYMBOL)
-        .unwrap_or_default()
-    {
+        .unwrap_or_default();
+    if shell != Shell::Bash {
+        if is_parallel {
+            bail!("Parallel commands are only supported in bash");
+        }
+        let values = argc::eval(
+            runtime,
+            &source,
+            &cmd_args,
+            Some(&args[2]),
+            get_term_width(),
+        )?;
+        return Ok(eval_values_to_shell(shell, &values));
+    }
+    let output = if is_parallel {
         let cmd_args_len = cmd_args.len();
         if cmd_args_len < 3 {
             bail!("No parallel command")
         }
         let bash_options = get_bash_options();
-        let mut code = retrieve_argc_variables().unwrap_or_default();
+        let mut code = source_includes(&includes);
+        code.push_str(&retrieve_argc_variables().unwrap_or_default());
         let mut cmds = vec![cmd_args[2].to_string()];
         cmds.extend(cmd_args[3..].iter().map(|v| escape_shell_words(v)));
         code.push_str(&cmds.join(" "));
@@ -248,13 +409,60 @@ fn run_eval(runtime: NativeRuntime, args: Vec<String>) -> Result<String> {
         )?;
         let bash_options = get_bash_options();
         let dir_vars = export_dir_vars(&args[2]);
-        let code = argc::ArgcValue::to_bash(&values);
+        let mut code = argc::ArgcValue::to_bash(&values);
+        if let Some(recipe) = uptodate::Recipe::new(&args[2], &values)? {
+            code = recipe.wrap_bash(&code);
+        }
         let export_vars = export_argc_variables(&code);
-        format!("{bash_options}{dir_vars}{export_vars}{code}")
+        let source_includes = source_includes(&includes);
+        format!("{bash_options}{dir_vars}{source_includes}{export_vars}{code}")
     };
     Ok(output)
 }
 
+/// Define the functions of the included scripts before the generated code runs
+fn source_includes(includes: &[PathBuf]) -> String {
+    includes
+        .iter()
+        .map(|v| {
+            let path = v.absolutize().unwrap_or(v.into()).display().to_string();
+            format!("source {}\n", escape_shell_words(&path))
+        })
+        .collect()
+}
+
+fn take_eval_shell(args: &mut Vec<String>) -> Result<Shell> {
+    if args.get(2).map(|v| v != "--shell").unwrap_or(true) {
+        return Ok(Shell::Bash);
+    }
+    let shell: Shell = args
+        .get(3)
+        .map(|v| v.as_str())
+        .unwrap_or_default()
+        .parse()?;
+    if !matches!(
+        shell,
+        Shell::Bash | Shell::Zsh | Shell::Fish | Shell::Powershell | Shell::Nushell
+    ) {
+        bail!(
+            "Unsupported shell `{}` for --argc-eval, must be one of bash,zsh,fish,powershell,nushell",
+            shell.name()
+        );
+    }
+    args.drain(2..4);
+    Ok(shell)
+}
+
+fn eval_values_to_shell(shell: Shell, values: &[argc::ArgcValue]) -> String {
+    match shell {
+        Shell::Zsh => argc::ArgcValue::to_zsh(values),
+        Shell::Fish => argc::ArgcValue::to_fish(values),
+        Shell::Powershell => argc::ArgcValue::to_powershell(values),
+        Shell::Nushell => argc::ArgcValue::to_nushell(values),
+        _ => argc::ArgcValue::to_bash(values),
+    }
+}
+
 fn run_command<T: AsRef<OsStr>>(
     script_path: &str,
     prog: &str,
@@ -448,6 +656,14 @@ fn get_argc_script_dir(path: &str) -> Option<String> {
 }
 
 fn parse_script_args(args: &[String]) -> Result<(String, String, Vec<String>)> {
+    let (source, script_file, cmd_args, _) = parse_script_args_with_includes(args)?;
+    Ok((source, script_file, cmd_args))
+}
+
+/// Like `parse_script_args`, also return the files spliced in by `@include` tags
+fn parse_script_args_with_includes(
+    args: &[String],
+) -> Result<(String, String, Vec<String>, Vec<PathBuf>)> {
     if args.is_empty() {
         bail!("No script file provided");
     }
@@ -456,11 +672,12 @@ fn parse_script_args(args: &[String]) -> Result<(String, String, Vec<String>)> {
     let args: Vec<String> = args[1..].to_vec();
     let source = fs::read_to_string(&script_file)
         .with_context(|| format!("Failed to load script at '{script_file}'"))?;
+    let (source, includes) = argc::resolve_includes(&source, &script_file)?;
     let name = get_script_name(&script_file)?;
     let name = name.strip_suffix(".sh").unwrap_or(name);
     let mut cmd_args = vec![name.to_string()];
     cmd_args.extend(args);
-    Ok((source, script_file, cmd_args))
+    Ok((source, script_file, cmd_args, includes))
 }
 
 fn get_script_name(script_path: &str) -> Result<&str> {
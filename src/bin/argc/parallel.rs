@@ -1,69 +1,321 @@
-use anyhow::Result;
+use anyhow::{anyhow, Result};
 use argc::{NativeRuntime, Runtime};
-use std::collections::HashMap;
-use std::process::{self, Command};
+use std::collections::{HashMap, HashSet};
+use std::env;
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::process::{self, Command, Stdio};
 use std::sync::mpsc::channel;
+#[cfg(unix)]
+use std::sync::{
+    atomic::{AtomicI32, Ordering},
+    OnceLock,
+};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
 use threadpool::ThreadPool;
 
 pub const PARALLEL_SYMBOL: &str = "___parallel___";
 
+const ARGC_PARALLEL_JOBS: &str = "ARGC_PARALLEL_JOBS";
+
+#[derive(Debug, Default)]
+pub struct ParallelOptions {
+    pub jobs: Option<usize>,
+    pub fail_fast: bool,
+}
+
+impl ParallelOptions {
+    /// Consume leading `-j/--jobs <N>` and `--fail-fast`, return the index of the script arg
+    pub fn parse(&mut self, args: &[String]) -> Result<usize> {
+        let mut i = 0;
+        while i < args.len() {
+            match args[i].as_str() {
+                "--fail-fast" => self.fail_fast = true,
+                "-j" | "--jobs" => {
+                    let value = args
+                        .get(i + 1)
+                        .ok_or_else(|| anyhow!("Missing value for '{}'", args[i]))?;
+                    self.jobs = Some(parse_jobs(value)?);
+                    i += 1;
+                }
+                arg => {
+                    if let Some(value) = arg.strip_prefix("--jobs=") {
+                        self.jobs = Some(parse_jobs(value)?);
+                    } else {
+                        break;
+                    }
+                }
+            }
+            i += 1;
+        }
+        Ok(i)
+    }
+
+    fn jobs_limit(&self) -> Result<usize> {
+        if let Some(jobs) = self.jobs {
+            return Ok(jobs);
+        }
+        match env::var(ARGC_PARALLEL_JOBS) {
+            Ok(value) if !value.is_empty() => parse_jobs(&value),
+            _ => Ok(num_cpus::get()),
+        }
+    }
+
+    /// Prefixed live output and the summary are turned on by passing any option
+    fn is_streaming(&self) -> bool {
+        self.jobs.is_some() || self.fail_fast
+    }
+}
+
+#[derive(Debug)]
+enum JobStatus {
+    Exited(i32),
+    Killed,
+    Skipped,
+    Failed(String),
+}
+
+#[derive(Debug, Default)]
+struct JobsState {
+    running: HashMap<usize, u32>,
+    killed: HashSet<usize>,
+    cancelled: bool,
+}
+
 pub fn parallel(
     runtime: NativeRuntime,
     shell: &str,
     script_file: &str,
     args: &[String],
+    options: &ParallelOptions,
 ) -> Result<()> {
     let jobs = to_jobs(args);
-    let jobs_len = jobs.len();
-    let pool = ThreadPool::new(num_cpus::get());
+    let pool = ThreadPool::new(options.jobs_limit()?);
     let (tx, rx) = channel();
+    let state = Arc::new(Mutex::new(JobsState::default()));
+    let fail_fast = options.fail_fast;
+    let streaming = options.is_streaming();
     let path_env = runtime.path_env_with_current_exe();
     let mut shell_extra_args = runtime.shell_args(shell);
     shell_extra_args.push(script_file.to_string());
     shell_extra_args.push(PARALLEL_SYMBOL.to_string());
-    for (i, job_args) in jobs.into_iter().enumerate() {
+    #[cfg(unix)]
+    forward_signals(jobs.len());
+    for (i, job_args) in jobs.iter().cloned().enumerate() {
         let tx = tx.clone();
-        let shell = shell.to_string();
-        let path_env = path_env.clone();
-        let shell_extra_args = shell_extra_args.clone();
+        let state = state.clone();
+        let mut command = Command::new(shell);
+        command
+            .args(&shell_extra_args)
+            .args(job_args)
+            .env("ARGC_PARALLEL", "1")
+            .env("PATH", &path_env)
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped());
+        #[cfg(unix)]
+        {
+            // Run each job in its own process group, so killing it also kills its children
+            use std::os::unix::process::CommandExt;
+            command.process_group(0);
+        }
+        let prefix = streaming.then(|| format!("[job{}] ", i + 1));
         pool.execute(move || {
-            Command::new(shell)
-                .args(shell_extra_args)
-                .args(job_args)
-                .env("ARGC_PARALLEL", "1")
-                .env("PATH", path_env)
-                .output()
-                .ok()
-                .and_then(|output| tx.send((i, output)).ok());
+            let start = Instant::now();
+            let (status, output) = run_job(i, command, &state, fail_fast, prefix);
+            let _ = tx.send((i, status, output, start.elapsed()));
         });
     }
     pool.join();
     drop(tx);
-    let mut job_outputs = HashMap::new();
+    let mut results: Vec<_> = rx.into_iter().collect();
+    results.sort_by_key(|(i, _, _, _)| *i);
     let mut exit = 0;
-    for (i, job_output) in rx {
-        if !job_output.status.success() {
+    let mut summary = vec!["Summary:".to_string()];
+    for (i, status, (stdout, stderr), elapsed) in results {
+        if !matches!(status, JobStatus::Exited(0)) {
             exit = 1;
         }
-        job_outputs.insert(
-            i,
-            (
-                String::from_utf8_lossy(&job_output.stdout).to_string(),
-                String::from_utf8_lossy(&job_output.stderr).to_string(),
-            ),
-        );
+        if !streaming {
+            let _ = io::stdout().write_all(&stdout);
+            let _ = io::stderr().write_all(&stderr);
+            if let JobStatus::Failed(err) = &status {
+                eprintln!("Failed to run '{}', {err}", jobs[i].join(" "));
+            }
+            continue;
+        }
+        let status = match status {
+            JobStatus::Exited(0) => format!("exit 0  {}", format_duration(elapsed)),
+            JobStatus::Exited(code) => format!("exit {code}  {}", format_duration(elapsed)),
+            JobStatus::Killed => format!("killed  {}", format_duration(elapsed)),
+            JobStatus::Skipped => "skipped".to_string(),
+            JobStatus::Failed(err) => format!("failed to run, {err}"),
+        };
+        summary.push(format!("  [job{}] {}  {status}", i + 1, jobs[i].join(" ")));
     }
-    for i in 0..jobs_len {
-        if let Some((stdout, stderr)) = job_outputs.get(&i) {
-            if !stdout.is_empty() {
-                print!("{stdout}")
+    if streaming {
+        eprintln!("{}", summary.join("\n"));
+    }
+    let _ = io::stdout().flush();
+    process::exit(exit)
+}
+
+fn run_job(
+    i: usize,
+    mut command: Command,
+    state: &Mutex<JobsState>,
+    fail_fast: bool,
+    prefix: Option<String>,
+) -> (JobStatus, (Vec<u8>, Vec<u8>)) {
+    let mut child = {
+        // Keep the lock while spawning, so a job can't slip past a fail-fast cancellation
+        let mut state = state.lock().unwrap();
+        if state.cancelled {
+            return (JobStatus::Skipped, Default::default());
+        }
+        match command.spawn() {
+            Ok(child) => {
+                state.running.insert(i, child.id());
+                set_job_group(i, child.id());
+                child
             }
-            if !stderr.is_empty() {
-                eprint!("{stderr}")
+            Err(err) => return (JobStatus::Failed(err.to_string()), Default::default()),
+        }
+    };
+    let readers = [
+        child
+            .stdout
+            .take()
+            .map(|v| forward_output(v, prefix.clone(), false)),
+        child
+            .stderr
+            .take()
+            .map(|v| forward_output(v, prefix.clone(), true)),
+    ];
+    let status = child.wait();
+    set_job_group(i, 0);
+    let [stdout, stderr] = readers.map(|v| v.and_then(|v| v.join().ok()).unwrap_or_default());
+    let output = (stdout, stderr);
+    let mut state = state.lock().unwrap();
+    state.running.remove(&i);
+    if state.killed.contains(&i) {
+        return (JobStatus::Killed, output);
+    }
+    let status = match status {
+        Ok(status) => JobStatus::Exited(status.code().unwrap_or(1)),
+        Err(err) => JobStatus::Failed(err.to_string()),
+    };
+    if fail_fast && !matches!(status, JobStatus::Exited(0)) && !state.cancelled {
+        state.cancelled = true;
+        let running: Vec<(usize, u32)> = state.running.iter().map(|(k, v)| (*k, *v)).collect();
+        for (job, pid) in running {
+            state.killed.insert(job);
+            kill_job(pid);
+        }
+    }
+    (status, output)
+}
+
+/// Forward output line by line, prefixing every line with the job name,
+/// or collect all of it to print after the jobs finish when there is no prefix
+fn forward_output<R: Read + Send + 'static>(
+    reader: R,
+    prefix: Option<String>,
+    is_stderr: bool,
+) -> thread::JoinHandle<Vec<u8>> {
+    thread::spawn(move || {
+        let mut reader = BufReader::new(reader);
+        let mut buf = vec![];
+        let Some(prefix) = prefix else {
+            let _ = reader.read_to_end(&mut buf);
+            return buf;
+        };
+        loop {
+            buf.clear();
+            match reader.read_until(b'\n', &mut buf) {
+                Ok(0) | Err(_) => break,
+                Ok(_) => {
+                    let line = String::from_utf8_lossy(&buf);
+                    let line = line.trim_end_matches(['\r', '\n']);
+                    if is_stderr {
+                        let _ = writeln!(io::stderr().lock(), "{prefix}{line}");
+                    } else {
+                        let _ = writeln!(io::stdout().lock(), "{prefix}{line}");
+                    }
+                }
+            }
+        }
+        vec![]
+    })
+}
+
+/// Process groups of the running jobs, indexed by job
+#[cfg(unix)]
+static JOB_GROUPS: OnceLock<Vec<AtomicI32>> = OnceLock::new();
+
+/// Jobs run outside the terminal's process group, so pass interrupts on to them
+#[cfg(unix)]
+fn forward_signals(jobs: usize) {
+    extern "C" fn handler(signal: libc::c_int) {
+        for group in JOB_GROUPS.get().into_iter().flatten() {
+            let pgid = group.load(Ordering::SeqCst);
+            if pgid > 0 {
+                unsafe {
+                    libc::kill(-pgid, signal);
+                }
             }
         }
+        unsafe {
+            libc::signal(signal, libc::SIG_DFL);
+            libc::raise(signal);
+        }
     }
-    process::exit(exit)
+    let _ = JOB_GROUPS.set((0..jobs).map(|_| AtomicI32::new(0)).collect());
+    for signal in [libc::SIGINT, libc::SIGTERM, libc::SIGHUP] {
+        unsafe {
+            libc::signal(signal, handler as *const () as libc::sighandler_t);
+        }
+    }
+}
+
+#[cfg(unix)]
+fn set_job_group(i: usize, pid: u32) {
+    if let Some(group) = JOB_GROUPS.get().and_then(|v| v.get(i)) {
+        group.store(pid as i32, Ordering::SeqCst);
+    }
+}
+
+#[cfg(not(unix))]
+fn set_job_group(_i: usize, _pid: u32) {}
+
+#[cfg(unix)]
+fn kill_job(pid: u32) {
+    unsafe {
+        libc::kill(-(pid as libc::pid_t), libc::SIGTERM);
+    }
+}
+
+#[cfg(not(unix))]
+fn kill_job(pid: u32) {
+    let _ = Command::new("taskkill")
+        .args(["/T", "/F", "/PID", &pid.to_string()])
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status();
+}
+
+fn parse_jobs(value: &str) -> Result<usize> {
+    match value.parse::<usize>() {
+        Ok(v) if v > 0 => Ok(v),
+        _ => Err(anyhow!(
+            "Invalid jobs value '{value}', expect a positive integer"
+        )),
+    }
+}
+
+fn format_duration(duration: Duration) -> String {
+    format!("{:.2}s", duration.as_secs_f64())
 }
 
 fn to_jobs(args: &[String]) -> Vec<Vec<String>> {
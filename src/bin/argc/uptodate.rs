@@ -0,0 +1,237 @@
+use anyhow::{Context, Result};
+use argc::{utils::escape_shell_words, ArgcValue};
+use path_absolutize::Absolutize;
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+};
+
+pub const ARGC_CACHE_FILE: &str = ".argc-cache";
+
+const ARGC_FORCE: &str = "ARGC_FORCE";
+
+/// A recipe that declares `@sources`, resolved against the script dir
+#[derive(Debug)]
+pub struct Recipe {
+    name: String,
+    key: String,
+    hash: String,
+    fresh: bool,
+    cache_file: PathBuf,
+}
+
+impl Recipe {
+    /// Lookup the recipe matched by `values`, return None if it declares no `@sources`
+    pub fn new(script_path: &str, values: &[ArgcValue]) -> Result<Option<Self>> {
+        let mut command_fn = None;
+        let mut recipe = None;
+        for value in values {
+            match value {
+                ArgcValue::CommandFn(name) => command_fn = Some(name),
+                ArgcValue::CommandRecipe(name, sources, generates) => {
+                    recipe = Some((name, sources, generates))
+                }
+                _ => {}
+            }
+        }
+        let (command_fn, (name, sources, generates)) = match (command_fn, recipe) {
+            (Some(command_fn), Some(recipe)) => (command_fn, recipe),
+            _ => return Ok(None),
+        };
+        let script_path = Path::new(script_path)
+            .absolutize()
+            .with_context(|| format!("Invalid script path '{script_path}'"))?
+            .to_path_buf();
+        let (script_dir, script_name) = match (script_path.parent(), script_path.file_name()) {
+            (Some(dir), Some(name)) => (dir, name.to_string_lossy().to_string()),
+            _ => return Ok(None),
+        };
+        let hash = hash_inputs(script_dir, sources, generates, values)?;
+        let key = format!("{script_name}:{command_fn}");
+        let cache_file = script_dir.join(ARGC_CACHE_FILE);
+        let force = env::var(ARGC_FORCE)
+            .map(|v| !v.is_empty() && v != "0")
+            .unwrap_or_default();
+        let fresh = !force
+            && generates.iter().all(|v| script_dir.join(v).exists())
+            && load_cache(&cache_file, &key).as_deref() == Some(hash.as_str());
+        Ok(Some(Self {
+            name: name.clone(),
+            key,
+            hash,
+            fresh,
+            cache_file,
+        }))
+    }
+
+    /// Wrap the generated code, skip it when the recipe is fresh or record the hash when it succeeds
+    pub fn wrap_bash(&self, code: &str) -> String {
+        if self.fresh {
+            return format!(
+                "echo {}",
+                escape_shell_words(&format!("{} is up to date", self.name))
+            );
+        }
+        let cache_file = escape_shell_words(&self.cache_file.display().to_string());
+        let key = escape_shell_words(&self.key);
+        let hash = escape_shell_words(&self.hash);
+        format!(
+            r#"{code}
+if [[ $? -eq 0 ]]; then
+    {{ [[ -f {cache_file} ]] && awk -F '\t' -v key={key} '$1 != key' {cache_file}; printf '%s\t%s\n' {key} {hash}; }} > {cache_file}.tmp && mv {cache_file}.tmp {cache_file}
+fi"#
+        )
+    }
+}
+
+fn load_cache(cache_file: &Path, key: &str) -> Option<String> {
+    let content = fs::read_to_string(cache_file).ok()?;
+    content.lines().find_map(|line| {
+        let (k, v) = line.split_once('\t')?;
+        (k == key).then(|| v.to_string())
+    })
+}
+
+/// Hash the matched arguments and the declared patterns together with the path and content of every matched file
+fn hash_inputs(
+    dir: &Path,
+    sources: &[String],
+    generates: &[String],
+    values: &[ArgcValue],
+) -> Result<String> {
+    let mut hasher = Fnv64::default();
+    for value in values {
+        match value {
+            ArgcValue::Single(..)
+            | ArgcValue::SingleFn(..)
+            | ArgcValue::Multiple(..)
+            | ArgcValue::Map(..)
+            | ArgcValue::PositionalSingle(..)
+            | ArgcValue::PositionalSingleFn(..)
+            | ArgcValue::PositionalMultiple(..)
+            | ArgcValue::ExtraPositionalMultiple(..)
+            | ArgcValue::Env(..)
+            | ArgcValue::EnvFn(..) => hasher.write(format!("{value:?}").as_bytes()),
+            _ => {}
+        }
+    }
+    for pattern in sources {
+        hasher.write(pattern.as_bytes());
+        for path in glob(dir, pattern) {
+            let data =
+                fs::read(&path).with_context(|| format!("Failed to read '{}'", path.display()))?;
+            hasher.write(path.to_string_lossy().as_bytes());
+            hasher.write(&data);
+        }
+    }
+    for path in generates {
+        hasher.write(path.as_bytes());
+    }
+    Ok(format!("{:016x}", hasher.0))
+}
+
+struct Fnv64(u64);
+
+impl Default for Fnv64 {
+    fn default() -> Self {
+        Self(0xcbf29ce484222325)
+    }
+}
+
+impl Fnv64 {
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes.iter().chain([&0xff]) {
+            self.0 ^= *byte as u64;
+            self.0 = self.0.wrapping_mul(0x100000001b3);
+        }
+    }
+}
+
+/// List files matched by a glob pattern, supports `*`, `?` and `**`; a directory matches all files beneath it
+fn glob(dir: &Path, pattern: &str) -> Vec<PathBuf> {
+    let (base, pattern) = match pattern.strip_prefix('/') {
+        Some(v) => (PathBuf::from("/"), v),
+        None => (dir.to_path_buf(), pattern),
+    };
+    let segments: Vec<&str> = pattern
+        .split(['/', '\\'])
+        .filter(|v| !v.is_empty() && *v != ".")
+        .collect();
+    let mut output = vec![];
+    glob_segments(&base, &segments, &mut output);
+    output.sort();
+    output.dedup();
+    output
+}
+
+fn glob_segments(dir: &Path, segments: &[&str], output: &mut Vec<PathBuf>) {
+    let Some((segment, rest)) = segments.split_first() else {
+        collect_files(dir, output);
+        return;
+    };
+    if *segment == "**" {
+        glob_segments(dir, rest, output);
+        for entry in read_dir(dir) {
+            if entry.is_dir() {
+                glob_segments(&entry, segments, output);
+            }
+        }
+    } else if !segment.contains(['*', '?']) {
+        let path = dir.join(segment);
+        if path.exists() {
+            glob_segments(&path, rest, output);
+        }
+    } else {
+        for entry in read_dir(dir) {
+            let matched = entry
+                .file_name()
+                .map(|v| wildcard_match(segment, &v.to_string_lossy()))
+                .unwrap_or_default();
+            if matched {
+                glob_segments(&entry, rest, output);
+            }
+        }
+    }
+}
+
+fn collect_files(path: &Path, output: &mut Vec<PathBuf>) {
+    if path.is_dir() {
+        for entry in read_dir(path) {
+            collect_files(&entry, output);
+        }
+    } else if path.is_file() {
+        output.push(path.to_path_buf());
+    }
+}
+
+fn read_dir(dir: &Path) -> Vec<PathBuf> {
+    let mut entries: Vec<PathBuf> = match fs::read_dir(dir) {
+        Ok(v) => v.filter_map(|v| v.ok().map(|v| v.path())).collect(),
+        Err(_) => vec![],
+    };
+    entries.sort();
+    entries
+}
+
+fn wildcard_match(pattern: &str, value: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let value: Vec<char> = value.chars().collect();
+    let (mut p, mut v) = (0, 0);
+    let mut backtrack = None;
+    while v < value.len() {
+        if p < pattern.len() && (pattern[p] == '?' || pattern[p] == value[v]) {
+            p += 1;
+            v += 1;
+        } else if p < pattern.len() && pattern[p] == '*' {
+            backtrack = Some((p, v));
+            p += 1;
+        } else if let Some((bp, bv)) = backtrack {
+            p = bp + 1;
+            v = bv + 1;
+            backtrack = Some((bp, bv + 1));
+        } else {
+            return false;
+        }
+    }
+    pattern[p..].iter().all(|v| *v == '*')
+}
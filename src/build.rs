@@ -1,13 +1,18 @@
 use crate::{
+    color::HelpPainter,
     command::Command,
-    param::{FlagOptionParam, Param, PositionalParam},
-    utils::{escape_shell_words, ARGC_LOAD_DOTENV, ARGC_REQUIRE_PARAMS, ARGC_REQUIRE_TOOLS},
-    ChoiceValue, DefaultValue,
+    locale::Messages,
+    param::{FlagOptionParam, Param, ParamData, PositionalParam},
+    utils::{
+        escape_shell_words, render_deprecated_warning, ARGC_LOAD_DOTENV, ARGC_PRINT_HELP,
+        ARGC_REQUIRE_PARAMS, ARGC_REQUIRE_TOOLS, META_ABBREVIATE_LONG_OPTIONS, META_COMBINE_SHORTS,
+    },
+    ChoiceValue, DefaultValue, ValueType,
 };
 use anyhow::Result;
 use indexmap::IndexSet;
 
-const UTIL_FNS: [(&str, &str); 6] = [
+const UTIL_FNS: [(&str, &str); 14] = [
     ("_argc_take_args", include_str!("template/take_args.sh")),
     (
         "_argc_match_positionals",
@@ -21,11 +26,63 @@ const UTIL_FNS: [(&str, &str); 6] = [
         "_argc_validate_choices",
         include_str!("template/validate_choices.sh"),
     ),
+    (
+        "_argc_resolve_choices",
+        include_str!("template/resolve_choices.sh"),
+    ),
+    (
+        "_argc_validate_type",
+        include_str!("template/validate_type.sh"),
+    ),
     ("_argc_check_bool", include_str!("template/check_bool.sh")),
     (
         "_argc_maybe_flag_option",
         include_str!("template/maybe_flag_option.sh"),
     ),
+    (
+        "_argc_check_conflicts",
+        include_str!("template/check_conflicts.sh"),
+    ),
+    (
+        "_argc_check_requires",
+        include_str!("template/check_requires.sh"),
+    ),
+    ("_argc_suggest", include_str!("template/suggest.sh")),
+    (
+        "_argc_split_shorts",
+        include_str!("template/split_shorts.sh"),
+    ),
+    ("_argc_abbreviate", include_str!("template/abbreviate.sh")),
+    ("_argc_print_help", ARGC_PRINT_HELP),
+];
+
+/// Messages used by the util functions, with the bash expressions of their args
+const UTIL_FN_MESSAGES: [(&str, &[(&str, &str)]); 13] = [
+    ("ambiguous-argument", &[("name", "$key")]),
+    ("possible-arguments", &[("names", "${list%, }")]),
+    (
+        "invalid-env",
+        &[("env", "$env_name"), ("name", "$param_name")],
+    ),
+    (
+        "conflicting-arguments",
+        &[("name", "$found"), ("other", "$name")],
+    ),
+    ("missing-requires", &[("name", "$name")]),
+    (
+        "unexpected-argument",
+        &[("name", "${argc__positionals[index]}")],
+    ),
+    (
+        "invalid-value",
+        &[("value", "$item"), ("target", "$render_name")],
+    ),
+    ("possible-values", &[("values", "$concated_choices")]),
+    ("expected", &[("expected", "$expected")]),
+    ("incorrect-values", &[("name", "$param")]),
+    ("similar-subcommand", &[("value", "$best")]),
+    ("similar-argument", &[("value", "$best")]),
+    ("similar-value", &[("value", "$best")]),
 ];
 
 pub fn build(source: &str, root_name: &str, wrap_width: Option<usize>) -> Result<String> {
@@ -61,7 +118,9 @@ pub fn build(source: &str, root_name: &str, wrap_width: Option<usize>) -> Result
 }
 
 fn build_root(cmd: &Command, wrap_width: Option<usize>) -> String {
-    let command = build_command(cmd, wrap_width);
+    let combine_shorts = cmd.has_metadata(META_COMBINE_SHORTS);
+    let abbreviate = cmd.has_metadata(META_ABBREVIATE_LONG_OPTIONS);
+    let command = build_command(cmd, wrap_width, combine_shorts, abbreviate);
     let (before_hook, after_hook) = cmd.exist_hooks();
     let before_hook = if before_hook {
         "\n    _argc_before"
@@ -72,7 +131,7 @@ fn build_root(cmd: &Command, wrap_width: Option<usize>) -> String {
     let mut util_fns = String::new();
     for (fn_name, util_fn) in UTIL_FNS {
         if command.contains(fn_name) || util_fns.contains(fn_name) {
-            util_fns.push_str(&format!("\n{}\n", util_fn.trim()));
+            util_fns.push_str(&format!("\n{}\n", localize_util_fn(cmd, util_fn.trim())));
         }
     }
     let dotenv = if let Some(value) = cmd.dotenv() {
@@ -85,16 +144,42 @@ fn build_root(cmd: &Command, wrap_width: Option<usize>) -> String {
         util_fns.push_str(&format!("\n{ARGC_REQUIRE_PARAMS}\n"));
     }
     let require_flag_options = if command.contains("_argc_required_flag_options") {
-        r#"
-    _argc_require_params "error: the following required arguments were not provided:" "${_argc_required_flag_options[@]}""#
+        let message = render_message(cmd, "missing-arguments", &[]);
+        format!(
+            r#"
+    _argc_require_params "{message}" "${{_argc_required_flag_options[@]}}""#
+        )
     } else {
-        ""
+        String::new()
     };
     let require_envs = if command.contains("_argc_required_envs") {
-        r#"
-    _argc_require_params "error: the following required environments were not provided:" "${_argc_required_envs[@]}""#
+        let message = render_message(cmd, "missing-environments", &[]);
+        format!(
+            r#"
+    _argc_require_params "{message}" "${{_argc_required_envs[@]}}""#
+        )
     } else {
-        ""
+        String::new()
+    };
+    let (save_args, restore_args) =
+        if command.contains("_argc_split_shorts") || command.contains("_argc_abbreviate") {
+            (
+                "\n    _argc_raw_args=(\"${argc__args[@]}\")",
+                "\n    argc__args=(\"${_argc_raw_args[@]}\")",
+            )
+        } else {
+            ("", "")
+        };
+    let (init_deps, run_deps) = if command.contains("argc__deps") {
+        (
+            "\n    argc__deps=()",
+            r#"
+        for _argc_dep in "${argc__deps[@]}"; do
+            "$_argc_dep"
+        done"#,
+        )
+    } else {
+        ("", "")
     };
     let require_tools = if command.contains("_argc_tools") {
         util_fns.push_str(&format!("\n{ARGC_REQUIRE_TOOLS}\n"));
@@ -111,7 +196,8 @@ fn build_root(cmd: &Command, wrap_width: Option<usize>) -> String {
 
 _argc_run() {{
     if [[ "${{1:-}}" == "___internal___" ]]; then
-        _argc_die "error: unsupported ___internal___ command"
+        _argc_run_internal "${{@:2}}"
+        exit
     fi
     if [[ "${{OS:-}}" == "Windows_NT" ]] && [[ -n "${{MSYSTEM:-}}" ]]; then
         set -o igncr
@@ -119,15 +205,35 @@ _argc_run() {{
     argc__args=("$(basename "$0" .sh)" "$@")
     argc__positionals=()
     _argc_index=1
-    _argc_len="${{#argc__args[@]}}"{dotenv}
+    _argc_len="${{#argc__args[@]}}"{dotenv}{save_args}
     _argc_required_flag_options=()
     _argc_required_envs=()
-    _argc_tools=()
-    _argc_parse{require_flag_options}{require_envs}{require_tools}{before_hook}
-    if [ -n "${{argc__fn:-}}" ]; then
+    _argc_tools=(){init_deps}
+    _argc_parse{restore_args}{require_flag_options}{require_envs}{require_tools}{before_hook}
+    if [ -n "${{argc__fn:-}}" ]; then{run_deps}
         $argc__fn "${{argc__positionals[@]}}"{after_hook}
     fi
 }}
+
+_argc_run_internal() {{
+    local fn="$1"
+    argc__args=("${{@:2}}")
+    if [[ "${{#argc__args[@]}}" -eq 0 ]]; then
+        argc__args=("$(basename "$0" .sh)")
+    fi
+    argc__positionals=()
+    _argc_index=1
+    _argc_len="${{#argc__args[@]}}"
+    _argc_required_flag_options=()
+    _argc_required_envs=()
+    _argc_tools=()
+    eval "$(
+        exec 3>&1 >/dev/null 2>&1
+        trap 'declare -p "${{!argc_@}}" >&3' EXIT
+        _argc_parse
+    )"
+    "$fn" "${{argc__positionals[@]}}"
+}}
 {command}{util_fns}
 _argc_die() {{
     if [[ $# -eq 0 ]]; then
@@ -144,7 +250,12 @@ _argc_run "$@"
     )
 }
 
-fn build_command(cmd: &Command, wrap_width: Option<usize>) -> String {
+fn build_command(
+    cmd: &Command,
+    wrap_width: Option<usize>,
+    combine_shorts: bool,
+    abbreviate: bool,
+) -> String {
     let suffix = if cmd.is_root() {
         String::new()
     } else {
@@ -152,12 +263,12 @@ fn build_command(cmd: &Command, wrap_width: Option<usize>) -> String {
     };
 
     let usage = {
-        let usage = cmd.render_help(wrap_width);
-        let usage = usage.trim();
+        let usage = cmd.render_help(wrap_width, HelpPainter::new(true));
+        let usage = usage.trim().replace('\x1b', "\\e");
         format!(
             r#"
 _argc_usage{suffix}() {{
-    cat <<-'EOF'
+    _argc_print_help <<-'EOF'
 {usage}
 EOF
     exit
@@ -179,19 +290,19 @@ _argc_version{suffix}() {{
         );
     }
 
-    let parse = build_parse(cmd, &suffix);
+    let parse = build_parse(cmd, &suffix, combine_shorts, abbreviate);
 
     let subcmds = cmd
         .subcommands
         .iter()
-        .map(|v| build_command(v, wrap_width))
+        .map(|v| build_command(v, wrap_width, combine_shorts, abbreviate))
         .collect::<Vec<String>>()
         .join("");
 
     format!(r#"{usage}{version}{parse}{subcmds}"#)
 }
 
-fn build_parse(cmd: &Command, suffix: &str) -> String {
+fn build_parse(cmd: &Command, suffix: &str, combine_shorts: bool, abbreviate: bool) -> String {
     let mut parse_help = {
         let help_flags = cmd.help_flags.join(" | ");
         format!(
@@ -225,12 +336,18 @@ fn build_parse(cmd: &Command, suffix: &str) -> String {
         let parses: Vec<String> = cmd
             .flag_option_params
             .iter()
-            .map(|param| build_parse_flag_option(param, &flag_option_signs))
+            .map(|param| build_parse_flag_option(cmd, param, &flag_option_signs))
             .collect();
         parses.join("")
     } else {
         String::new()
     };
+    let subcmd_names = cmd
+        .list_subcommand_names()
+        .iter()
+        .map(|v| escape_shell_words(v))
+        .collect::<Vec<String>>()
+        .join(" ");
     let parse_subcommands = if !cmd.subcommands.is_empty() {
         let mut parses: Vec<String> = cmd
             .subcommands
@@ -238,9 +355,15 @@ fn build_parse(cmd: &Command, suffix: &str) -> String {
             .map(|subcmd| {
                 let paths = subcmd.paths.join("_");
                 let names = subcmd.list_names().join(" | ");
+                let warning = match subcmd.deprecated() {
+                    Some(message) => {
+                        build_deprecated_warning("command", &subcmd.cmd_name(), message, 3)
+                    }
+                    None => String::new(),
+                };
                 format!(
                     r#"
-        {names})
+        {names}){warning}
             _argc_index=$((_argc_index + 1))
             _argc_action=_argc_parse_{paths}
             break
@@ -265,6 +388,13 @@ fn build_parse(cmd: &Command, suffix: &str) -> String {
             .collect::<Vec<String>>()
             .join("");
 
+        let invalid_value = render_message(
+            cmd,
+            "invalid-value",
+            &[("value", "$help_arg"), ("target", "\\`<command>\\`")],
+        );
+        let possible_values =
+            render_message(cmd, "possible-values", &[("values", "$_argc_subcmds")]);
         parses.push(format!(
                 r#"
         help)
@@ -274,7 +404,7 @@ fn build_parse(cmd: &Command, suffix: &str) -> String {
                 _argc_usage{suffix}
                 ;;
             *)
-                _argc_die "error: invalid value \`$help_arg\` for \`<command>\`"$'\n'"  [possible values: $_argc_subcmds]"
+                _argc_die "{invalid_value}"$'\n'"  {possible_values}$(_argc_suggest value "$help_arg" {subcmd_names})"
                 ;;
             esac
             ;;"#
@@ -286,15 +416,34 @@ fn build_parse(cmd: &Command, suffix: &str) -> String {
 
     let handle_unknown_flag_options = if !cmd.flag_option_params.is_empty() {
         let signs = flag_option_signs.iter().collect::<String>();
+        let flag_option_names = cmd
+            .all_flag_options()
+            .iter()
+            .flat_map(|v| v.list_names())
+            .map(|v| escape_shell_words(&v))
+            .collect::<Vec<String>>()
+            .join(" ");
+        let unexpected_argument =
+            render_message(cmd, "unexpected-argument", &[("name", "$_argc_key")]);
         format!(
             r#"
             if _argc_maybe_flag_option "{signs}" "$_argc_item"; then
-                _argc_die "error: unexpected argument \`$_argc_key\` found"
+                _argc_die "{unexpected_argument}$(_argc_suggest argument "$_argc_key" {flag_option_names})"
             fi"#,
         )
     } else {
         String::new()
     };
+    let handle_unknown_flag_options = if combine_shorts {
+        format!("{}{handle_unknown_flag_options}", build_split_shorts(cmd))
+    } else {
+        handle_unknown_flag_options
+    };
+    let handle_unknown_flag_options = if abbreviate {
+        format!("{}{handle_unknown_flag_options}", build_abbreviate(cmd))
+    } else {
+        handle_unknown_flag_options
+    };
     let parse_fallback = if !cmd.subcommands.is_empty() && cmd.positional_params.is_empty() {
         let name = cmd.full_name();
         if let Some(subcmd) = cmd.find_default_subcommand() {
@@ -309,10 +458,16 @@ fn build_parse(cmd: &Command, suffix: &str) -> String {
             ;;"#
             )
         } else {
+            let invalid_subcommand = render_message(
+                cmd,
+                "invalid-subcommand",
+                &[("cmd", &name), ("arg", "$_argc_item")],
+            );
+            let subcommands = render_message(cmd, "subcommands", &[("names", "$_argc_subcmds")]);
             format!(
                 r#"
         *){handle_unknown_flag_options}
-            _argc_die "error: \`{name}\` requires a subcommand but one was not provided"$'\n'"  [subcommands: $_argc_subcmds]"
+            _argc_die "{invalid_subcommand}"$'\n'"  {subcommands}$(_argc_suggest subcommand "$_argc_item" {subcmd_names})"
             ;;"#
             )
         }
@@ -339,6 +494,7 @@ fn build_parse(cmd: &Command, suffix: &str) -> String {
     };
 
     let flag_option_bind_envs = build_flag_option_bind_envs(cmd);
+    let param_relations = build_param_relations(cmd);
     let default_flag_options = build_default_flag_options(cmd);
     let required_flag_options = build_required_flag_options(cmd);
 
@@ -372,7 +528,7 @@ _argc_parse{suffix}() {{
         _argc_key="${{_argc_item%%=*}}"
         case "$_argc_key" in{combined_case}
         esac
-    done{flag_option_bind_envs}{default_flag_options}{required_flag_options}{require_tools}
+    done{flag_option_bind_envs}{param_relations}{default_flag_options}{required_flag_options}{require_tools}
     if [[ -n "${{_argc_action:-}}" ]]; then
         $_argc_action
     else{handle}
@@ -382,28 +538,67 @@ _argc_parse{suffix}() {{
     )
 }
 
-fn build_parse_flag_option(param: &FlagOptionParam, signs: &IndexSet<char>) -> String {
+fn build_parse_flag_option(
+    cmd: &Command,
+    param: &FlagOptionParam,
+    signs: &IndexSet<char>,
+) -> String {
     let names = param.list_names().join(" | ");
     let long_name = param.long_name();
     let var_name = param.var_name();
+    let multiple_occurrences = render_message(cmd, "multiple-occurrences", &[("name", &long_name)]);
+    let warning = match param.deprecated() {
+        Some(message) => {
+            let kind = if param.is_flag() { "flag" } else { "option" };
+            let warning = build_deprecated_warning(kind, &long_name, message, 4);
+            format!(
+                r#"
+            if [[ -z "${{{var_name}:-}}" ]]; then{warning}
+            fi"#
+            )
+        }
+        None => String::new(),
+    };
     if param.is_flag() {
         if param.id() == "help" || param.id() == "version" {
             return String::new();
         }
+        let no_flag_value = render_message(cmd, "no-flag-value", &[("name", &long_name)]);
+        if let Some(negated_name) = param.negated_name() {
+            let names = param
+                .list_names()
+                .into_iter()
+                .filter(|v| v != &negated_name)
+                .collect::<Vec<String>>()
+                .join(" | ");
+            return [(names, 1), (negated_name, 0)]
+                .into_iter()
+                .map(|(names, value)| {
+                    format!(
+                        r#"
+        {names})
+            if [[ "$_argc_item" == *=* ]]; then
+                _argc_die "{no_flag_value}"
+            fi{warning}
+            _argc_index=$((_argc_index + 1))
+            {var_name}={value}
+            ;;"#
+                    )
+                })
+                .collect();
+        }
         let variant = if param.multiple_occurs() {
             format!("{var_name}=$(({var_name} + 1))")
         } else {
-            format!(
-                r#"_argc_die "error: the argument \`{long_name}\` cannot be used multiple times""#
-            )
+            format!(r#"_argc_die "{multiple_occurrences}""#)
         };
 
         format!(
             r#"
         {names})
             if [[ "$_argc_item" == *=* ]]; then
-                _argc_die "error: flag \`{long_name}\` don't accept any value"
-            fi
+                _argc_die "{no_flag_value}"
+            fi{warning}
             _argc_index=$((_argc_index + 1))
             if [[ -n "${{{var_name}:-}}" ]]; then
                 {variant}
@@ -423,11 +618,20 @@ fn build_parse_flag_option(param: &FlagOptionParam, signs: &IndexSet<char>) -> S
             None => String::new(),
         };
         let render_name_notations = param.render_name_notations();
+        let incorrect_values =
+            render_message(cmd, "incorrect-values", &[("name", &render_name_notations)]);
         let render_first_notation = param.render_first_notation();
         let choice = build_choice(
             "{_argc_take_args_values[@]}",
             &format!("`{render_first_notation}`"),
-            param.choice(),
+            param.data(),
+            r#"_argc_take_args_values=("${_argc_resolve_choices_values[@]}")"#,
+            3,
+        );
+        let value_type = build_value_type(
+            "{_argc_take_args_values[@]}",
+            &format!("`{render_first_notation}`"),
+            param.value_type(),
             3,
         );
         let variant = if param.multiple_values() {
@@ -441,7 +645,7 @@ fn build_parse_flag_option(param: &FlagOptionParam, signs: &IndexSet<char>) -> S
             if [[ -z "${{{var_name}:-}}" ]]; then
                 {var_name}="${{_argc_take_args_values[0]:-}}"
             else
-                _argc_die "error: the argument \`{long_name}\` cannot be used multiple times"
+                _argc_die "{multiple_occurrences}"
             fi"#
             )
         };
@@ -450,7 +654,7 @@ fn build_parse_flag_option(param: &FlagOptionParam, signs: &IndexSet<char>) -> S
             let not_assigned = if min == 1 {
                 format!(
                     r#"
-                _argc_die "error: incorrect number of values for \`{render_name_notations}\`""#
+                _argc_die "{incorrect_values}""#
                 )
             } else {
                 r#"
@@ -474,12 +678,67 @@ fn build_parse_flag_option(param: &FlagOptionParam, signs: &IndexSet<char>) -> S
         format!(
             r#"
         {names}){code}
-            _argc_index=$((_argc_index + _argc_take_args_len + 1)){choice}{variant}
+            _argc_index=$((_argc_index + _argc_take_args_len + 1)){choice}{value_type}{warning}{variant}
             ;;"#
         )
     }
 }
 
+fn build_split_shorts(cmd: &Command) -> String {
+    let is_short = |name: &String| name.len() == 2 && name.starts_with('-') && name != "--";
+    let subcmds = cmd
+        .subcommands
+        .iter()
+        .flat_map(|v| v.list_names())
+        .filter(is_short)
+        .collect::<Vec<String>>();
+    let shorts = cmd
+        .flag_option_params
+        .iter()
+        .flat_map(|v| v.list_names())
+        .filter(is_short)
+        .collect::<Vec<String>>();
+    if subcmds.is_empty() && shorts.is_empty() {
+        return String::new();
+    }
+    let subcmds = escape_shell_words(&subcmds.join(" "));
+    let shorts = escape_shell_words(&shorts.join(" "));
+    format!(
+        r#"
+            if _argc_split_shorts "$_argc_item" {subcmds} {shorts}; then
+                argc__args=("${{argc__args[@]:0:_argc_index}}" "${{_argc_split_shorts_values[@]}}" "${{argc__args[@]:$((_argc_index + 1))}}")
+                _argc_len="${{#argc__args[@]}}"
+                continue
+            fi"#
+    )
+}
+
+fn build_abbreviate(cmd: &Command) -> String {
+    let mut names: Vec<String> = vec![];
+    for param in cmd.flag_option_params.iter().filter(|v| !v.prefixed()) {
+        names.push(param.long_name());
+        names.extend(param.negated_name());
+    }
+    for name in cmd.help_flags.iter().chain(cmd.version_flags.iter()) {
+        if !names.iter().any(|v| v == name) {
+            names.push(name.to_string());
+        }
+    }
+    let names = names
+        .iter()
+        .filter(|v| v.starts_with("--"))
+        .map(|v| escape_shell_words(v))
+        .collect::<Vec<String>>()
+        .join(" ");
+    format!(
+        r#"
+            if _argc_abbreviate "$_argc_item" {names}; then
+                argc__args[_argc_index]="$_argc_abbreviate_value"
+                continue
+            fi"#
+    )
+}
+
 fn build_handle(cmd: &Command, suffix: &str) -> String {
     if !cmd.subcommands.is_empty() && cmd.command_fn.is_none() && cmd.positional_params.is_empty() {
         return format!(
@@ -488,10 +747,21 @@ fn build_handle(cmd: &Command, suffix: &str) -> String {
         );
     }
     let set_argc_fn = match &cmd.command_fn {
-        Some(fn_name) => format!(
-            r#"
+        Some(fn_name) => {
+            let deps = if cmd.dep_fns.is_empty() {
+                String::new()
+            } else {
+                format!(
+                    r#"
+        argc__deps=({})"#,
+                    cmd.dep_fns.join(" ")
+                )
+            };
+            format!(
+                r#"{deps}
         argc__fn={fn_name}"#
-        ),
+            )
+        }
         None => String::new(),
     };
     let run_help = format!(
@@ -573,10 +843,30 @@ fn build_positionals(cmd: &Command) -> String {
             } else {
                 var_name.clone()
             };
+            let resolve_choice = if !multiple {
+                format!(
+                    r#"{var_name}="${{_argc_resolve_choices_values[0]}}"
+            argc__positionals[values_index]="${var_name}""#
+                )
+            } else if param.delimiter().is_none() {
+                format!(
+                    r#"{var_name}=("${{_argc_resolve_choices_values[@]}}")
+            argc__positionals=("${{argc__positionals[@]:0:values_index}}" "${{{var_name}[@]}}" "${{argc__positionals[@]:values_index+values_size}}")"#
+                )
+            } else {
+                format!(r#"{var_name}=("${{_argc_resolve_choices_values[@]}}")"#)
+            };
             let choice = build_choice(
                 &choice_variable,
                 &format!("`{render_value}`"),
-                param.choice(),
+                param.data(),
+                &resolve_choice,
+                3,
+            );
+            let value_type = build_value_type(
+                &choice_variable,
+                &format!("`{render_value}`"),
+                param.value_type(),
                 3,
             );
             let default = if param.default().is_some() {
@@ -590,15 +880,24 @@ fn build_positionals(cmd: &Command) -> String {
             };
 
             let required = if param.required() {
+                let missing_argument =
+                    render_message(cmd, "missing-argument", &[("name", &render_value)]);
                 format!(
                     r#"
-            _argc_die "error: the required arguments \`{render_value}\` were not provided""#
+            _argc_die "{missing_argument}""#
                 )
             } else {
                 String::new()
             };
 
-            let bind_env = build_positional_bind_env(param);
+            let bind_env = build_positional_bind_env(cmd, param);
+
+            let warning = match param.deprecated() {
+                Some(message) => {
+                    build_deprecated_warning("argument", param.notation(), message, 3)
+                }
+                None => String::new(),
+            };
 
             let handle_nonexist = format!("{default}{required}");
             let handle_nonexist = if !handle_nonexist.is_empty() {
@@ -612,7 +911,7 @@ fn build_positionals(cmd: &Command) -> String {
             format!(
                 r#"
         IFS=: read -r values_index values_size <<<"${{_argc_match_positionals_values[{index}]:-}}"
-        if [[ -n "$values_index" ]]; then{variant}{choice}{bind_env}{handle_nonexist}
+        if [[ -n "$values_index" ]]; then{warning}{variant}{choice}{value_type}{bind_env}{handle_nonexist}
         fi"#
             )
         })
@@ -632,16 +931,25 @@ fn build_flag_option_bind_envs(cmd: &Command) -> String {
             let var_name = param.var_name();
             let render_name = param.render_name_notations();
             let code = if param.is_flag() {
+                let negated = if param.negatable() {
+                    format!(
+                        r#"
+        else
+            {var_name}=0"#
+                    )
+                } else {
+                    String::new()
+                };
                 format!(
                     r#"
     if [[ -z "${{{var_name}:-}}" ]] && [[ -n "${{{env_name}:-}}" ]]; then
         if _argc_check_bool {env_name} "{render_name}"; then
-            {var_name}=1
+            {var_name}=1{negated}
         fi
     fi"#
                 )
             } else {
-                let handle_bind_env = build_handle_bind_env(param, &render_name, 2);
+                let handle_bind_env = build_handle_bind_env(cmd, param, &render_name, 2);
                 format!(
                     r#"
     if [[ -z "${{{var_name}:-}}" ]] && [[ -n "${{{env_name}:-}}" ]]; then{handle_bind_env}
@@ -654,11 +962,11 @@ fn build_flag_option_bind_envs(cmd: &Command) -> String {
     output.join("")
 }
 
-fn build_positional_bind_env(param: &PositionalParam) -> String {
+fn build_positional_bind_env(cmd: &Command, param: &PositionalParam) -> String {
     match param.bind_env() {
         None => String::new(),
         Some(env_name) => {
-            let handle_bind_env = build_handle_bind_env(param, &param.render_notation(), 3);
+            let handle_bind_env = build_handle_bind_env(cmd, param, &param.render_notation(), 3);
             format!(
                 r#"
         elif [[ -n "${{{env_name}:-}}" ]]; then{handle_bind_env}
@@ -668,9 +976,18 @@ fn build_positional_bind_env(param: &PositionalParam) -> String {
     }
 }
 
-fn build_handle_bind_env<T: Param>(param: &T, render_name: &str, indent_level: usize) -> String {
+fn build_handle_bind_env<T: Param>(
+    cmd: &Command,
+    param: &T,
+    render_name: &str,
+    indent_level: usize,
+) -> String {
     let indent = build_indent(indent_level);
     let env_name = param.bind_env().unwrap_or_default();
+    let target_name = cmd.message(
+        "bind-env-target",
+        &[("env", &env_name), ("name", render_name)],
+    );
     let var_name = param.var_name();
     let split_env = match param.delimiter() {
         Some(delimiter) => format!(
@@ -685,8 +1002,15 @@ fn build_handle_bind_env<T: Param>(param: &T, render_name: &str, indent_level: u
 
     let choice = build_choice(
         "{_argc_env_values[@]}",
-        &format!(r#"environment variable `{env_name}` that bound to `{render_name}`"#),
-        param.choice(),
+        &target_name,
+        param.data(),
+        r#"_argc_env_values=("${_argc_resolve_choices_values[@]}")"#,
+        indent_level,
+    );
+    let value_type = build_value_type(
+        "{_argc_env_values[@]}",
+        &target_name,
+        param.value_type(),
         indent_level,
     );
 
@@ -701,7 +1025,53 @@ fn build_handle_bind_env<T: Param>(param: &T, render_name: &str, indent_level: u
 {indent}{var_name}="${{_argc_env_values[0]}}""#
         )
     };
-    format!(r#"{indent}{split_env}{choice}{variant}"#)
+    format!(r#"{indent}{split_env}{choice}{value_type}{variant}"#)
+}
+
+fn build_param_relations(cmd: &Command) -> String {
+    let mut output = vec![];
+    for (ids, _) in &cmd.conflicts {
+        let values = ids
+            .iter()
+            .filter_map(|id| {
+                let param = cmd.find_flag_option_by_id(id)?;
+                Some(format!(
+                    "{} {}",
+                    escape_shell_words(&param.long_name()),
+                    param.var_name()
+                ))
+            })
+            .collect::<Vec<String>>()
+            .join(" ");
+        output.push(format!(
+            r#"
+    _argc_check_conflicts {values}"#
+        ));
+    }
+    for (id, required_ids, _) in &cmd.requires {
+        let Some(param) = cmd.find_flag_option_by_id(id) else {
+            continue;
+        };
+        let values = required_ids
+            .iter()
+            .filter_map(|id| cmd.find_flag_option_by_id(id))
+            .map(|param| {
+                escape_shell_words(&format!(
+                    "{}:{}",
+                    param.var_name(),
+                    param.render_name_notations()
+                ))
+            })
+            .collect::<Vec<String>>()
+            .join(" ");
+        output.push(format!(
+            r#"
+    _argc_check_requires {} {} {values}"#,
+            escape_shell_words(&param.long_name()),
+            param.var_name()
+        ));
+    }
+    output.join("")
 }
 
 fn build_required_flag_options(cmd: &Command) -> String {
@@ -789,11 +1159,17 @@ fn build_envs(cmd: &Command) -> String {
         .map(|param| {
             let var_name = param.var_name();
             let default = build_default(&format!("export {var_name}"), param.default(), 3);
-            let choice = build_choice(
-                &var_name,
-                &format!(r#"environment variable `{var_name}`"#),
-                param.choice(),
-                3,
+            let target_name = cmd.message("env-target", &[("env", &var_name)]);
+            let choice = format!(
+                "{}{}",
+                build_choice(
+                    &var_name,
+                    &target_name,
+                    param.data(),
+                    &format!(r#"{var_name}="${{_argc_resolve_choices_values[0]}}""#),
+                    3,
+                ),
+                build_value_type(&var_name, &target_name, param.value_type(), 3,)
             );
             if default.is_empty() && choice.is_empty() {
                 String::new()
@@ -822,6 +1198,12 @@ fn build_envs(cmd: &Command) -> String {
         .join("")
 }
 
+fn build_deprecated_warning(kind: &str, name: &str, message: &str, indent_level: usize) -> String {
+    let indent = build_indent(indent_level);
+    let warning = escape_shell_words(&render_deprecated_warning(kind, name, message));
+    format!("\n{indent}printf '%s\\n' {warning} >&2")
+}
+
 fn build_default(var_name: &str, value: Option<&DefaultValue>, indent_level: usize) -> String {
     let indent = build_indent(indent_level);
     match value {
@@ -839,12 +1221,41 @@ fn build_default(var_name: &str, value: Option<&DefaultValue>, indent_level: usi
 fn build_choice(
     variable: &str,
     target_name: &str,
-    choice: Option<&ChoiceValue>,
+    data: &ParamData,
+    resolve: &str,
     indent: usize,
 ) -> String {
     let indent = build_indent(indent);
-    match choice {
+    match data.choice.as_ref() {
         Some(value) => match value {
+            ChoiceValue::Values(values)
+                if data.choice_ignore_case || !data.choice_aliases.is_empty() =>
+            {
+                let values = values
+                    .iter()
+                    .map(|v| escape_shell_words(v))
+                    .collect::<Vec<String>>()
+                    .join(" ");
+                let aliases = if data.choice_aliases.is_empty() {
+                    r#""""#.to_string()
+                } else {
+                    let aliases = data
+                        .choice_aliases
+                        .iter()
+                        .flat_map(|(alias, target)| {
+                            [escape_shell_words(alias), escape_shell_words(target)]
+                        })
+                        .collect::<Vec<String>>()
+                        .join(" ");
+                    format!(r#""$(printf "%s\n" {aliases})""#)
+                };
+                let ignore_case = if data.choice_ignore_case { 1 } else { 0 };
+                format!(
+                    r#"
+{indent}_argc_resolve_choices '{target_name}' "$(printf "%s\n" {values})" {aliases} {ignore_case} "${variable}"
+{indent}{resolve}"#
+                )
+            }
             ChoiceValue::Values(values) => {
                 let values = values
                     .iter()
@@ -871,6 +1282,53 @@ fn build_choice(
     }
 }
 
+fn build_value_type(
+    variable: &str,
+    target_name: &str,
+    value_type: Option<&ValueType>,
+    indent: usize,
+) -> String {
+    let indent = build_indent(indent);
+    match value_type {
+        Some(value_type) => format!(
+            r#"
+{indent}_argc_validate_type '{target_name}' {} {} "${variable}""#,
+            escape_shell_words(&value_type.render()),
+            escape_shell_words(&value_type.expected()),
+        ),
+        None => String::new(),
+    }
+}
+
 fn build_indent(indent_level: usize) -> String {
     "    ".repeat(indent_level)
 }
+
+/// Render a message into a double-quoted bash string, the args are inserted as is
+fn render_message(cmd: &Command, key: &str, args: &[(&str, &str)]) -> String {
+    cmd.messages().render_with(key, args, escape_double_quoted)
+}
+
+/// Replace the English messages of an util function with the messages of `cmd`
+fn localize_util_fn(cmd: &Command, util_fn: &str) -> String {
+    let english = Messages::default();
+    let mut output = util_fn.to_string();
+    for (key, args) in UTIL_FN_MESSAGES {
+        let text = english.render_with(key, args, escape_double_quoted);
+        if output.contains(&text) {
+            output = output.replace(&text, &render_message(cmd, key, args));
+        }
+    }
+    output
+}
+
+fn escape_double_quoted(text: &str) -> String {
+    let mut output = String::new();
+    for ch in text.chars() {
+        if matches!(ch, '\\' | '"' | '`' | '$') {
+            output.push('\\');
+        }
+        output.push(ch);
+    }
+    output
+}
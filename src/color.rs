@@ -0,0 +1,300 @@
+#[cfg(any(feature = "build", feature = "eval"))]
+use crate::locale::Messages;
+use anyhow::{bail, Result};
+use std::fmt;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct CompColor {
+    pub(crate) code: ColorCode,
+    pub(crate) style: ColorStyle,
+}
+
+impl CompColor {
+    pub(crate) fn new(code: ColorCode, style: ColorStyle) -> Self {
+        Self { code, style }
+    }
+
+    pub(crate) fn parse(s: &str) -> Result<Self> {
+        if let Some((code, style)) = s.split_once(',') {
+            if let (Ok(code), Ok(style)) = (code.parse(), style.parse()) {
+                return Ok(Self::new(code, style));
+            }
+        } else if let Ok(code) = s.parse() {
+            return Ok(Self::new(code, ColorStyle::Regular));
+        }
+        bail!("Invalid CompColor value")
+    }
+
+    pub(crate) fn ser(&self) -> String {
+        if self.style == ColorStyle::Regular {
+            format!("{}", self.code)
+        } else {
+            format!("{},{}", self.code, self.style)
+        }
+    }
+
+    pub(crate) fn ansi_code(&self) -> String {
+        let mut ret = if self.style != ColorStyle::Regular {
+            format!("{};", self.style.ansi_code())
+        } else {
+            String::new()
+        };
+        ret.push_str(self.code.ansi_code());
+        ret
+    }
+
+    pub(crate) fn style(&self) -> String {
+        let mut ret = self.code.to_string();
+        if self.style != ColorStyle::Regular {
+            ret.push_str(&format!(" {}", self.style))
+        }
+        ret
+    }
+
+    pub(crate) fn of_flag() -> Self {
+        Self::new(ColorCode::Cyan, ColorStyle::Regular)
+    }
+
+    pub(crate) fn of_option() -> Self {
+        Self::new(ColorCode::Cyan, ColorStyle::Bold)
+    }
+
+    pub(crate) fn of_command() -> Self {
+        Self::new(ColorCode::Magenta, ColorStyle::Regular)
+    }
+
+    pub(crate) fn of_dir() -> Self {
+        Self::new(ColorCode::Blue, ColorStyle::Bold)
+    }
+
+    pub(crate) fn of_file() -> Self {
+        Self::new(ColorCode::Default, ColorStyle::Regular)
+    }
+
+    pub(crate) fn of_file_exe() -> Self {
+        Self::new(ColorCode::Green, ColorStyle::Bold)
+    }
+
+    pub(crate) fn of_symlink() -> Self {
+        Self::new(ColorCode::Cyan, ColorStyle::Bold)
+    }
+
+    pub(crate) fn of_value() -> Self {
+        Self::new(ColorCode::Default, ColorStyle::Regular)
+    }
+
+    pub(crate) fn of_heading() -> Self {
+        Self::new(ColorCode::Default, ColorStyle::Bold)
+    }
+
+    pub(crate) fn of_placeholder() -> Self {
+        Self::new(ColorCode::Green, ColorStyle::Regular)
+    }
+
+    pub(crate) fn of_default() -> Self {
+        Self::new(ColorCode::Yellow, ColorStyle::Regular)
+    }
+
+    pub(crate) fn paint(&self, text: &str) -> String {
+        if text.is_empty() {
+            return String::new();
+        }
+        format!("\x1b[{}m{text}\x1b[0m", self.ansi_code())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ColorCode {
+    Black,
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+    White,
+    Default,
+}
+
+impl FromStr for ColorCode {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s {
+            "black" => Ok(Self::Black),
+            "red" => Ok(Self::Red),
+            "green" => Ok(Self::Green),
+            "yellow" => Ok(Self::Yellow),
+            "blue" => Ok(Self::Blue),
+            "magenta" => Ok(Self::Magenta),
+            "cyan" => Ok(Self::Cyan),
+            "white" => Ok(Self::White),
+            "default" => Ok(Self::Default),
+            _ => bail!("Invalid ColorCode value"),
+        }
+    }
+}
+
+impl fmt::Display for ColorCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Black => write!(f, "black"),
+            Self::Red => write!(f, "red"),
+            Self::Green => write!(f, "green"),
+            Self::Yellow => write!(f, "yellow"),
+            Self::Blue => write!(f, "blue"),
+            Self::Magenta => write!(f, "magenta"),
+            Self::Cyan => write!(f, "cyan"),
+            Self::White => write!(f, "white"),
+            Self::Default => write!(f, "default"),
+        }
+    }
+}
+
+impl ColorCode {
+    pub fn ansi_code(&self) -> &str {
+        match self {
+            Self::Black => "30",
+            Self::Red => "31",
+            Self::Green => "32",
+            Self::Yellow => "33",
+            Self::Blue => "34",
+            Self::Magenta => "35",
+            Self::Cyan => "36",
+            Self::White => "37",
+            Self::Default => "39",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ColorStyle {
+    Regular,
+    Bold,
+}
+
+impl FromStr for ColorStyle {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s {
+            "regular" => Ok(Self::Regular),
+            "bold" => Ok(Self::Bold),
+            _ => bail!("Invalid CodeStyle value"),
+        }
+    }
+}
+
+impl fmt::Display for ColorStyle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Regular => write!(f, "regular"),
+            Self::Bold => write!(f, "bold"),
+        }
+    }
+}
+
+impl ColorStyle {
+    pub fn ansi_code(&self) -> &str {
+        match self {
+            Self::Regular => "0",
+            Self::Bold => "1",
+        }
+    }
+}
+
+/// Paint the parts of the help text, does nothing if color is disabled
+#[cfg(any(feature = "build", feature = "eval"))]
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct HelpPainter {
+    pub(crate) color: bool,
+}
+
+#[cfg(any(feature = "build", feature = "eval"))]
+impl HelpPainter {
+    pub(crate) fn new(color: bool) -> Self {
+        Self { color }
+    }
+
+    pub(crate) fn heading(&self, text: &str) -> String {
+        self.paint(CompColor::of_heading(), text)
+    }
+
+    pub(crate) fn command(&self, text: &str) -> String {
+        self.paint(CompColor::of_command(), text)
+    }
+
+    pub(crate) fn placeholder(&self, text: &str) -> String {
+        self.paint(CompColor::of_placeholder(), text)
+    }
+
+    /// Paint the names in `-f, --foo <VALUE>` as options and the rest as placeholders
+    pub(crate) fn flag_option(&self, body: &str) -> String {
+        if !self.color {
+            return body.to_string();
+        }
+        body.split(' ')
+            .map(|token| {
+                if !token.starts_with(['-', '+']) {
+                    return self.placeholder(token);
+                }
+                let (name, rest) = token.split_at(token.find(['=', ',']).unwrap_or(token.len()));
+                let rest = match rest.strip_prefix('=') {
+                    Some(value) => format!("={}", self.placeholder(value)),
+                    None => rest.to_string(),
+                };
+                format!("{}{rest}", self.paint(CompColor::of_option(), name))
+            })
+            .collect::<Vec<String>>()
+            .join(" ")
+    }
+
+    /// Paint the `[default: ...]` notes in the description
+    pub(crate) fn describe(&self, text: &str, messages: &Messages) -> String {
+        if !self.color {
+            return text.to_string();
+        }
+        let note = messages.get("default-value");
+        let prefix = &note[..note.find("{value}").unwrap_or(note.len())];
+        if prefix.is_empty() {
+            return text.to_string();
+        }
+        let mut output = String::new();
+        let mut rest = text;
+        while let Some(start) = rest.find(prefix) {
+            let Some(len) = rest[start..].find(']') else {
+                break;
+            };
+            output.push_str(&rest[..start]);
+            output.push_str(&self.paint(CompColor::of_default(), &rest[start..start + len + 1]));
+            rest = &rest[start + len + 1..];
+        }
+        output.push_str(rest);
+        output
+    }
+
+    fn paint(&self, color: CompColor, text: &str) -> String {
+        if self.color {
+            color.paint(text)
+        } else {
+            text.to_string()
+        }
+    }
+}
+
+/// Length of the text without the ANSI escape sequences
+pub(crate) fn visible_len(text: &str) -> usize {
+    let mut len = 0;
+    let mut escaped = false;
+    for ch in text.chars() {
+        if escaped {
+            escaped = !ch.is_ascii_alphabetic();
+        } else if ch == '\x1b' {
+            escaped = true;
+        } else {
+            len += ch.len_utf8();
+        }
+    }
+    len
+}
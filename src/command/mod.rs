@@ -5,6 +5,10 @@ use self::names_checker::NamesChecker;
 use self::share_data::ShareData;
 
 use crate::argc_value::ArgcValue;
+use crate::color::visible_len;
+#[cfg(any(feature = "build", feature = "eval"))]
+use crate::color::HelpPainter;
+use crate::locale::Messages;
 #[cfg(feature = "eval")]
 use crate::matcher::Matcher;
 use crate::param::{EnvParam, FlagOptionParam, Param, PositionalParam};
@@ -13,19 +17,22 @@ use crate::param::{EnvValue, FlagOptionValue, PositionalValue};
 use crate::parser::{parse, parse_symbol, Event, EventData, EventScope, Position};
 use crate::runtime::Runtime;
 use crate::utils::{
-    AFTER_HOOK, BEFORE_HOOK, MAIN_NAME, META_BINNAME, META_COMBINE_SHORTS, META_DEFAULT_SUBCOMMAND,
-    META_DOTENV, META_INHERIT_FLAG_OPTIONS, META_REQUIRE_TOOLS, META_SYMBOL, META_VERSION,
-    ROOT_NAME,
+    AFTER_HOOK, BEFORE_HOOK, MAIN_NAME, META_ABBREVIATE_LONG_OPTIONS, META_ARGFILE, META_BINNAME,
+    META_COMBINE_SHORTS, META_DEFAULT_SUBCOMMAND, META_DEPRECATED, META_DOTENV, META_HELP_TEMPLATE,
+    META_HIDDEN, META_INHERIT_FLAG_OPTIONS, META_LOCALE, META_MESSAGE, META_REQUIRE_TOOLS,
+    META_SYMBOL, META_VERSION, ROOT_NAME,
 };
 use crate::Result;
 
 use anyhow::{anyhow, bail};
 use indexmap::{IndexMap, IndexSet};
 use serde::Serialize;
-use std::cell::RefCell;
+use std::cell::{Ref, RefCell};
 use std::collections::HashMap;
 use std::sync::Arc;
 
+type RecipeDeps = Vec<(Vec<String>, Position)>;
+
 #[derive(Debug, Clone)]
 pub(crate) struct ExternalSubcommandInfo {
     pub name: String,
@@ -49,11 +56,23 @@ pub(crate) struct Command {
     pub(crate) subcommand_fns: HashMap<String, Position>,
     pub(crate) default_subcommand: Option<(usize, Position)>,
     pub(crate) aliases: Option<(Vec<String>, Position)>,
+    pub(crate) group: Option<String>,
+    pub(crate) deps: Vec<(Vec<String>, Position)>,
+    pub(crate) dep_fns: Vec<String>,
+    pub(crate) sources: Vec<String>,
+    pub(crate) generates: Vec<String>,
+    // (command line, describe, position)
+    pub(crate) examples: Vec<(String, String, Position)>,
     pub(crate) version: Option<String>,
+    pub(crate) help_template: Option<String>,
     pub(crate) names_checker: NamesChecker,
     pub(crate) share: Arc<RefCell<ShareData>>,
     // (key, value, position)
     pub(crate) metadata: Vec<(String, String, Position)>,
+    // (ids, position)
+    pub(crate) conflicts: Vec<(Vec<String>, Position)>,
+    // (id, required ids, position)
+    pub(crate) requires: Vec<(String, Vec<String>, Position)>,
     pub(crate) symbols: IndexMap<char, SymbolParam>,
     pub(crate) require_tools: IndexSet<String>,
     pub(crate) help_flags: Vec<&'static str>,
@@ -63,8 +82,20 @@ pub(crate) struct Command {
 
 impl Command {
     pub(crate) fn new(source: &str, root_name: &str) -> Result<Self> {
+        Self::new_with_locale(source, root_name, None)
+    }
+
+    /// Like `new`, `locale` picks the messages unless `@meta locale` is set
+    pub(crate) fn new_with_locale(
+        source: &str,
+        root_name: &str,
+        locale: Option<&str>,
+    ) -> Result<Self> {
         let events = parse(source)?;
         let mut root = Command::new_from_events(&events)?;
+        if let Some(locale) = locale.filter(|_| !root.has_metadata(META_LOCALE)) {
+            root.share.borrow_mut().messages.set_locale(locale);
+        }
         root.share.borrow_mut().root_name = root
             .get_metadata(META_BINNAME)
             .map(|v| v.to_string())
@@ -74,6 +105,11 @@ impl Command {
             root.inherit_flag_options();
         }
         root.inherit_envs();
+        root.inherit_help_template();
+        root.resolve_param_relations()?;
+        let mut recipes = IndexMap::new();
+        root.collect_recipes(&mut recipes);
+        root.resolve_deps(&recipes)?;
         root.propagate_cmd_name_to_params();
         Ok(root)
     }
@@ -101,16 +137,95 @@ impl Command {
             arg_values.push(ArgcValue::ParamFn(args[2].clone()));
             return Ok(arg_values);
         }
-        let mut matcher = Matcher::new(runtime, self, args, false);
+        let args = match self.expand_argfiles(runtime, args) {
+            Ok(args) => args,
+            Err(err) => return Ok(vec![ArgcValue::Error((format!("error: {err}"), 1))]),
+        };
+        let mut matcher = Matcher::new(runtime, self, &args, false);
         if let Some(script_path) = script_path {
             matcher.set_script_path(script_path)
         }
         if let Some(wrap_width) = wrap_width {
             matcher.set_wrap_width(wrap_width)
         }
+        let color = runtime.use_color();
+        if color || runtime.is_terminal() {
+            matcher.set_help_painter(HelpPainter::new(color))
+        }
         Ok(matcher.to_arg_values())
     }
 
+    #[cfg(all(feature = "eval", feature = "export"))]
+    pub(crate) fn eval_json<T: Runtime>(
+        &mut self,
+        runtime: T,
+        args: &[String],
+        script_path: Option<&str>,
+        wrap_width: Option<usize>,
+    ) -> Result<serde_json::Value> {
+        if args.is_empty() {
+            bail!("Invalid args");
+        }
+        let args = match self.expand_argfiles(runtime, args) {
+            Ok(args) => args,
+            Err(err) => {
+                return Ok(serde_json::json!({
+                    "command": self.cmd_paths(),
+                    "error": { "message": format!("error: {err}"), "exit": 1 },
+                }))
+            }
+        };
+        let mut matcher = Matcher::new(runtime, self, &args, false);
+        if let Some(script_path) = script_path {
+            matcher.set_script_path(script_path)
+        }
+        if let Some(wrap_width) = wrap_width {
+            matcher.set_wrap_width(wrap_width)
+        }
+        Ok(matcher.to_json_value())
+    }
+
+    /// Replace `@path` arguments with the shell-words read from that file when `@meta argfile` is set
+    #[cfg(feature = "eval")]
+    fn expand_argfiles<T: Runtime>(&self, runtime: T, args: &[String]) -> Result<Vec<String>> {
+        if !self.has_metadata(META_ARGFILE) {
+            return Ok(args.to_vec());
+        }
+        let mut output = vec![];
+        let mut dash = false;
+        for (i, arg) in args.iter().enumerate() {
+            if i == 0 || dash {
+                output.push(arg.clone());
+            } else {
+                expand_argfile(runtime, arg, 0, &mut output, &mut dash)?;
+            }
+        }
+        Ok(output)
+    }
+
+    /// Match the `@example` command lines of every command, returning the ones that fail
+    #[cfg(feature = "eval")]
+    pub(crate) fn check_examples<T: Runtime>(&self, runtime: T) -> Vec<(Position, String)> {
+        let mut output = vec![];
+        let mut cmds = vec![self];
+        while let Some(cmd) = cmds.pop() {
+            for (value, _, position) in &cmd.examples {
+                let err = match shell_words::split(value) {
+                    Ok(args) if !args.is_empty() => {
+                        Matcher::new(runtime, self, &args, false).check_example(&cmd.paths)
+                    }
+                    _ => Some("it is not a valid command line".to_string()),
+                };
+                if let Some(err) = err {
+                    output.push((*position, format!("example `{value}` fails, {err}")));
+                }
+            }
+            cmds.extend(cmd.subcommands.iter());
+        }
+        output.sort_by_key(|(position, _)| *position);
+        output
+    }
+
     #[cfg(feature = "export")]
     pub(crate) fn export(&self) -> CommandValue {
         let mut extra: IndexMap<String, serde_json::Value> = IndexMap::new();
@@ -122,6 +237,12 @@ impl Command {
             if self.get_metadata(META_COMBINE_SHORTS).is_some() {
                 extra.insert("combine_shorts".into(), true.into());
             }
+            if self.get_metadata(META_ABBREVIATE_LONG_OPTIONS).is_some() {
+                extra.insert("abbreviate_long_options".into(), true.into());
+            }
+            if self.get_metadata(META_ARGFILE).is_some() {
+                extra.insert("argfile".into(), true.into());
+            }
             if let Some(dotenv) = self.dotenv() {
                 extra.insert("dotenv".into(), dotenv.into());
             }
@@ -135,6 +256,15 @@ impl Command {
         } else if let Some((idx, _)) = &self.default_subcommand {
             extra.insert("default_subcommand".into(), (*idx).into());
         }
+        if let Some(group) = &self.group {
+            extra.insert("group".into(), group.clone().into());
+        }
+        if self.hidden() {
+            extra.insert("hidden".into(), true.into());
+        }
+        if let Some(message) = self.deprecated() {
+            extra.insert("deprecated".into(), message.into());
+        }
         if !self.metadata.is_empty() {
             extra.insert(
                 "metadata".into(),
@@ -152,6 +282,35 @@ impl Command {
                     .collect::<IndexMap<String, Option<String>>>()),
             );
         }
+        if !self.conflicts.is_empty() {
+            extra.insert(
+                "conflicts".into(),
+                serde_json::json!(self
+                    .conflicts
+                    .iter()
+                    .map(|(ids, _)| ids.clone())
+                    .collect::<Vec<Vec<String>>>()),
+            );
+        }
+        if !self.requires.is_empty() {
+            extra.insert(
+                "requires".into(),
+                serde_json::json!(self
+                    .requires
+                    .iter()
+                    .map(|(id, ids, _)| (id.clone(), ids.clone()))
+                    .collect::<IndexMap<String, Vec<String>>>()),
+            );
+        }
+        if !self.dep_fns.is_empty() {
+            extra.insert("deps".into(), self.dep_fns.clone().into());
+        }
+        if !self.sources.is_empty() {
+            extra.insert("sources".into(), self.sources.clone().into());
+        }
+        if !self.generates.is_empty() {
+            extra.insert("generates".into(), self.generates.clone().into());
+        }
         extra.insert("command_fn".into(), self.command_fn.clone().into());
         let flag_options = self.all_flag_options().iter().map(|v| v.export()).collect();
         CommandValue {
@@ -162,6 +321,14 @@ impl Command {
             flag_options,
             positionals: self.positional_params.iter().map(|v| v.export()).collect(),
             envs: self.env_params.iter().map(|v| v.export()).collect(),
+            examples: self
+                .examples
+                .iter()
+                .map(|(command, describe, _)| ExampleValue {
+                    command: command.clone(),
+                    describe: describe.clone(),
+                })
+                .collect(),
             subcommands: self.subcommands.iter().map(|v| v.export()).collect(),
             extra,
         }
@@ -170,7 +337,13 @@ impl Command {
     pub(crate) fn new_from_events(events: &[Event]) -> Result<Self> {
         let mut root_cmd = Command::default();
         let share_data = root_cmd.share.clone();
+        let mut unresolved_include = false;
+        let mut last_data: Option<&EventData> = None;
+        // `@group` of the following flags/options and of the following subcommands
+        let mut param_group: Option<String> = None;
+        let mut cmd_group: Option<String> = None;
         for event in events {
+            let prev_data = last_data.replace(&event.data);
             let Event { data, position } = event.clone();
             match data {
                 EventData::Describe(value) => {
@@ -183,6 +356,26 @@ impl Command {
                 }
                 EventData::Meta(key, value) => {
                     let cmd = Self::get_cmd(&mut root_cmd, "@meta", position)?;
+                    // Markers right below a param apply to the param instead of the command
+                    if key == META_HIDDEN || key == META_DEPRECATED {
+                        let param_data = match prev_data {
+                            Some(EventData::FlagOption(_)) => {
+                                cmd.flag_option_params.last_mut().map(|v| v.data_mut())
+                            }
+                            Some(EventData::Positional(_)) => {
+                                cmd.positional_params.last_mut().map(|v| v.data_mut())
+                            }
+                            _ => None,
+                        };
+                        if let Some(param_data) = param_data {
+                            if key == META_HIDDEN {
+                                param_data.hidden = true;
+                            } else {
+                                param_data.deprecated = Some(value);
+                            }
+                            continue;
+                        }
+                    }
                     match key.as_str() {
                         META_SYMBOL => {
                             let (ch, name, choice_fn) = parse_symbol(&value).ok_or_else(|| {
@@ -198,6 +391,23 @@ impl Command {
                                 cmd.version = Some(value.clone());
                             }
                         }
+                        // A path is spliced in as an inline template when the script is loaded
+                        META_HELP_TEMPLATE if value.contains(['{', '\n']) => {
+                            cmd.help_template = Some(value.clone());
+                        }
+                        META_LOCALE if !share_data.borrow_mut().messages.set_locale(&value) => {
+                            bail!("@meta(line {}) unsupported locale '{}'", position, value)
+                        }
+                        META_MESSAGE => {
+                            let (name, text) = value.split_once(' ').unwrap_or((&value, ""));
+                            if !share_data
+                                .borrow_mut()
+                                .messages
+                                .set_message(name, text.trim())
+                            {
+                                bail!("@meta(line {}) unknown message '{}'", position, name)
+                            }
+                        }
                         _ => {}
                     }
                     cmd.metadata.push((key, value, position));
@@ -215,12 +425,53 @@ impl Command {
                     if !value.is_empty() {
                         subcmd.describe.clone_from(&value);
                     }
+                    subcmd.group.clone_from(&cmd_group);
+                    param_group = None;
+                }
+                EventData::Group(value) => {
+                    let group = (!value.is_empty()).then_some(value);
+                    // Outside a @cmd block, the group applies to the subcommands declared next
+                    match share_data.borrow().scope {
+                        EventScope::Root => {
+                            param_group.clone_from(&group);
+                            cmd_group = group;
+                        }
+                        EventScope::CmdStart => param_group = group,
+                        EventScope::FnEnd => cmd_group = group,
+                    }
                 }
                 EventData::Aliases(values) => {
                     let cmd = Self::get_cmd(&mut root_cmd, "@alias", position)?;
                     cmd.aliases = Some((values.to_vec(), position));
                 }
-                EventData::FlagOption(param) => {
+                EventData::Deps(names) => {
+                    let cmd = Self::get_cmd(&mut root_cmd, "@dep", position)?;
+                    cmd.deps.push((names, position));
+                }
+                EventData::Sources(paths) => {
+                    let cmd = Self::get_cmd(&mut root_cmd, "@sources", position)?;
+                    cmd.sources.extend(paths);
+                }
+                EventData::Generates(paths) => {
+                    let cmd = Self::get_cmd(&mut root_cmd, "@generates", position)?;
+                    cmd.generates.extend(paths);
+                }
+                EventData::Example(value, describe) => {
+                    let cmd = Self::get_cmd(&mut root_cmd, "@example", position)?;
+                    cmd.examples.push((value, describe, position));
+                }
+                EventData::Conflicts(names) => {
+                    let cmd = Self::get_cmd(&mut root_cmd, "@conflicts", position)?;
+                    if names.len() < 2 {
+                        bail!("@conflicts(line {}) requires at least two params", position)
+                    }
+                    cmd.conflicts.push((names, position));
+                }
+                EventData::Requires(name, names) => {
+                    let cmd = Self::get_cmd(&mut root_cmd, "@requires", position)?;
+                    cmd.requires.push((name, names, position));
+                }
+                EventData::FlagOption(mut param) => {
                     param.guard().map_err(|err| {
                         anyhow!("{}(line {}) is invalid, {err}", param.tag_name(), position)
                     })?;
@@ -233,6 +484,11 @@ impl Command {
                         );
                     }
                     cmd.names_checker.check_flag_option(&param, position)?;
+                    // A top-level group used by the root flags/options doesn't leak into the subcommands
+                    if share_data.borrow().scope == EventScope::Root {
+                        cmd_group = None;
+                    }
+                    param.set_group(param_group.clone());
                     cmd.flag_option_params.push(param);
                 }
                 EventData::Env(param) => {
@@ -338,15 +594,31 @@ impl Command {
                     }
                     share_data.borrow_mut().scope = EventScope::FnEnd;
                 }
+                // Resolved by `resolve_includes` before parsing when the script path is known
+                EventData::Include(_) => {
+                    unresolved_include = true;
+                }
                 EventData::Unknown(name) => {
                     bail!("@{}(line {}) is unknown tag", name, position);
                 }
             }
         }
-        root_cmd.share.borrow().check_param_fn()?;
+        // Functions may be defined in the included scripts
+        if !unresolved_include {
+            root_cmd.share.borrow().check_param_fn()?;
+        }
         Ok(root_cmd)
     }
 
+    /// Render a message of the catalog picked by `@meta locale` or the environment
+    pub(crate) fn message(&self, key: &str, args: &[(&str, &str)]) -> String {
+        self.share.borrow().messages.render(key, args)
+    }
+
+    pub(crate) fn messages(&self) -> Ref<'_, Messages> {
+        std::cell::Ref::map(self.share.borrow(), |v| &v.messages)
+    }
+
     pub(crate) fn has_metadata(&self, key: &str) -> bool {
         self.metadata.iter().any(|(k, _, _)| k == key)
     }
@@ -437,6 +709,7 @@ impl Command {
     pub(crate) fn list_subcommand_names(&self) -> Vec<String> {
         self.subcommands
             .iter()
+            .filter(|v| !v.hidden())
             .flat_map(|v| v.list_names())
             .collect()
     }
@@ -488,6 +761,14 @@ impl Command {
             && self.positional_params[0].terminated()
     }
 
+    pub(crate) fn hidden(&self) -> bool {
+        self.has_metadata(META_HIDDEN)
+    }
+
+    pub(crate) fn deprecated(&self) -> Option<&str> {
+        self.get_metadata(META_DEPRECATED)
+    }
+
     pub(crate) fn dotenv(&self) -> Option<&str> {
         let dotenv = self.get_metadata(META_DOTENV)?;
         let dotenv = if dotenv.is_empty() { ".env" } else { dotenv };
@@ -645,6 +926,74 @@ impl Command {
         }
     }
 
+    fn inherit_help_template(&mut self) {
+        for subcmd in self.subcommands.iter_mut() {
+            if subcmd.help_template.is_none() {
+                subcmd.help_template = self.help_template.clone();
+            }
+            subcmd.inherit_help_template();
+        }
+    }
+
+    fn resolve_param_relations(&mut self) -> Result<()> {
+        let resolve = |cmd: &Self, tag_name: &str, name: &str, pos: Position| -> Result<String> {
+            match cmd.find_flag_option(name) {
+                Some(param) => Ok(param.id().to_string()),
+                None => bail!("{tag_name}(line {pos}) references unknown flag or option '{name}'"),
+            }
+        };
+        let mut conflicts = vec![];
+        for (names, pos) in &self.conflicts {
+            let ids = names
+                .iter()
+                .map(|name| resolve(self, "@conflicts", name, *pos))
+                .collect::<Result<Vec<String>>>()?;
+            conflicts.push((ids, *pos));
+        }
+        let mut requires = vec![];
+        for (name, names, pos) in &self.requires {
+            let id = resolve(self, "@requires", name, *pos)?;
+            let ids = names
+                .iter()
+                .map(|name| resolve(self, "@requires", name, *pos))
+                .collect::<Result<Vec<String>>>()?;
+            requires.push((id, ids, *pos));
+        }
+        self.conflicts = conflicts;
+        self.requires = requires;
+        for subcmd in self.subcommands.iter_mut() {
+            subcmd.resolve_param_relations()?;
+        }
+        Ok(())
+    }
+
+    fn collect_recipes(&self, recipes: &mut IndexMap<String, RecipeDeps>) {
+        if let Some(match_fn) = &self.match_fn {
+            recipes.insert(match_fn.clone(), self.deps.clone());
+        }
+        for subcmd in self.subcommands.iter() {
+            subcmd.collect_recipes(recipes);
+        }
+    }
+
+    fn resolve_deps(&mut self, recipes: &IndexMap<String, RecipeDeps>) -> Result<()> {
+        let mut dep_fns = IndexSet::new();
+        for (names, pos) in &self.deps {
+            for name in names {
+                visit_dep(recipes, name, *pos, &mut vec![], &mut dep_fns)?;
+            }
+        }
+        self.dep_fns = dep_fns.into_iter().collect();
+        for subcmd in self.subcommands.iter_mut() {
+            subcmd.resolve_deps(recipes)?;
+        }
+        Ok(())
+    }
+
+    pub(crate) fn find_flag_option_by_id(&self, id: &str) -> Option<&FlagOptionParam> {
+        self.flag_option_params.iter().find(|v| v.id() == id)
+    }
+
     fn add_positional_param(&mut self, param: PositionalParam, pos: Position) -> Result<()> {
         self.names_checker.check_positional(&param, pos)?;
         self.positional_params.push(param);
@@ -680,7 +1029,11 @@ impl Command {
         if self.find_flag_option("help").is_some() {
             return None;
         }
-        let describe = if describe { "Print help" } else { "" };
+        let describe = if describe {
+            self.message("print-help", &[])
+        } else {
+            String::new()
+        };
         let short = if self.find_flag_option("-h").is_none() {
             Some("-h")
         } else {
@@ -689,7 +1042,7 @@ impl Command {
         Some(FlagOptionParam::create_help_flag(
             short,
             long_prefix,
-            describe,
+            &describe,
         ))
     }
 
@@ -700,7 +1053,11 @@ impl Command {
         if self.find_flag_option("version").is_some() {
             return None;
         }
-        let describe = if describe { "Print version" } else { "" };
+        let describe = if describe {
+            self.message("print-version", &[])
+        } else {
+            String::new()
+        };
         let short = if self.find_flag_option("-V").is_none() {
             Some("-V")
         } else {
@@ -709,14 +1066,17 @@ impl Command {
         Some(FlagOptionParam::create_version_flag(
             short,
             long_prefix,
-            describe,
+            &describe,
         ))
     }
 }
 
 #[cfg(any(feature = "build", feature = "eval"))]
 impl Command {
-    pub(crate) fn render_help(&self, wrap_width: Option<usize>) -> String {
+    pub(crate) fn render_help(&self, wrap_width: Option<usize>, painter: HelpPainter) -> String {
+        if let Some(template) = &self.help_template {
+            return self.render_help_template(template, wrap_width, painter);
+        }
         let mut output = vec![];
         if !&self.describe.is_empty() {
             output.push(render_block("", &self.describe, wrap_width));
@@ -724,41 +1084,110 @@ impl Command {
         if !output.is_empty() {
             output.push(String::new());
         }
-        output.push(self.render_usage());
+        output.push(format!(
+            "{} {}",
+            painter.heading(&self.message("usage", &[])),
+            self.render_usage()
+        ));
         output.push(String::new());
-        output.extend(self.render_positionals(wrap_width));
-        output.extend(self.render_flag_options(wrap_width));
-        output.extend(self.render_subcommands(wrap_width));
-        output.extend(self.render_external_subcommands(wrap_width));
-        output.extend(self.render_envs(wrap_width));
+        output.extend(self.render_positionals(wrap_width, painter));
+        output.extend(self.render_flag_options(wrap_width, painter));
+        output.extend(self.render_subcommands(wrap_width, painter));
+        output.extend(self.render_external_subcommands(wrap_width, painter));
+        output.extend(self.render_envs(wrap_width, painter));
+        output.extend(self.render_examples(wrap_width, painter));
         if output.is_empty() {
             return "\n".to_string();
         }
         output.join("\n")
     }
 
+    /// Fill the placeholders of `@meta help-template` with the sections of the default help
+    fn render_help_template(
+        &self,
+        template: &str,
+        wrap_width: Option<usize>,
+        painter: HelpPainter,
+    ) -> String {
+        let render = |name: &str| -> Option<String> {
+            let (lines, heading) = match name {
+                "about" => return Some(render_block("", &self.describe, wrap_width)),
+                "usage" => return Some(self.render_usage()),
+                "version" => return Some(self.version.clone().unwrap_or_default()),
+                "positionals" => (self.render_positionals(wrap_width, painter), "args"),
+                "options" => (self.render_flag_options(wrap_width, painter), "options"),
+                "subcommands" => {
+                    let mut lines = self.render_subcommands(wrap_width, painter);
+                    lines.extend(self.render_external_subcommands(wrap_width, painter));
+                    (lines, "commands")
+                }
+                "envs" => (self.render_envs(wrap_width, painter), "environments"),
+                "examples" => (self.render_examples(wrap_width, painter), "examples"),
+                _ => return None,
+            };
+            let skip = match lines.first() {
+                Some(line) if *line == painter.heading(&self.message(heading, &[])) => 1,
+                _ => 0,
+            };
+            Some(lines[skip..].join("\n").trim_end().to_string())
+        };
+        let mut output = vec![];
+        for line in template.lines() {
+            let mut text = String::new();
+            let mut rest = line;
+            while let Some(start) = rest.find('{') {
+                let value = rest[start + 1..]
+                    .find('}')
+                    .and_then(|len| Some((len, render(&rest[start + 1..start + 1 + len])?)));
+                let Some((len, value)) = value else {
+                    text.push_str(&rest[..start + 1]);
+                    rest = &rest[start + 1..];
+                    continue;
+                };
+                text.push_str(&rest[..start]);
+                text.push_str(&value);
+                rest = &rest[start + len + 2..];
+            }
+            text.push_str(rest);
+            // Drop the lines whose placeholders are all empty
+            if text.trim().is_empty() && !line.trim().is_empty() {
+                continue;
+            }
+            output.push(text);
+        }
+        output.join("\n")
+    }
+
     fn render_usage(&self) -> String {
-        let mut output = vec!["USAGE:".to_string()];
-        output.extend(self.cmd_paths());
-        let required_options: Vec<String> = self
+        let mut output = self.cmd_paths();
+        let flag_options: Vec<&FlagOptionParam> = self
             .flag_option_params
+            .iter()
+            .filter(|v| !v.hidden())
+            .collect();
+        let required_options: Vec<String> = flag_options
             .iter()
             .filter(|v| v.required())
             .map(|v| v.render_name_notations())
             .collect();
-        if self.flag_option_params.len() != required_options.len() {
+        if flag_options.len() != required_options.len() {
             output.push("[OPTIONS]".to_string());
         }
         output.extend(required_options);
         if !self.subcommands.is_empty() {
             output.push("<COMMAND>".to_string());
         } else {
-            output.extend(self.positional_params.iter().map(|v| v.render_notation()));
+            output.extend(
+                self.positional_params
+                    .iter()
+                    .filter(|v| !v.hidden())
+                    .map(|v| v.render_notation()),
+            );
         }
         output.join(" ")
     }
 
-    fn render_flag_options(&self, wrap_width: Option<usize>) -> Vec<String> {
+    fn render_flag_options(&self, wrap_width: Option<usize>, painter: HelpPainter) -> Vec<String> {
         let mut output = vec![];
         let default_subcmd = self.find_default_subcommand();
         if self.flag_option_params.is_empty()
@@ -769,57 +1198,137 @@ impl Command {
             return output;
         }
 
-        let params = match default_subcmd {
-            Some(subcmd) => [self.all_flag_options(), subcmd.all_flag_options()].concat(),
-            None => self.all_flag_options(),
-        };
-
-        let mut value_size = 0;
-        let list: IndexMap<String, String> = params
+        let mut params: Vec<(&Self, &FlagOptionParam)> = self
+            .all_flag_options()
             .into_iter()
-            .map(|param| {
-                let value = param.render_body();
-                let describe = param.render_describe();
-                value_size = value_size.max(value.len());
-                (value, describe)
-            })
+            .map(|param| (self, param))
             .collect();
+        if let Some(subcmd) = default_subcmd {
+            params.extend(
+                subcmd
+                    .all_flag_options()
+                    .into_iter()
+                    .map(|param| (subcmd, param)),
+            );
+        }
+        params.retain(|(_, param)| !param.hidden());
+        if params.is_empty() {
+            return output;
+        }
+
+        let mut value_size = 0;
+        let mut sections: IndexMap<Option<&str>, IndexMap<String, String>> =
+            IndexMap::from([(None, IndexMap::new())]);
+        for (cmd, param) in params {
+            let value = param.render_body();
+            let describe = cmd.render_flag_option_describe(param);
+            value_size = value_size.max(value.len());
+            sections.entry(param.group()).or_default().insert(
+                painter.flag_option(&value),
+                painter.describe(&describe, &self.messages()),
+            );
+        }
         value_size += 2;
-        output.push("OPTIONS:".to_string());
-        render_list(
-            &mut output,
-            list.into_iter().collect(),
-            value_size,
-            wrap_width,
+        for (group, list) in sections {
+            if list.is_empty() {
+                continue;
+            }
+            output.push(painter.heading(&match group {
+                Some(group) => format!("{group}:"),
+                None => self.message("options", &[]),
+            }));
+            render_list(
+                &mut output,
+                list.into_iter().collect(),
+                value_size,
+                wrap_width,
+            );
+        }
+        output
+    }
+
+    fn render_flag_option_describe(&self, param: &FlagOptionParam) -> String {
+        let mut output = param.render_describe(&self.messages());
+        let id = param.id();
+        let render_names = |ids: Vec<&String>| -> String {
+            ids.into_iter()
+                .filter_map(|v| self.find_flag_option_by_id(v).map(|v| v.long_name()))
+                .collect::<IndexSet<String>>()
+                .into_iter()
+                .collect::<Vec<String>>()
+                .join(", ")
+        };
+        let mut notes = vec![];
+        let conflicts = render_names(
+            self.conflicts
+                .iter()
+                .filter(|(ids, _)| ids.iter().any(|v| v == id))
+                .flat_map(|(ids, _)| ids.iter().filter(|v| *v != id))
+                .collect(),
         );
+        if !conflicts.is_empty() {
+            notes.push(self.message("conflicts-with", &[("names", &conflicts)]));
+        }
+        let requires = render_names(
+            self.requires
+                .iter()
+                .filter(|(v, _, _)| v == id)
+                .flat_map(|(_, ids, _)| ids.iter())
+                .collect(),
+        );
+        if !requires.is_empty() {
+            notes.push(self.message("requires", &[("names", &requires)]));
+        }
+        if param.deprecated().is_some() {
+            notes.push(self.message("deprecated", &[]));
+        }
+        let sep = if output.contains('\n') { '\n' } else { ' ' };
+        for note in notes {
+            if !output.is_empty() {
+                output.push(sep);
+            }
+            output.push_str(&note);
+        }
         output
     }
 
-    fn render_positionals(&self, wrap_width: Option<usize>) -> Vec<String> {
+    fn render_positionals(&self, wrap_width: Option<usize>, painter: HelpPainter) -> Vec<String> {
         let mut output = vec![];
         let params = match self.find_default_subcommand() {
             Some(subcmd) => &subcmd.positional_params,
             None => &self.positional_params,
         };
+        let params: Vec<&PositionalParam> = params.iter().filter(|v| !v.hidden()).collect();
         if params.is_empty() {
             return output;
         }
         let mut value_size = 0;
         let list: Vec<_> = params
-            .iter()
+            .into_iter()
             .map(|param| {
                 let value = param.render_notation();
                 value_size = value_size.max(value.len());
-                (value, param.render_describe())
+                let mut describe = param.render_describe(&self.messages());
+                if param.deprecated().is_some() {
+                    let sep = if describe.contains('\n') { '\n' } else { ' ' };
+                    if !describe.is_empty() {
+                        describe.push(sep);
+                    }
+                    describe.push_str(&self.message("deprecated", &[]));
+                }
+                (
+                    painter.placeholder(&value),
+                    painter.describe(&describe, &self.messages()),
+                )
             })
             .collect();
         value_size += 2;
-        output.push("ARGS:".to_string());
+        output.push(painter.heading(&self.message("args", &[])));
         render_list(&mut output, list, value_size, wrap_width);
         output
     }
 
-    fn render_envs(&self, wrap_width: Option<usize>) -> Vec<String> {
+    fn render_envs(&self, wrap_width: Option<usize>, painter: HelpPainter) -> Vec<String> {
         let mut output = vec![];
         let params = match self.find_default_subcommand() {
             Some(subcmd) => &subcmd.env_params,
@@ -828,43 +1337,81 @@ impl Command {
         if params.is_empty() {
             return output;
         }
+        let messages = self.messages();
         let mut value_size = 0;
         let list: Vec<_> = params
             .iter()
             .map(|param| {
                 let value = param.render_body();
                 value_size = value_size.max(value.len());
-                (value, param.render_describe())
+                (
+                    painter.placeholder(&value),
+                    painter.describe(&param.render_describe(&messages), &messages),
+                )
             })
             .collect();
         value_size += 2;
-        output.push("ENVIRONMENTS:".to_string());
+        output.push(painter.heading(&self.message("environments", &[])));
         render_list(&mut output, list, value_size, wrap_width);
         output
     }
 
-    fn render_subcommands(&self, wrap_width: Option<usize>) -> Vec<String> {
+    fn render_examples(&self, wrap_width: Option<usize>, painter: HelpPainter) -> Vec<String> {
         let mut output = vec![];
-        if self.subcommands.is_empty() {
+        if self.examples.is_empty() {
             return output;
         }
         let mut value_size = 0;
         let list: Vec<_> = self
-            .subcommands
+            .examples
             .iter()
-            .map(|subcmd| {
-                let value = subcmd.cmd_name();
+            .map(|(value, describe, _)| {
                 value_size = value_size.max(value.len());
-                (value, subcmd.render_subcommand_describe())
+                (painter.command(value), describe.clone())
             })
             .collect();
         value_size += 2;
-        output.push("COMMANDS:".to_string());
+        output.push(painter.heading(&self.message("examples", &[])));
         render_list(&mut output, list, value_size, wrap_width);
         output
     }
 
-    fn render_external_subcommands(&self, wrap_width: Option<usize>) -> Vec<String> {
+    fn render_subcommands(&self, wrap_width: Option<usize>, painter: HelpPainter) -> Vec<String> {
+        let mut output = vec![];
+        let subcommands: Vec<&Self> = self.subcommands.iter().filter(|v| !v.hidden()).collect();
+        if subcommands.is_empty() {
+            return output;
+        }
+        let mut value_size = 0;
+        let mut sections: IndexMap<Option<&str>, Vec<(String, String)>> =
+            IndexMap::from([(None, vec![])]);
+        for subcmd in subcommands {
+            let value = subcmd.cmd_name();
+            value_size = value_size.max(value.len());
+            sections
+                .entry(subcmd.group.as_deref())
+                .or_default()
+                .push((painter.command(&value), subcmd.render_subcommand_describe()));
+        }
+        value_size += 2;
+        for (group, list) in sections {
+            if list.is_empty() {
+                continue;
+            }
+            output.push(painter.heading(&match group {
+                Some(group) => format!("{group}:"),
+                None => self.message("commands", &[]),
+            }));
+            render_list(&mut output, list, value_size, wrap_width);
+        }
+        output
+    }
+
+    fn render_external_subcommands(
+        &self,
+        wrap_width: Option<usize>,
+        painter: HelpPainter,
+    ) -> Vec<String> {
         let mut output = vec![];
         if self.external_subcommands.is_empty() {
             return output;
@@ -875,11 +1422,11 @@ impl Command {
             .iter()
             .map(|info| {
                 value_size = value_size.max(info.name.len());
-                (info.name.clone(), info.description.clone())
+                (painter.command(&info.name), info.description.clone())
             })
             .collect();
         value_size += 2;
-        output.push("EXTERNAL COMMANDS:".to_string());
+        output.push(painter.heading(&self.message("external-commands", &[])));
         render_list(&mut output, list, value_size, wrap_width);
         output
     }
@@ -890,13 +1437,19 @@ impl Command {
             if !output.is_empty() {
                 output.push(' ')
             }
-            output.push_str(&format!("[aliases: {}]", aliases.join(", ")));
+            output.push_str(&self.message("aliases", &[("aliases", &aliases.join(", "))]));
         }
         if self.has_metadata(META_DEFAULT_SUBCOMMAND) {
             if !output.is_empty() {
                 output.push(' ')
             }
-            output.push_str("[default]");
+            output.push_str(&self.message("default-subcommand", &[]));
+        }
+        if self.deprecated().is_some() {
+            if !output.is_empty() {
+                output.push(' ')
+            }
+            output.push_str(&self.message("deprecated", &[]));
         }
         output
     }
@@ -912,11 +1465,19 @@ pub struct CommandValue {
     pub flag_options: Vec<FlagOptionValue>,
     pub positionals: Vec<PositionalValue>,
     pub envs: Vec<EnvValue>,
+    pub examples: Vec<ExampleValue>,
     pub subcommands: Vec<CommandValue>,
     #[serde(flatten)]
     pub extra: IndexMap<String, serde_json::Value>,
 }
 
+#[cfg(feature = "export")]
+#[derive(Debug, Serialize)]
+pub struct ExampleValue {
+    pub command: String,
+    pub describe: String,
+}
+
 pub(crate) type SymbolParam = (String, Option<String>);
 
 fn retrieve_cmd<'a>(cmd: &'a mut Command, paths: &[String]) -> Option<&'a mut Command> {
@@ -957,6 +1518,82 @@ fn sanitize_cmd_name(name: &str) -> String {
     name.trim_end_matches('_').to_string()
 }
 
+#[cfg(feature = "eval")]
+const ARGFILE_MAX_DEPTH: usize = 8;
+
+#[cfg(feature = "eval")]
+fn expand_argfile<T: Runtime>(
+    runtime: T,
+    arg: &str,
+    depth: usize,
+    output: &mut Vec<String>,
+    dash: &mut bool,
+) -> Result<()> {
+    let path = match arg.strip_prefix('@') {
+        Some(path) if !path.is_empty() && !*dash => path,
+        _ => {
+            *dash = *dash || arg == "--";
+            output.push(arg.to_string());
+            return Ok(());
+        }
+    };
+    if depth >= ARGFILE_MAX_DEPTH {
+        bail!("argfile `{path}` is nested more than {ARGFILE_MAX_DEPTH} levels deep");
+    }
+    let content = runtime
+        .read_to_string(path)
+        .ok_or_else(|| anyhow!("failed to read argfile `{path}`"))?;
+    for (i, line) in content.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let words = shell_words::split(line)
+            .map_err(|err| anyhow!("argfile `{path}` line {}: {err}", i + 1))?;
+        for word in words {
+            expand_argfile(runtime, &word, depth + 1, output, dash)?;
+            if output.len() > crate::utils::MAX_ARGS {
+                bail!(
+                    "argfile `{path}` line {}: expands to more than {} arguments",
+                    i + 1,
+                    crate::utils::MAX_ARGS
+                );
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Depth-first walk of `@dep` edges, so every recipe lands after its own dependencies
+fn visit_dep(
+    recipes: &IndexMap<String, RecipeDeps>,
+    name: &str,
+    pos: Position,
+    stack: &mut Vec<String>,
+    output: &mut IndexSet<String>,
+) -> Result<()> {
+    if output.contains(name) {
+        return Ok(());
+    }
+    let deps = match recipes.get(name) {
+        Some(v) => v,
+        None => bail!("@dep(line {pos}) references unknown recipe '{name}'"),
+    };
+    if let Some(idx) = stack.iter().position(|v| v == name) {
+        let chain = [&stack[idx..], &[name.to_string()]].concat().join(" -> ");
+        bail!("@dep(line {pos}) has circular dependency '{chain}'");
+    }
+    stack.push(name.to_string());
+    for (names, pos) in deps {
+        for name in names {
+            visit_dep(recipes, name, *pos, stack, output)?;
+        }
+    }
+    stack.pop();
+    output.insert(name.to_string());
+    Ok(())
+}
+
 #[cfg(any(feature = "eval", feature = "compgen"))]
 pub(crate) fn collect_external_subcommands<T: Runtime>(
     runtime: T,
@@ -1043,7 +1680,7 @@ fn render_list(
                 render_block(&" ".repeat(10), &describe, wrap_width)
             )
         } else {
-            let spaces = " ".repeat(value_size - value.len());
+            let spaces = " ".repeat(value_size - visible_len(&value));
             render_block(&format!("  {value}{spaces}"), &describe, wrap_width)
         };
         mapped_list.push(item);
@@ -1057,8 +1694,9 @@ fn render_list(
 }
 
 fn render_block(name: &str, describe: &str, wrap_width: Option<usize>) -> String {
-    let size = wrap_width.unwrap_or(999) - name.len();
-    let empty = " ".repeat(name.len());
+    let name_len = visible_len(name);
+    let size = wrap_width.unwrap_or(999) - name_len;
+    let empty = " ".repeat(name_len);
     describe
         .split('\n')
         .flat_map(|v| {
@@ -1,4 +1,5 @@
 use crate::{
+    locale::Messages,
     parser::{EventScope, Position},
     utils::ROOT_NAME,
 };
@@ -15,6 +16,7 @@ pub(crate) struct ShareData {
     pub(crate) cmd_pos: usize,
     pub(crate) default_fns: Vec<(String, Position)>,
     pub(crate) choice_fns: Vec<(String, Position)>,
+    pub(crate) messages: Messages,
 }
 
 impl ShareData {
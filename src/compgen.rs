@@ -1,14 +1,13 @@
+pub(crate) use crate::color::CompColor;
 use crate::command::{collect_external_subcommands, Command};
 use crate::matcher::Matcher;
 use crate::runtime::Runtime;
 use crate::utils::{is_quote_char, is_windows_path, unbalance_quote};
 use crate::Shell;
 
-use anyhow::{anyhow, bail, Result};
+use anyhow::{anyhow, Result};
 use indexmap::IndexMap;
 use std::collections::{HashMap, HashSet};
-use std::fmt;
-use std::str::FromStr;
 
 pub const COMPGEN_KIND_SYMBOL: &str = "___compgen_kind___";
 
@@ -356,187 +355,6 @@ pub(crate) type CandidateValue = (String, String, bool, CompColor); // value, de
 
 /// (value, description, nospace, color)
 pub(crate) type CompItem = (String, String, bool, CompColor);
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub(crate) struct CompColor {
-    pub(crate) code: ColorCode,
-    pub(crate) style: ColorStyle,
-}
-
-impl CompColor {
-    pub(crate) fn new(code: ColorCode, style: ColorStyle) -> Self {
-        Self { code, style }
-    }
-
-    pub(crate) fn parse(s: &str) -> Result<Self> {
-        if let Some((code, style)) = s.split_once(',') {
-            if let (Ok(code), Ok(style)) = (code.parse(), style.parse()) {
-                return Ok(Self::new(code, style));
-            }
-        } else if let Ok(code) = s.parse() {
-            return Ok(Self::new(code, ColorStyle::Regular));
-        }
-        bail!("Invalid CompColor value")
-    }
-
-    pub(crate) fn ser(&self) -> String {
-        if self.style == ColorStyle::Regular {
-            format!("{}", self.code)
-        } else {
-            format!("{},{}", self.code, self.style)
-        }
-    }
-
-    pub(crate) fn ansi_code(&self) -> String {
-        let mut ret = if self.style != ColorStyle::Regular {
-            format!("{};", self.style.ansi_code())
-        } else {
-            String::new()
-        };
-        ret.push_str(self.code.ansi_code());
-        ret
-    }
-
-    pub(crate) fn style(&self) -> String {
-        let mut ret = self.code.to_string();
-        if self.style != ColorStyle::Regular {
-            ret.push_str(&format!(" {}", self.style))
-        }
-        ret
-    }
-
-    pub(crate) fn of_flag() -> Self {
-        Self::new(ColorCode::Cyan, ColorStyle::Regular)
-    }
-
-    pub(crate) fn of_option() -> Self {
-        Self::new(ColorCode::Cyan, ColorStyle::Bold)
-    }
-
-    pub(crate) fn of_command() -> Self {
-        Self::new(ColorCode::Magenta, ColorStyle::Regular)
-    }
-
-    pub(crate) fn of_dir() -> Self {
-        Self::new(ColorCode::Blue, ColorStyle::Bold)
-    }
-
-    pub(crate) fn of_file() -> Self {
-        Self::new(ColorCode::Default, ColorStyle::Regular)
-    }
-
-    pub(crate) fn of_file_exe() -> Self {
-        Self::new(ColorCode::Green, ColorStyle::Bold)
-    }
-
-    pub(crate) fn of_symlink() -> Self {
-        Self::new(ColorCode::Cyan, ColorStyle::Bold)
-    }
-
-    pub(crate) fn of_value() -> Self {
-        Self::new(ColorCode::Default, ColorStyle::Regular)
-    }
-}
-
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub(crate) enum ColorCode {
-    Black,
-    Red,
-    Green,
-    Yellow,
-    Blue,
-    Magenta,
-    Cyan,
-    White,
-    Default,
-}
-
-impl FromStr for ColorCode {
-    type Err = anyhow::Error;
-
-    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
-        match s {
-            "black" => Ok(Self::Black),
-            "red" => Ok(Self::Red),
-            "green" => Ok(Self::Green),
-            "yellow" => Ok(Self::Yellow),
-            "blue" => Ok(Self::Blue),
-            "magenta" => Ok(Self::Magenta),
-            "cyan" => Ok(Self::Cyan),
-            "white" => Ok(Self::White),
-            "default" => Ok(Self::Default),
-            _ => bail!("Invalid ColorCode value"),
-        }
-    }
-}
-
-impl fmt::Display for ColorCode {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        match self {
-            Self::Black => write!(f, "black"),
-            Self::Red => write!(f, "red"),
-            Self::Green => write!(f, "green"),
-            Self::Yellow => write!(f, "yellow"),
-            Self::Blue => write!(f, "blue"),
-            Self::Magenta => write!(f, "magenta"),
-            Self::Cyan => write!(f, "cyan"),
-            Self::White => write!(f, "white"),
-            Self::Default => write!(f, "default"),
-        }
-    }
-}
-
-impl ColorCode {
-    pub fn ansi_code(&self) -> &str {
-        match self {
-            Self::Black => "30",
-            Self::Red => "31",
-            Self::Green => "32",
-            Self::Yellow => "33",
-            Self::Blue => "34",
-            Self::Magenta => "35",
-            Self::Cyan => "36",
-            Self::White => "37",
-            Self::Default => "39",
-        }
-    }
-}
-
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub(crate) enum ColorStyle {
-    Regular,
-    Bold,
-}
-
-impl FromStr for ColorStyle {
-    type Err = anyhow::Error;
-
-    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
-        match s {
-            "regular" => Ok(Self::Regular),
-            "bold" => Ok(Self::Bold),
-            _ => bail!("Invalid CodeStyle value"),
-        }
-    }
-}
-
-impl fmt::Display for ColorStyle {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        match self {
-            Self::Regular => write!(f, "regular"),
-            Self::Bold => write!(f, "bold"),
-        }
-    }
-}
-
-impl ColorStyle {
-    pub fn ansi_code(&self) -> &str {
-        match self {
-            Self::Regular => "0",
-            Self::Bold => "1",
-        }
-    }
-}
-
 pub(crate) struct ArgcPathValue {
     pub(crate) is_dir: bool,
     pub(crate) exts: Vec<String>,
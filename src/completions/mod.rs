@@ -1,3 +1,7 @@
+mod standalone;
+
+pub use standalone::generate_standalone_completions;
+
 use crate::Shell;
 
 const BASH_SCRIPT: &str = include_str!("argc.bash");
@@ -0,0 +1,143 @@
+# Bash completion for __COMMANDS__, generated by `argc --argc-build-completions`.
+# It does not require argc at runtime.
+
+__FN__() {
+    local words=() cur cmd=0 expect="" dashdash=0 positional=0 word next i trim
+    __FN___parse_line
+    cur="${words[${#words[@]}-1]}"
+    COMPREPLY=()
+
+    for ((i = 1; i < ${#words[@]} - 1; i++)); do
+        word="${words[i]}"
+        if [[ -n "$expect" ]]; then
+            expect=""
+        elif [[ $dashdash -eq 0 && "$word" == "--" ]]; then
+            dashdash=1
+        elif [[ $dashdash -eq 0 && "$word" == -?* ]]; then
+            if [[ "$word" != *=* ]]; then
+                expect="$(__FN___option "$cmd" "$word")"
+            fi
+        elif [[ $positional -eq 0 ]] && next="$(__FN___subcommand "$cmd" "$word")"; then
+            cmd="$next"
+        else
+            positional=$((positional + 1))
+        fi
+    done
+
+    if [[ -n "$expect" ]]; then
+        __FN___values "$cmd" "$expect" "$cur" ""
+    elif [[ $dashdash -eq 0 && "$cur" == -*=* ]]; then
+        expect="$(__FN___option "$cmd" "${cur%%=*}")"
+        if [[ -n "$expect" ]]; then
+            __FN___values "$cmd" "$expect" "${cur#*=}" "${cur%%=*}="
+        fi
+    elif [[ $dashdash -eq 0 && "$cur" == -* ]]; then
+        __FN___options "$cmd" "$cur"
+    else
+        if [[ $positional -eq 0 ]]; then
+            __FN___subcommands "$cmd" "$cur"
+        fi
+        __FN___values "$cmd" "@$positional" "$cur" ""
+    fi
+
+    if [[ "$cur" == *[$COMP_WORDBREAKS]* ]]; then
+        trim="${cur%"${cur##*[$COMP_WORDBREAKS]}"}"
+        COMPREPLY=("${COMPREPLY[@]#"$trim"}")
+    fi
+}
+
+__FN___subcommand() {
+    case "$1:$2" in
+__SUBCOMMAND_CASES__
+    *) return 1 ;;
+    esac
+}
+
+__FN___option() {
+    case "$1:$2" in
+__OPTION_CASES__
+    esac
+}
+
+__FN___subcommands() {
+    case "$1" in
+__SUBCOMMANDS_CASES__
+    esac
+}
+
+__FN___options() {
+    case "$1" in
+__OPTIONS_CASES__
+    esac
+}
+
+__FN___values() {
+    case "$1:$2" in
+__VALUES_CASES__
+    esac
+}
+
+__FN___add() {
+    local cur="$1" prefix="$2" value
+    shift 2
+    for value in "$@"; do
+        if [[ "$value" == "$cur"* ]]; then
+            COMPREPLY+=("$prefix$value")
+        fi
+    done
+}
+
+__FN___path() {
+    local line
+    compopt -o filenames 2>/dev/null
+    while IFS= read -r line; do
+        COMPREPLY+=("$2$line")
+    done < <(compgen "$3" -- "$1")
+}
+
+__FN___fn() {
+    local line
+    while IFS= read -r line; do
+        line="${line%%$'\t'*}"
+        if [[ -n "$line" && "$line" != __argc_* && "$line" == "$1"* ]]; then
+            COMPREPLY+=("$2$line")
+        fi
+    done < <(ARGC_COMPGEN=1 ARGC_CWORD="$1" ARGC_LAST_ARG="$cur" "${words[0]}" ___internal___ "$3" "${words[@]}" 2>/dev/null)
+}
+
+__FN___parse_line() {
+    local line len i char prev_char word unbalance
+    line="${COMP_LINE:0:$COMP_POINT}"
+    len="${#line}"
+
+    for ((i=0; i<len; i++)); do
+        char="${line:i:1}"
+        if [[ -n "$unbalance" ]]; then
+            word="$word$char"
+            if [[  "$unbalance" == "$char" ]]; then
+                unbalance=""
+            fi
+        elif [[ "$char" == " " ]]; then
+            if [[ "$prev_char" == "\\" ]]; then
+                word="$word$char"
+            elif [[ -n "$word" ]]; then
+                words+=( "$word" )
+                word=""
+            fi
+        elif [[ "$char" == "'" || "$char" == '"' ]]; then
+            word="$word$char"
+            unbalance="$char"
+        elif [[ "$char" == "\\" ]]; then
+            if [[ "$prev_char" == "\\" ]]; then
+                word="$word$char"
+            fi
+        else
+            word="$word$char"
+        fi
+        prev_char="$char"
+    done
+
+    words+=( "$word" )
+}
+
+complete -F __FN__ __COMMANDS__
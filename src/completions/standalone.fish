@@ -0,0 +1,112 @@
+# Fish completion for __COMMANDS__, generated by `argc --argc-build-completions`.
+# It does not require argc at runtime.
+
+function __FN__
+    set -l words (commandline -opc)
+    set -l cur (commandline -ct)
+    set -l args $words
+    set -e args[1]
+    set -l cmd 0
+    set -l expect ""
+    set -l dashdash 0
+    set -l positional 0
+    set -l next
+
+    for word in $args
+        if test -n "$expect"
+            set expect ""
+        else if test $dashdash -eq 0 -a "$word" = "--"
+            set dashdash 1
+        else if test $dashdash -eq 0; and string match -q -- '-?*' $word
+            if not string match -q -- '*=*' $word
+                set expect (__FN___option $cmd $word)
+            end
+        else if test $positional -eq 0; and set next (__FN___subcommand $cmd $word)
+            set cmd $next
+        else
+            set positional (math $positional + 1)
+        end
+    end
+
+    if test -n "$expect"
+        __FN___values $cmd $expect "$cur" ""
+    else if test $dashdash -eq 0; and string match -q -- '-*=*' "$cur"
+        set -l name (string split -m 1 = -- $cur)
+        set expect (__FN___option $cmd $name[1])
+        if test -n "$expect"
+            __FN___values $cmd $expect "$name[2]" "$name[1]="
+        end
+    else if test $dashdash -eq 0; and string match -q -- '-*' "$cur"
+        __FN___options $cmd
+    else
+        if test $positional -eq 0
+            __FN___subcommands $cmd
+        end
+        __FN___values $cmd "@$positional" "$cur" ""
+    end
+end
+
+function __FN___subcommand
+    switch "$argv[1]:$argv[2]"
+__SUBCOMMAND_CASES__
+        case '*'
+            return 1
+    end
+end
+
+function __FN___option
+    switch "$argv[1]:$argv[2]"
+__OPTION_CASES__
+    end
+end
+
+function __FN___subcommands
+    switch $argv[1]
+__SUBCOMMANDS_CASES__
+    end
+end
+
+function __FN___options
+    switch $argv[1]
+__OPTIONS_CASES__
+    end
+end
+
+function __FN___values
+    switch "$argv[1]:$argv[2]"
+__VALUES_CASES__
+    end
+end
+
+function __FN___add
+    for value in $argv[2..-1]
+        printf '%s%s\n' $argv[1] $value
+    end
+end
+
+function __FN___path
+    for value in (__fish_complete_path $argv[1])
+        printf '%s%s\n' $argv[2] $value
+    end
+end
+
+function __FN___dir
+    for value in (__fish_complete_directories $argv[1])
+        printf '%s%s\n' $argv[2] $value
+    end
+end
+
+function __FN___fn
+    set -l words (commandline -opc) (commandline -ct)
+    set -lx ARGC_COMPGEN 1
+    set -lx ARGC_CWORD $argv[1]
+    set -lx ARGC_LAST_ARG (commandline -ct)
+    for line in ($words[1] ___internal___ $argv[3] $words 2>/dev/null)
+        set -l value (string split -m 1 \t -- $line)[1]
+        if test -n "$value"; and not string match -q -- '__argc_*' $value
+            printf '%s%s\n' $argv[2] $line
+        end
+    end
+end
+
+__COMPLETE__
@@ -0,0 +1,337 @@
+use crate::command::Command;
+use crate::param::{Param, ParamData, ValueType};
+use crate::utils::{escape_shell_words, sanitize_var_name};
+use crate::Shell;
+
+use anyhow::{bail, Result};
+
+const BASH_SCRIPT: &str = include_str!("standalone.bash");
+const FISH_SCRIPT: &str = include_str!("standalone.fish");
+const ZSH_SCRIPT: &str = include_str!("standalone.zsh");
+
+/// Key of a multiple positional, matches every remaining positional index
+const REST_POSITIONALS: &str = "@*";
+
+/// Generate a completion script that does not call back into argc.
+///
+/// Dynamic values (`_choice_fn`) are resolved by running the completed command
+/// itself with `___internal___`, which works for both eval-based and built scripts.
+pub fn generate_standalone_completions(
+    shell: Shell,
+    source: &str,
+    root_name: &str,
+    commands: &[String],
+) -> Result<String> {
+    let cmd = Command::new(source, root_name)?;
+    let mut table = vec![];
+    collect_comp_commands(&cmd, &mut table);
+    let func = format!("_{}_completions", sanitize_var_name(root_name));
+    let output = match shell {
+        Shell::Bash => {
+            render(BASH_SCRIPT, &Bash, &table).replace("__COMMANDS__", &commands.join(" "))
+        }
+        Shell::Zsh => render(ZSH_SCRIPT, &Zsh, &table).replace("__COMMANDS__", &commands.join(" ")),
+        Shell::Fish => {
+            let complete = commands
+                .iter()
+                .map(|v| format!("complete -c {} -f -a '(__FN__)'", fish_quote(v)))
+                .collect::<Vec<_>>()
+                .join("\n");
+            render(FISH_SCRIPT, &Fish, &table)
+                .replace("__COMPLETE__", &complete)
+                .replace("__COMMANDS__", &commands.join(" "))
+        }
+        _ => bail!(
+            "Standalone completions are not supported for {}, use one of bash, zsh, fish",
+            shell.name()
+        ),
+    };
+    Ok(output.replace("__FN__", &func))
+}
+
+#[derive(Debug, Default)]
+struct CompCommand {
+    subcommands: Vec<(Vec<String>, String, usize)>,
+    options: Vec<(Vec<String>, String, Option<String>)>,
+    values: Vec<(String, CompValue)>,
+}
+
+#[derive(Debug)]
+enum CompValue {
+    Choices(Vec<String>),
+    ChoiceFn(String),
+    Path,
+    Dir,
+}
+
+fn collect_comp_commands(cmd: &Command, table: &mut Vec<CompCommand>) -> usize {
+    let idx = table.len();
+    table.push(CompCommand::default());
+    let mut comp_cmd = CompCommand::default();
+    for param in cmd.all_flag_options() {
+        if param.hidden() {
+            continue;
+        }
+        let key = if param.is_flag() || param.prefixed() {
+            None
+        } else {
+            Some(param.long_name())
+        };
+        if let Some(key) = &key {
+            let value_name = param.notations().first().map(|v| v.as_str());
+            if let Some(value) = comp_value(value_name.unwrap_or_default(), param.data()) {
+                comp_cmd.values.push((key.clone(), value));
+            }
+        }
+        comp_cmd.options.push((
+            param.list_names(),
+            param.describe_oneline().to_string(),
+            key,
+        ));
+    }
+    for (i, param) in cmd.positional_params.iter().enumerate() {
+        if param.hidden() {
+            continue;
+        }
+        let key = if param.multiple_values() {
+            REST_POSITIONALS.to_string()
+        } else {
+            format!("@{i}")
+        };
+        if let Some(value) = comp_value(param.notation(), param.data()) {
+            comp_cmd.values.push((key, value));
+        }
+    }
+    for subcmd in cmd.subcommands.iter().filter(|v| !v.hidden()) {
+        let child = collect_comp_commands(subcmd, table);
+        comp_cmd.subcommands.push((
+            subcmd.list_names(),
+            subcmd.describe_oneline().to_string(),
+            child,
+        ));
+    }
+    table[idx] = comp_cmd;
+    idx
+}
+
+fn comp_value(value_name: &str, data: &ParamData) -> Option<CompValue> {
+    if let Some((choice_fn, _)) = data.choice_fn() {
+        return Some(CompValue::ChoiceFn(choice_fn.to_string()));
+    }
+    if let Some(choices) = data.choice_values() {
+        return Some(CompValue::Choices(choices.clone()));
+    }
+    match data.value_type {
+        Some(ValueType::Path | ValueType::File) => return Some(CompValue::Path),
+        Some(ValueType::Dir) => return Some(CompValue::Dir),
+        _ => {}
+    }
+    // Same rules as `__argc_value` in compgen
+    let value_name = value_name.to_lowercase();
+    if ["path", "file", "arg", "any"]
+        .iter()
+        .any(|v| value_name.contains(v))
+    {
+        Some(CompValue::Path)
+    } else if value_name.contains("dir") || value_name.contains("folder") {
+        Some(CompValue::Dir)
+    } else {
+        None
+    }
+}
+
+trait Render {
+    fn case(&self, patterns: &[String], body: &str) -> String;
+    fn pattern(&self, idx: usize, key: &str) -> String;
+    fn quote(&self, value: &str) -> String;
+    fn echo(&self, value: &str) -> String;
+    fn list(&self, items: &[(String, String)]) -> String;
+    fn value(&self, value: &CompValue) -> String;
+}
+
+fn render(template: &str, r: &impl Render, table: &[CompCommand]) -> String {
+    let mut subcommand_cases = vec![];
+    let mut option_cases = vec![];
+    let mut subcommands_cases = vec![];
+    let mut options_cases = vec![];
+    let mut values_cases = vec![];
+    for (idx, cmd) in table.iter().enumerate() {
+        let mut items = vec![];
+        for (names, describe, child) in &cmd.subcommands {
+            let patterns: Vec<String> = names.iter().map(|v| r.pattern(idx, v)).collect();
+            subcommand_cases.push(r.case(&patterns, &r.echo(&child.to_string())));
+            items.extend(names.iter().map(|v| (v.clone(), describe.clone())));
+        }
+        if !items.is_empty() {
+            subcommands_cases.push(r.case(&[idx.to_string()], &r.list(&items)));
+        }
+        let mut items = vec![];
+        for (names, describe, key) in &cmd.options {
+            if let Some(key) = key {
+                let patterns: Vec<String> = names.iter().map(|v| r.pattern(idx, v)).collect();
+                option_cases.push(r.case(&patterns, &r.echo(key)));
+            }
+            items.extend(names.iter().map(|v| (v.clone(), describe.clone())));
+        }
+        if !items.is_empty() {
+            options_cases.push(r.case(&[idx.to_string()], &r.list(&items)));
+        }
+        for (key, value) in &cmd.values {
+            values_cases.push(r.case(&[r.pattern(idx, key)], &r.value(value)));
+        }
+    }
+    template
+        .replace("__SUBCOMMAND_CASES__", &subcommand_cases.join("\n"))
+        .replace("__OPTION_CASES__", &option_cases.join("\n"))
+        .replace("__SUBCOMMANDS_CASES__", &subcommands_cases.join("\n"))
+        .replace("__OPTIONS_CASES__", &options_cases.join("\n"))
+        .replace("__VALUES_CASES__", &values_cases.join("\n"))
+}
+
+fn words(r: &impl Render, values: &[String]) -> String {
+    values
+        .iter()
+        .map(|v| r.quote(v))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn sh_pattern(idx: usize, key: &str) -> String {
+    if key == REST_POSITIONALS {
+        format!("{}*", escape_shell_words(&format!("{idx}:@")))
+    } else {
+        escape_shell_words(&format!("{idx}:{key}"))
+    }
+}
+
+struct Bash;
+
+impl Render for Bash {
+    fn case(&self, patterns: &[String], body: &str) -> String {
+        format!("    {}) {body} ;;", patterns.join(" | "))
+    }
+
+    fn pattern(&self, idx: usize, key: &str) -> String {
+        sh_pattern(idx, key)
+    }
+
+    fn quote(&self, value: &str) -> String {
+        escape_shell_words(value)
+    }
+
+    fn echo(&self, value: &str) -> String {
+        format!("echo {}", self.quote(value))
+    }
+
+    fn list(&self, items: &[(String, String)]) -> String {
+        let names: Vec<String> = items.iter().map(|(v, _)| v.clone()).collect();
+        format!("__FN___add \"$2\" \"\" {}", words(self, &names))
+    }
+
+    fn value(&self, value: &CompValue) -> String {
+        match value {
+            CompValue::Choices(choices) => {
+                format!("__FN___add \"$3\" \"$4\" {}", words(self, choices))
+            }
+            CompValue::ChoiceFn(choice_fn) => {
+                format!("__FN___fn \"$3\" \"$4\" {}", self.quote(choice_fn))
+            }
+            CompValue::Path => "__FN___path \"$3\" \"$4\" -f".into(),
+            CompValue::Dir => "__FN___path \"$3\" \"$4\" -d".into(),
+        }
+    }
+}
+
+struct Zsh;
+
+impl Render for Zsh {
+    fn case(&self, patterns: &[String], body: &str) -> String {
+        format!("    {}) {body} ;;", patterns.join(" | "))
+    }
+
+    fn pattern(&self, idx: usize, key: &str) -> String {
+        sh_pattern(idx, key)
+    }
+
+    fn quote(&self, value: &str) -> String {
+        escape_shell_words(value)
+    }
+
+    fn echo(&self, value: &str) -> String {
+        format!("echo {}", self.quote(value))
+    }
+
+    fn list(&self, items: &[(String, String)]) -> String {
+        let items: Vec<String> = items
+            .iter()
+            .map(|(name, describe)| {
+                let name = name.replace(':', "\\:");
+                if describe.is_empty() {
+                    name
+                } else {
+                    format!("{name}:{describe}")
+                }
+            })
+            .collect();
+        format!("items=({})", words(self, &items))
+    }
+
+    fn value(&self, value: &CompValue) -> String {
+        match value {
+            CompValue::Choices(choices) => format!("compadd -- {}", words(self, choices)),
+            CompValue::ChoiceFn(choice_fn) => format!("__FN___fn {}", self.quote(choice_fn)),
+            CompValue::Path => "_files".into(),
+            CompValue::Dir => "_files -/".into(),
+        }
+    }
+}
+
+struct Fish;
+
+impl Render for Fish {
+    fn case(&self, patterns: &[String], body: &str) -> String {
+        format!("        case {}\n            {body}", patterns.join(" "))
+    }
+
+    fn pattern(&self, idx: usize, key: &str) -> String {
+        if key == REST_POSITIONALS {
+            format!("{}*", fish_quote(&format!("{idx}:@")))
+        } else {
+            fish_quote(&format!("{idx}:{key}"))
+        }
+    }
+
+    fn quote(&self, value: &str) -> String {
+        fish_quote(value)
+    }
+
+    fn echo(&self, value: &str) -> String {
+        format!("echo {}", self.quote(value))
+    }
+
+    fn list(&self, items: &[(String, String)]) -> String {
+        let values: Vec<String> = items
+            .iter()
+            .flat_map(|(name, describe)| [name.clone(), describe.clone()])
+            .collect();
+        format!("printf '%s\\t%s\\n' {}", words(self, &values))
+    }
+
+    fn value(&self, value: &CompValue) -> String {
+        match value {
+            CompValue::Choices(choices) => {
+                format!("__FN___add \"$argv[4]\" {}", words(self, choices))
+            }
+            CompValue::ChoiceFn(choice_fn) => format!(
+                "__FN___fn \"$argv[3]\" \"$argv[4]\" {}",
+                self.quote(choice_fn)
+            ),
+            CompValue::Path => "__FN___path \"$argv[3]\" \"$argv[4]\"".into(),
+            CompValue::Dir => "__FN___dir \"$argv[3]\" \"$argv[4]\"".into(),
+        }
+    }
+}
+
+fn fish_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\\', "\\\\").replace('\'', "\\'"))
+}
@@ -0,0 +1,547 @@
+use crate::{
+    command::Command,
+    param::{Param, ParamData},
+};
+
+use anyhow::{bail, Result};
+use indexmap::IndexMap;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DocFormat {
+    Markdown,
+    Html,
+}
+
+impl DocFormat {
+    pub fn list_names() -> &'static str {
+        "markdown,html"
+    }
+
+    fn extension(&self) -> &'static str {
+        match self {
+            DocFormat::Markdown => "md",
+            DocFormat::Html => "html",
+        }
+    }
+}
+
+impl FromStr for DocFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s {
+            "markdown" | "md" => Ok(Self::Markdown),
+            "html" => Ok(Self::Html),
+            _ => bail!(
+                "The provided format is either invalid or missing, must be one of {}",
+                DocFormat::list_names(),
+            ),
+        }
+    }
+}
+
+/// Generate reference documentation, one page per command or a single combined page
+pub fn docgen(
+    source: &str,
+    root_name: &str,
+    format: DocFormat,
+    single_page: bool,
+) -> Result<Vec<(String, String)>> {
+    let root_cmd = Command::new(source, root_name)?;
+    let linker = Linker {
+        extension: format.extension(),
+        single_page,
+    };
+    let mut pages = vec![];
+    docpage_impl(&mut pages, &root_cmd, &[], &linker);
+    let output = if single_page {
+        let blocks: Vec<Block> = pages.into_iter().flat_map(|(_, blocks)| blocks).collect();
+        let filename = linker.filename(&root_cmd);
+        vec![(
+            filename,
+            render_page(format, &root_cmd.full_name(), &blocks),
+        )]
+    } else {
+        pages
+            .into_iter()
+            .map(|(cmd, blocks)| {
+                let title = cmd.full_name();
+                (linker.filename(cmd), render_page(format, &title, &blocks))
+            })
+            .collect()
+    };
+    Ok(output)
+}
+
+struct Linker {
+    extension: &'static str,
+    single_page: bool,
+}
+
+impl Linker {
+    fn filename(&self, cmd: &Command) -> String {
+        format!("{}.{}", cmd.full_name(), self.extension)
+    }
+
+    fn href(&self, cmd: &Command) -> String {
+        if self.single_page {
+            format!("#{}", cmd.full_name())
+        } else {
+            self.filename(cmd)
+        }
+    }
+
+    fn heading_level(&self) -> usize {
+        if self.single_page {
+            2
+        } else {
+            1
+        }
+    }
+}
+
+enum Block {
+    Heading(usize, String, String),
+    Text(Vec<Inline>),
+    Code(String),
+    List(Vec<Item>),
+}
+
+enum Inline {
+    Text(String),
+    Code(String),
+    Link(String, String),
+    LineBreak,
+}
+
+struct Item {
+    head: Vec<Inline>,
+    notes: Vec<Vec<Inline>>,
+}
+
+fn docpage_impl<'a>(
+    pages: &mut Vec<(&'a Command, Vec<Block>)>,
+    cmd: &'a Command,
+    parents: &[&Command],
+    linker: &Linker,
+) {
+    let blocks = render_command(cmd, parents, linker);
+    pages.push((cmd, blocks));
+    let mut parents = parents.to_vec();
+    parents.push(cmd);
+    for subcmd in cmd.subcommands.iter().filter(|v| !v.hidden()) {
+        docpage_impl(pages, subcmd, &parents, linker);
+    }
+}
+
+fn render_command(cmd: &Command, parents: &[&Command], linker: &Linker) -> Vec<Block> {
+    let level = linker.heading_level();
+    let mut blocks = vec![Block::Heading(
+        level,
+        cmd.cmd_paths().join(" "),
+        cmd.full_name(),
+    )];
+    if !parents.is_empty() {
+        let mut line = vec![];
+        for parent in parents {
+            let name = parent.cmd_paths().last().cloned().unwrap_or_default();
+            line.push(Inline::Link(name, linker.href(parent)));
+            line.push(Inline::Text(" › ".into()));
+        }
+        line.push(Inline::Text(
+            cmd.cmd_paths().last().cloned().unwrap_or_default(),
+        ));
+        blocks.push(Block::Text(line));
+    }
+    for paragraph in cmd.describe.split("\n\n") {
+        if !paragraph.trim().is_empty() {
+            blocks.push(Block::Text(render_describe(paragraph)));
+        }
+    }
+    let aliases = cmd.list_alias_names();
+    if !aliases.is_empty() {
+        let mut line = vec![Inline::Text("Aliases: ".into())];
+        push_code_list(&mut line, aliases.iter().map(|v| v.as_str()));
+        blocks.push(Block::Text(line));
+    }
+    blocks.push(Block::Heading(level + 1, "Usage".into(), String::new()));
+    blocks.push(Block::Code(render_usage(cmd)));
+    render_positionals_section(&mut blocks, cmd, level);
+    render_options_section(&mut blocks, cmd, level);
+    render_envs_section(&mut blocks, cmd, level);
+    render_subcommands_section(&mut blocks, cmd, level, linker);
+    render_examples_section(&mut blocks, cmd, level);
+    if let Some(version) = &cmd.version {
+        blocks.push(Block::Heading(level + 1, "Version".into(), String::new()));
+        blocks.push(Block::Text(vec![Inline::Text(version.clone())]));
+    }
+    blocks
+}
+
+fn render_usage(cmd: &Command) -> String {
+    let mut output = cmd.cmd_paths();
+    if !cmd.flag_option_params.is_empty() {
+        output.push("[OPTIONS]".into());
+    }
+    if !cmd.subcommands.is_empty() {
+        output.push("<COMMAND>".into());
+    } else {
+        output.extend(
+            cmd.positional_params
+                .iter()
+                .filter(|v| !v.hidden())
+                .map(|v| v.render_notation()),
+        );
+    }
+    output.join(" ")
+}
+
+fn render_positionals_section(blocks: &mut Vec<Block>, cmd: &Command, level: usize) {
+    let params: Vec<_> = cmd
+        .positional_params
+        .iter()
+        .filter(|v| !v.hidden())
+        .collect();
+    if params.is_empty() {
+        return;
+    }
+    blocks.push(Block::Heading(level + 1, "Arguments".into(), String::new()));
+    let items = params
+        .into_iter()
+        .map(|param| render_param(param, vec![Inline::Code(param.render_notation())]))
+        .collect();
+    blocks.push(Block::List(items));
+}
+
+fn render_options_section(blocks: &mut Vec<Block>, cmd: &Command, level: usize) {
+    let mut params = cmd.all_flag_options();
+    params.retain(|v| !v.hidden());
+    if params.is_empty() {
+        return;
+    }
+    blocks.push(Block::Heading(level + 1, "Options".into(), String::new()));
+    let mut sections: IndexMap<Option<&str>, Vec<Item>> = IndexMap::from([(None, vec![])]);
+    for param in params {
+        let mut name = String::new();
+        if let Some(short) = param.short() {
+            name.push_str(&format!("{short}, "));
+        }
+        name.push_str(&param.render_long_name());
+        if !param.is_flag() {
+            name.push(' ');
+            name.push_str(&param.render_notations());
+        }
+        let item = render_param(param, vec![Inline::Code(name)]);
+        sections.entry(param.group()).or_default().push(item);
+    }
+    push_sections(blocks, sections, level);
+}
+
+fn render_envs_section(blocks: &mut Vec<Block>, cmd: &Command, level: usize) {
+    if cmd.env_params.is_empty() {
+        return;
+    }
+    blocks.push(Block::Heading(
+        level + 1,
+        "Environment Variables".into(),
+        String::new(),
+    ));
+    let items = cmd
+        .env_params
+        .iter()
+        .map(|param| render_param(param, vec![Inline::Code(param.var_name())]))
+        .collect();
+    blocks.push(Block::List(items));
+}
+
+fn render_subcommands_section(
+    blocks: &mut Vec<Block>,
+    cmd: &Command,
+    level: usize,
+    linker: &Linker,
+) {
+    let subcommands: Vec<_> = cmd.subcommands.iter().filter(|v| !v.hidden()).collect();
+    if subcommands.is_empty() {
+        return;
+    }
+    blocks.push(Block::Heading(level + 1, "Commands".into(), String::new()));
+    let mut sections: IndexMap<Option<&str>, Vec<Item>> = IndexMap::from([(None, vec![])]);
+    for subcmd in subcommands {
+        let name = subcmd.name.clone().unwrap_or_default();
+        let mut head = vec![Inline::Link(name, linker.href(subcmd))];
+        let describe = subcmd.describe_oneline();
+        if !describe.is_empty() {
+            head.push(Inline::Text(format!(": {describe}")));
+        }
+        let mut notes = vec![];
+        let aliases = subcmd.list_alias_names();
+        if !aliases.is_empty() {
+            let mut note = vec![Inline::Text("aliases: ".into())];
+            push_code_list(&mut note, aliases.iter().map(|v| v.as_str()));
+            notes.push(note);
+        }
+        if subcmd.deprecated().is_some() {
+            notes.push(vec![Inline::Text("deprecated".into())]);
+        }
+        let item = Item { head, notes };
+        sections
+            .entry(subcmd.group.as_deref())
+            .or_default()
+            .push(item);
+    }
+    push_sections(blocks, sections, level);
+}
+
+/// Push the ungrouped items, then each `@group` under its own heading
+fn push_sections(
+    blocks: &mut Vec<Block>,
+    sections: IndexMap<Option<&str>, Vec<Item>>,
+    level: usize,
+) {
+    for (group, items) in sections {
+        if items.is_empty() {
+            continue;
+        }
+        if let Some(group) = group {
+            blocks.push(Block::Heading(level + 2, group.into(), String::new()));
+        }
+        blocks.push(Block::List(items));
+    }
+}
+
+fn render_examples_section(blocks: &mut Vec<Block>, cmd: &Command, level: usize) {
+    if cmd.examples.is_empty() {
+        return;
+    }
+    blocks.push(Block::Heading(level + 1, "Examples".into(), String::new()));
+    let items = cmd
+        .examples
+        .iter()
+        .map(|(value, describe, _)| {
+            let mut head = vec![Inline::Code(value.clone())];
+            if !describe.is_empty() {
+                head.push(Inline::Text(format!(": {describe}")));
+            }
+            Item {
+                head,
+                notes: vec![],
+            }
+        })
+        .collect();
+    blocks.push(Block::List(items));
+}
+
+fn render_param<T: Param>(param: &T, mut head: Vec<Inline>) -> Item {
+    if !param.describe().is_empty() {
+        head.push(Inline::Text(": ".into()));
+        head.extend(render_describe(param.describe()));
+    }
+    let mut notes = vec![];
+    if param.required() {
+        notes.push(vec![Inline::Text("required".into())]);
+    }
+    if let Some(value) = param.default_value() {
+        notes.push(vec![
+            Inline::Text("default: ".into()),
+            Inline::Code(value.clone()),
+        ]);
+    }
+    if let Some(values) = param.choice_values() {
+        notes.push(render_choices(param.data(), values));
+    }
+    if let Some(env) = param.bind_env() {
+        notes.push(vec![Inline::Text("env: ".into()), Inline::Code(env)]);
+    }
+    if param.deprecated().is_some() {
+        notes.push(vec![Inline::Text("deprecated".into())]);
+    }
+    Item { head, notes }
+}
+
+fn render_choices(data: &ParamData, values: &[String]) -> Vec<Inline> {
+    let mut output = vec![Inline::Text("possible values: ".into())];
+    for (i, value) in values.iter().enumerate() {
+        if i > 0 {
+            output.push(Inline::Text(", ".into()));
+        }
+        output.push(Inline::Code(value.clone()));
+        let aliases = data.choice_aliases_of(value);
+        if !aliases.is_empty() {
+            output.push(Inline::Text(" (".into()));
+            push_code_list(&mut output, aliases.into_iter());
+            output.push(Inline::Text(")".into()));
+        }
+    }
+    if data.choice_ignore_case {
+        output.push(Inline::Text(" (case-insensitive)".into()));
+    }
+    output
+}
+
+fn render_describe(describe: &str) -> Vec<Inline> {
+    let mut output = vec![];
+    for (i, line) in describe.lines().enumerate() {
+        if i > 0 {
+            output.push(Inline::LineBreak);
+        }
+        output.push(Inline::Text(line.to_string()));
+    }
+    output
+}
+
+fn push_code_list<'a>(output: &mut Vec<Inline>, values: impl Iterator<Item = &'a str>) {
+    for (i, value) in values.enumerate() {
+        if i > 0 {
+            output.push(Inline::Text(", ".into()));
+        }
+        output.push(Inline::Code(value.to_string()));
+    }
+}
+
+fn render_page(format: DocFormat, title: &str, blocks: &[Block]) -> String {
+    match format {
+        DocFormat::Markdown => render_markdown(blocks),
+        DocFormat::Html => render_html(title, blocks),
+    }
+}
+
+fn render_markdown(blocks: &[Block]) -> String {
+    let mut output = vec![];
+    for block in blocks {
+        let text = match block {
+            Block::Heading(level, text, _) => {
+                format!("{} {}", "#".repeat(*level), escape_markdown(text))
+            }
+            Block::Text(inlines) => render_markdown_inlines(inlines, ""),
+            Block::Code(code) => format!("```\n{code}\n```"),
+            Block::List(items) => items
+                .iter()
+                .map(|item| {
+                    let mut lines =
+                        vec![format!("- {}", render_markdown_inlines(&item.head, "  "))];
+                    for note in &item.notes {
+                        lines.push(format!("  - {}", render_markdown_inlines(note, "    ")));
+                    }
+                    lines.join("\n")
+                })
+                .collect::<Vec<String>>()
+                .join("\n"),
+        };
+        output.push(text);
+    }
+    format!("{}\n", output.join("\n\n"))
+}
+
+fn render_markdown_inlines(inlines: &[Inline], indent: &str) -> String {
+    inlines
+        .iter()
+        .map(|inline| match inline {
+            Inline::Text(text) => escape_markdown(text),
+            Inline::Code(code) => {
+                if code.contains('`') {
+                    format!("`` {code} ``")
+                } else {
+                    format!("`{code}`")
+                }
+            }
+            Inline::Link(text, href) => format!("[{}]({href})", escape_markdown(text)),
+            Inline::LineBreak => format!("<br>\n{indent}"),
+        })
+        .collect()
+}
+
+fn escape_markdown(text: &str) -> String {
+    let mut output = String::with_capacity(text.len());
+    for ch in text.chars() {
+        if matches!(
+            ch,
+            '\\' | '`' | '*' | '_' | '[' | ']' | '<' | '>' | '#' | '|'
+        ) {
+            output.push('\\');
+        }
+        output.push(ch);
+    }
+    output
+}
+
+fn render_html(title: &str, blocks: &[Block]) -> String {
+    let mut output = vec![
+        "<!DOCTYPE html>".to_string(),
+        "<html>".to_string(),
+        "<head>".to_string(),
+        r#"<meta charset="utf-8">"#.to_string(),
+        format!("<title>{}</title>", escape_html(title)),
+        "</head>".to_string(),
+        "<body>".to_string(),
+    ];
+    for block in blocks {
+        let text = match block {
+            Block::Heading(level, text, id) => {
+                let id = if id.is_empty() {
+                    String::new()
+                } else {
+                    format!(r#" id="{}""#, escape_html(id))
+                };
+                format!("<h{level}{id}>{}</h{level}>", escape_html(text))
+            }
+            Block::Text(inlines) => format!("<p>{}</p>", render_html_inlines(inlines)),
+            Block::Code(code) => format!("<pre><code>{}</code></pre>", escape_html(code)),
+            Block::List(items) => {
+                let mut lines = vec!["<ul>".to_string()];
+                for item in items {
+                    let head = render_html_inlines(&item.head);
+                    if item.notes.is_empty() {
+                        lines.push(format!("<li>{head}</li>"));
+                    } else {
+                        lines.push(format!("<li>{head}"));
+                        lines.push("<ul>".to_string());
+                        for note in &item.notes {
+                            lines.push(format!("<li>{}</li>", render_html_inlines(note)));
+                        }
+                        lines.push("</ul>".to_string());
+                        lines.push("</li>".to_string());
+                    }
+                }
+                lines.push("</ul>".to_string());
+                lines.join("\n")
+            }
+        };
+        output.push(text);
+    }
+    output.push("</body>".to_string());
+    output.push("</html>".to_string());
+    format!("{}\n", output.join("\n"))
+}
+
+fn render_html_inlines(inlines: &[Inline]) -> String {
+    inlines
+        .iter()
+        .map(|inline| match inline {
+            Inline::Text(text) => escape_html(text),
+            Inline::Code(code) => format!("<code>{}</code>", escape_html(code)),
+            Inline::Link(text, href) => {
+                format!(
+                    r#"<a href="{}">{}</a>"#,
+                    escape_html(href),
+                    escape_html(text)
+                )
+            }
+            Inline::LineBreak => "<br>".to_string(),
+        })
+        .collect()
+}
+
+fn escape_html(text: &str) -> String {
+    let mut output = String::with_capacity(text.len());
+    for ch in text.chars() {
+        match ch {
+            '&' => output.push_str("&amp;"),
+            '<' => output.push_str("&lt;"),
+            '>' => output.push_str("&gt;"),
+            '"' => output.push_str("&quot;"),
+            _ => output.push(ch),
+        }
+    }
+    output
+}
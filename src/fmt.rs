@@ -0,0 +1,143 @@
+use crate::{
+    param::Param,
+    parser::{parse, parse_line, EventData},
+};
+
+use anyhow::{bail, Result};
+
+/// Format comment tags of a script.
+///
+/// Every tag is normalized to `# @tag`. Consecutive `@option`/`@flag`/`@arg`/`@env` lines form a block,
+/// whose tags are re-rendered from their parsed form with short/name/notation/describe columns aligned.
+/// Continuation lines of multi-line descriptions are kept as they are.
+pub fn fmt(source: &str) -> Result<String> {
+    let events = parse(source)?;
+    let lines: Vec<&str> = source.lines().collect();
+    let mut output: Vec<String> = vec![];
+    let mut block: Vec<Row> = vec![];
+    for line in &lines {
+        let row = match parse_line(line) {
+            Ok((_, Some(Some(data)))) => match data {
+                EventData::FlagOption(param) => Some(Row::param(&param)),
+                EventData::Positional(param) => Some(Row::param(&param)),
+                EventData::Env(param) => Some(Row::param(&param)),
+                EventData::Func(_) => None,
+                _ => {
+                    flush_block(&mut block, &mut output);
+                    output.push(normalize_tag(line));
+                    continue;
+                }
+            },
+            _ => None,
+        };
+        match row {
+            Some(row) => block.push(row),
+            None if !block.is_empty() && is_continuation(line) => {
+                block
+                    .last_mut()
+                    .unwrap()
+                    .continuations
+                    .push(line.to_string());
+            }
+            None => {
+                flush_block(&mut block, &mut output);
+                output.push(line.to_string());
+            }
+        }
+    }
+    flush_block(&mut block, &mut output);
+
+    let newline = if source.contains("\r\n") {
+        "\r\n"
+    } else {
+        "\n"
+    };
+    let mut formatted = output.join(newline);
+    if source.ends_with('\n') {
+        formatted.push_str(newline);
+    }
+    if let Ok(new_events) = parse(&formatted) {
+        if let Some((old, _)) = events
+            .iter()
+            .zip(new_events.iter())
+            .find(|(old, new)| old != new)
+        {
+            bail!(
+                "Unable to format line {}, the formatted tag changes its meaning",
+                old.position
+            );
+        }
+        if events.len() == new_events.len() {
+            return Ok(formatted);
+        }
+    }
+    bail!("Unable to format, the formatted script is invalid")
+}
+
+#[derive(Debug)]
+struct Row {
+    tag: String,
+    // short, name with notations, describe
+    columns: [String; 3],
+    continuations: Vec<String>,
+}
+
+impl Row {
+    fn param<T: Param>(param: &T) -> Self {
+        let [mut short, mut name, notations, describe] = param.render_source_columns();
+        // Notations must follow the name after a single space, so they share a column
+        if short.is_empty() && notations.is_empty() && is_short_only(&name) {
+            short = std::mem::take(&mut name);
+        } else if !notations.is_empty() {
+            name = format!("{name} {notations}");
+        }
+        Self {
+            tag: param.tag_name().to_string(),
+            columns: [short, name, describe],
+            continuations: vec![],
+        }
+    }
+}
+
+fn is_short_only(name: &str) -> bool {
+    let mut chars = name.chars();
+    matches!(
+        (chars.next(), chars.next(), chars.next()),
+        (Some('-' | '+'), Some(c), None) if c != '-' && c != '+'
+    )
+}
+
+fn flush_block(block: &mut Vec<Row>, output: &mut Vec<String>) {
+    let tag_width = block.iter().map(|v| v.tag.len()).max().unwrap_or_default();
+    let mut widths = [0; 3];
+    for row in block.iter() {
+        for (i, column) in row.columns.iter().enumerate() {
+            widths[i] = widths[i].max(column.chars().count());
+        }
+    }
+    for row in block.drain(..) {
+        let mut line = format!("# {:<tag_width$}", row.tag);
+        for (column, width) in row.columns.iter().zip(widths) {
+            if width > 0 {
+                line.push(' ');
+                line.push_str(&format!("{column:<width$}"));
+            }
+        }
+        output.push(line.trim_end().to_string());
+        output.extend(row.continuations);
+    }
+}
+
+/// Rewrite the prefix of a tag line like `#@cmd` or `##  @cmd` to `# @cmd`
+fn normalize_tag(line: &str) -> String {
+    let rest = line.trim_start_matches('#').trim_start_matches([' ', '\t']);
+    format!("# {}", rest.trim_end())
+}
+
+/// A comment line that belongs to the multi-line description of the previous tag
+fn is_continuation(line: &str) -> bool {
+    match line.strip_prefix('#') {
+        Some(rest) => !rest.trim_start_matches('#').trim_start().starts_with('@'),
+        None => false,
+    }
+}
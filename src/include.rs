@@ -0,0 +1,208 @@
+use crate::{
+    command::Command,
+    parser::{parse_line, EventData, Position},
+    utils::META_HELP_TEMPLATE,
+};
+
+#[cfg(feature = "lint")]
+use crate::lint::{lint, Diagnostic};
+
+use anyhow::{anyhow, Result};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// Splice the scripts referenced by `@include` tags into the source.
+///
+/// Paths are relative to the including script and every script is included at most once.
+/// The file of `@meta help-template <path>` is spliced in as an inline template.
+/// Returns the merged source and the included files, errors point to the file and line where they occur.
+pub fn resolve_includes(source: &str, script_path: &str) -> Result<(String, Vec<PathBuf>)> {
+    let resolver = Resolver::new(source, script_path)?;
+    if resolver.output.iter().eq(source.lines()) {
+        return Ok((source.to_string(), vec![]));
+    }
+    let merged = resolver.merged(source);
+    if let Err(err) = Command::new(&merged, "prog") {
+        return Err(anyhow!(resolver.locate_lines(&format!("{err:#}"))));
+    }
+    let files = resolver
+        .files
+        .into_iter()
+        .skip(1)
+        .map(|(_, v, _)| v)
+        .collect();
+    Ok((merged, files))
+}
+
+/// Lint a script with the files of its `@include` and `@meta help-template` tags spliced in.
+///
+/// Diagnostics point to the file and line where they occur,
+/// those in a help template point to the `@meta` tag that loads it.
+#[cfg(feature = "lint")]
+pub fn lint_with_includes(source: &str, script_path: &str) -> Result<Vec<Diagnostic>> {
+    let resolver = Resolver::new(source, script_path)?;
+    let mut diagnostics = lint(&resolver.merged(source));
+    for diagnostic in &mut diagnostics {
+        diagnostic.message = resolver.locate_lines(&diagnostic.message);
+        let Some((file_idx, position, verbatim)) = resolver.lines.get(diagnostic.line - 1) else {
+            continue;
+        };
+        diagnostic.line = *position;
+        if !verbatim {
+            diagnostic.column = 1;
+        }
+        if *file_idx > 0 {
+            diagnostic.file = Some(resolver.files[*file_idx].1.display().to_string());
+        }
+    }
+    Ok(diagnostics)
+}
+
+struct Resolver {
+    /// (canonical path, path, name shown in errors) of the root script and the included scripts
+    files: Vec<(PathBuf, PathBuf, String)>,
+    /// (index of file, line in file, whether the line is kept as is) of each line of the merged source
+    lines: Vec<(usize, Position, bool)>,
+    output: Vec<String>,
+}
+
+impl Resolver {
+    fn new(source: &str, script_path: &str) -> Result<Self> {
+        let script_path = Path::new(script_path);
+        let mut resolver = Resolver {
+            files: vec![(
+                canonicalize(script_path),
+                script_path.to_path_buf(),
+                String::new(),
+            )],
+            lines: vec![],
+            output: vec![],
+        };
+        resolver.splice(source, 0, script_path.parent().unwrap_or(Path::new("")))?;
+        Ok(resolver)
+    }
+
+    fn merged(&self, source: &str) -> String {
+        let mut merged = self.output.join("\n");
+        if source.ends_with('\n') {
+            merged.push('\n');
+        }
+        merged
+    }
+
+    fn splice(&mut self, source: &str, file_idx: usize, dir: &Path) -> Result<()> {
+        for (i, line) in source.lines().enumerate() {
+            let position = i + 1;
+            let path = match parse_line(line) {
+                Ok((_, Some(Some(EventData::Include(path))))) => path,
+                Ok((_, Some(Some(EventData::Meta(key, path)))))
+                    if key == META_HELP_TEMPLATE && !path.is_empty() && !path.contains('{') =>
+                {
+                    self.splice_help_template(&path, file_idx, position, dir)?;
+                    continue;
+                }
+                _ => {
+                    self.output.push(line.to_string());
+                    self.lines.push((file_idx, position, true));
+                    continue;
+                }
+            };
+            let file = dir.join(&path);
+            let content = fs::read_to_string(&file).map_err(|err| {
+                anyhow!(
+                    "@include({}) failed to read '{path}', {err}",
+                    self.location(file_idx, position)
+                )
+            })?;
+            let canonical_file = canonicalize(&file);
+            if self.files.iter().any(|(v, _, _)| *v == canonical_file) {
+                continue;
+            }
+            let name = match file_idx {
+                0 => path,
+                _ => Path::new(&self.files[file_idx].2)
+                    .parent()
+                    .unwrap_or(Path::new(""))
+                    .join(&path)
+                    .display()
+                    .to_string(),
+            };
+            self.files.push((canonical_file, file.clone(), name));
+            let included_idx = self.files.len() - 1;
+            self.splice(
+                &content,
+                included_idx,
+                file.parent().unwrap_or(Path::new("")),
+            )?;
+        }
+        Ok(())
+    }
+
+    fn splice_help_template(
+        &mut self,
+        path: &str,
+        file_idx: usize,
+        position: Position,
+        dir: &Path,
+    ) -> Result<()> {
+        let content = fs::read_to_string(dir.join(path)).map_err(|err| {
+            anyhow!(
+                "@meta({}) failed to read help template '{path}', {err}",
+                self.location(file_idx, position)
+            )
+        })?;
+        self.output.push(format!("# @meta {META_HELP_TEMPLATE}"));
+        for line in content.lines() {
+            let line = match line {
+                "" => "#".to_string(),
+                _ if line.starts_with('@') => format!("# \\{line}"),
+                _ => format!("# {line}"),
+            };
+            self.output.push(line);
+        }
+        self.lines
+            .resize(self.output.len(), (file_idx, position, false));
+        Ok(())
+    }
+
+    fn location(&self, file_idx: usize, position: Position) -> String {
+        match file_idx {
+            0 => format!("line {position}"),
+            _ => format!("{}:{position}", self.files[file_idx].2),
+        }
+    }
+
+    /// Rewrite `line N` of the merged source in the message to the original file and line
+    fn locate_lines(&self, message: &str) -> String {
+        let mut output = String::new();
+        let mut rest = message;
+        while let Some(idx) = rest.find("line ") {
+            let (head, tail) = rest.split_at(idx + 5);
+            let digits = tail.chars().take_while(|c| c.is_ascii_digit()).count();
+            let location = tail[..digits]
+                .parse::<usize>()
+                .ok()
+                .and_then(|v| self.lines.get(v.wrapping_sub(1)))
+                .map(|(file_idx, position, _)| self.location(*file_idx, *position));
+            match location {
+                Some(location) => {
+                    output.push_str(&head[..idx]);
+                    output.push_str(&location);
+                }
+                None => {
+                    output.push_str(head);
+                    output.push_str(&tail[..digits]);
+                }
+            }
+            rest = &tail[digits..];
+        }
+        output.push_str(rest);
+        output
+    }
+}
+
+fn canonicalize(path: &Path) -> PathBuf {
+    fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf())
+}
@@ -1,11 +1,23 @@
 mod argc_value;
 #[cfg(feature = "build")]
 mod build;
+mod color;
 mod command;
 #[cfg(feature = "compgen")]
 mod compgen;
 #[cfg(feature = "completions")]
 mod completions;
+#[cfg(feature = "docgen")]
+mod docgen;
+#[cfg(feature = "fmt")]
+mod fmt;
+#[cfg(feature = "include")]
+mod include;
+#[cfg(feature = "lint")]
+mod lint;
+mod locale;
+#[cfg(feature = "lsp")]
+mod lsp;
 #[cfg(feature = "mangen")]
 mod mangen;
 #[cfg(any(feature = "eval", feature = "compgen"))]
@@ -22,14 +34,26 @@ pub use argc_value::ArgcValue;
 #[cfg(feature = "build")]
 pub use build::build;
 #[cfg(feature = "export")]
-pub use command::CommandValue;
+pub use command::{CommandValue, ExampleValue};
 #[cfg(feature = "compgen")]
 pub use compgen::{compgen, compgen_kind, CompKind, COMPGEN_KIND_SYMBOL};
 #[cfg(feature = "completions")]
-pub use completions::generate_completions;
+pub use completions::{generate_completions, generate_standalone_completions};
+#[cfg(feature = "docgen")]
+pub use docgen::{docgen, DocFormat};
+#[cfg(feature = "fmt")]
+pub use fmt::fmt;
+#[cfg(all(feature = "include", feature = "lint"))]
+pub use include::lint_with_includes;
+#[cfg(feature = "include")]
+pub use include::resolve_includes;
+#[cfg(feature = "lint")]
+pub use lint::{lint, Diagnostic, Severity};
+#[cfg(feature = "lsp")]
+pub use lsp::serve_lsp;
 #[cfg(feature = "mangen")]
 pub use mangen::mangen;
-pub use param::{ChoiceValue, DefaultValue};
+pub use param::{ChoiceValue, DefaultValue, ValueType};
 #[cfg(feature = "export")]
 pub use param::{EnvValue, FlagOptionValue, PositionalValue};
 #[cfg(feature = "native-runtime")]
@@ -47,7 +71,8 @@ pub fn eval<T: Runtime>(
     script_path: Option<&str>,
     wrap_width: Option<usize>,
 ) -> Result<Vec<ArgcValue>> {
-    let mut cmd = command::Command::new(script_content, &args[0])?;
+    let locale = locale::locale_from_env(|name| runtime.env_var(name));
+    let mut cmd = command::Command::new_with_locale(script_content, &args[0], locale.as_deref())?;
     if let Some(p) = script_path {
         if cmd.has_metadata(crate::utils::META_EXTERNAL_SUBCOMMANDS) {
             cmd.external_subcommands = command::collect_external_subcommands(runtime, p);
@@ -56,6 +81,24 @@ pub fn eval<T: Runtime>(
     cmd.eval(runtime, args, script_path, wrap_width)
 }
 
+#[cfg(all(feature = "eval", feature = "export"))]
+pub fn eval_json<T: Runtime>(
+    runtime: T,
+    script_content: &str,
+    args: &[String],
+    script_path: Option<&str>,
+    wrap_width: Option<usize>,
+) -> Result<serde_json::Value> {
+    let locale = locale::locale_from_env(|name| runtime.env_var(name));
+    let mut cmd = command::Command::new_with_locale(script_content, &args[0], locale.as_deref())?;
+    if let Some(p) = script_path {
+        if cmd.has_metadata(crate::utils::META_EXTERNAL_SUBCOMMANDS) {
+            cmd.external_subcommands = command::collect_external_subcommands(runtime, p);
+        }
+    }
+    cmd.eval_json(runtime, args, script_path, wrap_width)
+}
+
 #[cfg(feature = "export")]
 pub fn export(source: &str, root_name: &str) -> Result<CommandValue> {
     let cmd = command::Command::new(source, root_name)?;
@@ -0,0 +1,317 @@
+use crate::{
+    command::Command,
+    param::Param,
+    parser::{parse_lenient, Event, EventData, Position},
+    runtime::native::NativeRuntime,
+    utils::{
+        META_ABBREVIATE_LONG_OPTIONS, META_ARGFILE, META_BINNAME, META_COMBINE_SHORTS,
+        META_DEFAULT_SUBCOMMAND, META_DEPRECATED, META_DOTENV, META_EXTERNAL_SUBCOMMANDS,
+        META_HELP_TEMPLATE, META_HIDDEN, META_INHERIT_FLAG_OPTIONS, META_LOCALE, META_MAN_SECTION,
+        META_MESSAGE, META_REQUIRE_TOOLS, META_SYMBOL, META_VERSION,
+    },
+};
+
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+const META_KEYS: [&str; 17] = [
+    META_VERSION,
+    META_BINNAME,
+    META_DOTENV,
+    META_DEFAULT_SUBCOMMAND,
+    META_INHERIT_FLAG_OPTIONS,
+    META_SYMBOL,
+    META_COMBINE_SHORTS,
+    META_ABBREVIATE_LONG_OPTIONS,
+    META_ARGFILE,
+    META_EXTERNAL_SUBCOMMANDS,
+    META_MAN_SECTION,
+    META_REQUIRE_TOOLS,
+    META_HIDDEN,
+    META_DEPRECATED,
+    META_HELP_TEMPLATE,
+    META_LOCALE,
+    META_MESSAGE,
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Severity::Error => write!(f, "error"),
+            Severity::Warning => write!(f, "warning"),
+        }
+    }
+}
+
+/// A problem found in a script, `line` and `column` are 1-based
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Diagnostic {
+    /// The included file the problem is in, `None` for the linted script itself
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub file: Option<String>,
+    pub line: usize,
+    pub column: usize,
+    pub severity: Severity,
+    pub code: &'static str,
+    pub message: String,
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}:{}: {}: {} [{}]",
+            self.line, self.column, self.severity, self.message, self.code
+        )
+    }
+}
+
+/// Check a script and report every problem found, sorted by position
+///
+/// `@include` tags are not followed, use `lint_with_includes` to check the included functions too.
+pub fn lint(source: &str) -> Vec<Diagnostic> {
+    let lines: Vec<&str> = source.lines().collect();
+    let (events, errors) = parse_lenient(source);
+    let mut linter = Linter {
+        lines: &lines,
+        diagnostics: vec![],
+    };
+    for (position, err) in errors {
+        let message = match err {
+            Some(err) => format!("fail to parse, {err}"),
+            None => "syntax error".to_string(),
+        };
+        linter.error(position, None, "syntax-error", message);
+    }
+    linter.check_events(&events);
+    match Command::new(source, "prog") {
+        Ok(cmd) => {
+            for (position, message) in cmd.check_examples(NativeRuntime) {
+                linter.error(position, Some("@example"), "invalid-example", message);
+            }
+        }
+        Err(err) => {
+            let message = err.to_string();
+            let position = error_line(&message).unwrap_or(1);
+            if !linter
+                .diagnostics
+                .iter()
+                .any(|v| v.line == position && v.severity == Severity::Error)
+            {
+                linter.error(position, None, "invalid", message);
+            }
+        }
+    }
+    let mut diagnostics = linter.diagnostics;
+    diagnostics.sort_by_key(|v| (v.line, v.column));
+    diagnostics
+}
+
+struct Linter<'a> {
+    lines: &'a [&'a str],
+    diagnostics: Vec<Diagnostic>,
+}
+
+type Aliases = Option<(Vec<String>, Position)>;
+
+#[derive(Debug)]
+struct LintCmd {
+    cmd_pos: Position,
+    aliases: Aliases,
+}
+
+impl Linter<'_> {
+    fn check_events(&mut self, events: &[Event]) {
+        let mut pending_cmd: Option<LintCmd> = None;
+        // (fn name, position, aliases)
+        let mut cmds: Vec<(String, Position, Aliases)> = vec![];
+        let mut fns: HashSet<String> = HashSet::new();
+        let mut param_fns: Vec<(String, Position)> = vec![];
+        for Event { data, position } in events {
+            let position = *position;
+            match data {
+                EventData::Cmd(_) => {
+                    if let Some(cmd) = pending_cmd.take() {
+                        self.cmd_without_fn(cmd.cmd_pos);
+                    }
+                    pending_cmd = Some(LintCmd {
+                        cmd_pos: position,
+                        aliases: None,
+                    });
+                }
+                EventData::Aliases(names) => {
+                    if let Some(cmd) = pending_cmd.as_mut() {
+                        cmd.aliases = Some((names.clone(), position));
+                    }
+                }
+                EventData::Func(name) => {
+                    fns.insert(name.clone());
+                    if let Some(cmd) = pending_cmd.take() {
+                        cmds.push((name.clone(), position, cmd.aliases));
+                    }
+                }
+                EventData::Meta(key, _) if !META_KEYS.contains(&key.as_str()) => {
+                    self.warning(
+                        position,
+                        Some(key),
+                        "unknown-meta",
+                        format!("@meta '{key}' is unknown"),
+                    );
+                }
+                EventData::FlagOption(param) if param.is_option() => {
+                    collect_param_fns(&mut param_fns, param, position)
+                }
+                EventData::Env(param) => collect_param_fns(&mut param_fns, param, position),
+                EventData::Positional(param) => collect_param_fns(&mut param_fns, param, position),
+                EventData::Unknown(name) => {
+                    self.error(
+                        position,
+                        Some(&format!("@{name}")),
+                        "unknown-tag",
+                        format!("@{name} is unknown tag"),
+                    );
+                }
+                _ => {}
+            }
+        }
+        if let Some(cmd) = pending_cmd {
+            self.cmd_without_fn(cmd.cmd_pos);
+        }
+        for (name, position) in param_fns {
+            if !fns.contains(&name) {
+                self.error(
+                    position,
+                    Some(&name),
+                    "missing-fn",
+                    format!("function '{name}' is missing"),
+                );
+            }
+        }
+        self.check_cmds(&cmds);
+    }
+
+    fn check_cmds(&mut self, cmds: &[(String, Position, Aliases)]) {
+        let mut defined: HashSet<&str> = HashSet::new();
+        // (parent, name) -> position
+        let mut names: HashMap<(String, String), Position> = HashMap::new();
+        for (fn_name, position, aliases) in cmds {
+            let (parent, child) = match fn_name.rsplit_once("::") {
+                Some((parent, child)) => (parent, child),
+                None => ("", fn_name.as_str()),
+            };
+            defined.insert(fn_name);
+            if !parent.is_empty() && !defined.contains(parent) {
+                self.error(
+                    *position,
+                    Some(fn_name),
+                    "unreachable-subcommand",
+                    format!(
+                        "subcommand '{fn_name}' is unreachable, its parent command '{parent}' is not defined before it"
+                    ),
+                );
+                continue;
+            }
+            names.insert((parent.to_string(), child.to_string()), *position);
+            let Some((aliases, aliases_pos)) = aliases else {
+                continue;
+            };
+            for alias in aliases {
+                let key = (parent.to_string(), alias.to_string());
+                if let Some(exist_pos) = names.get(&key) {
+                    self.error(
+                        *aliases_pos,
+                        Some(alias),
+                        "duplicate-alias",
+                        format!("alias '{alias}' conflicts with cmd or alias at line {exist_pos}"),
+                    );
+                } else {
+                    names.insert(key, *aliases_pos);
+                }
+            }
+        }
+    }
+
+    fn cmd_without_fn(&mut self, position: Position) {
+        self.error(
+            position,
+            Some("@cmd"),
+            "cmd-without-fn",
+            "@cmd is not followed by a function".to_string(),
+        );
+    }
+
+    fn error(
+        &mut self,
+        position: Position,
+        needle: Option<&str>,
+        code: &'static str,
+        message: String,
+    ) {
+        self.push(position, needle, Severity::Error, code, message);
+    }
+
+    fn warning(
+        &mut self,
+        position: Position,
+        needle: Option<&str>,
+        code: &'static str,
+        message: String,
+    ) {
+        self.push(position, needle, Severity::Warning, code, message);
+    }
+
+    fn push(
+        &mut self,
+        position: Position,
+        needle: Option<&str>,
+        severity: Severity,
+        code: &'static str,
+        message: String,
+    ) {
+        let line = self
+            .lines
+            .get(position.saturating_sub(1))
+            .copied()
+            .unwrap_or_default();
+        let column = needle
+            .and_then(|v| line.find(v))
+            .or_else(|| line.find(|c: char| !c.is_whitespace()))
+            .unwrap_or_default();
+        self.diagnostics.push(Diagnostic {
+            file: None,
+            line: position,
+            column: line[..column].chars().count() + 1,
+            severity,
+            code,
+            message,
+        });
+    }
+}
+
+fn collect_param_fns<T: Param>(
+    output: &mut Vec<(String, Position)>,
+    param: &T,
+    position: Position,
+) {
+    if let Some(name) = param.default_fn() {
+        output.push((name.clone(), position));
+    }
+    if let Some((name, _)) = param.choice_fn() {
+        output.push((name.clone(), position));
+    }
+}
+
+/// Extract the line number from errors like `@alias(line 3) ...` or `syntax error at line 3`
+fn error_line(message: &str) -> Option<Position> {
+    let (_, rest) = message.split_once("line ")?;
+    let digits: String = rest.chars().take_while(|c| c.is_ascii_digit()).collect();
+    digits.parse().ok()
+}
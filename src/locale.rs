@@ -0,0 +1,479 @@
+use std::collections::HashMap;
+
+type Catalog = &'static [(&'static str, &'static str)];
+
+/// Shipped catalogs, the English one defines every message
+const CATALOGS: [(&str, Catalog); 4] = [("en", &EN), ("de", &DE), ("ja", &JA), ("zh", &ZH)];
+
+const EN: [(&str, &str); 39] = [
+    ("usage", "USAGE:"),
+    ("args", "ARGS:"),
+    ("options", "OPTIONS:"),
+    ("commands", "COMMANDS:"),
+    ("external-commands", "EXTERNAL COMMANDS:"),
+    ("environments", "ENVIRONMENTS:"),
+    ("examples", "EXAMPLES:"),
+    ("print-help", "Print help"),
+    ("print-version", "Print version"),
+    ("possible-values", "[possible values: {values}]"),
+    ("default-value", "[default: {value}]"),
+    ("env-value", "[env: {env}]"),
+    ("conflicts-with", "[conflicts with: {names}]"),
+    ("requires", "[requires: {names}]"),
+    ("deprecated", "[deprecated]"),
+    ("aliases", "[aliases: {aliases}]"),
+    ("default-subcommand", "[default]"),
+    (
+        "missing-subcommand",
+        "error: `{cmd}` requires a subcommand but one was not provided",
+    ),
+    (
+        "invalid-subcommand",
+        "error: `{cmd}` requires a subcommand but '{arg}' is not one of them",
+    ),
+    ("subcommands", "[subcommands: {names}]"),
+    (
+        "unexpected-argument",
+        "error: unexpected argument `{name}` found",
+    ),
+    (
+        "missing-arguments",
+        "error: the following required arguments were not provided:",
+    ),
+    (
+        "missing-argument",
+        "error: the required arguments `{name}` were not provided",
+    ),
+    (
+        "missing-environments",
+        "error: the following required environments were not provided:",
+    ),
+    (
+        "multiple-occurrences",
+        "error: the argument `{name}` cannot be used multiple times",
+    ),
+    (
+        "invalid-value",
+        "error: invalid value `{value}` for {target}",
+    ),
+    ("env-target", "environment variable `{env}`"),
+    (
+        "bind-env-target",
+        "environment variable `{env}` that bound to `{name}`",
+    ),
+    (
+        "invalid-env",
+        "error: environment variable `{env}` has invalid value for param '{name}'",
+    ),
+    ("expected", "[expected: {expected}]"),
+    (
+        "incorrect-values",
+        "error: incorrect number of values for `{name}`",
+    ),
+    (
+        "no-flag-value",
+        "error: flag `{name}` don't accept any value",
+    ),
+    (
+        "conflicting-arguments",
+        "error: the argument `{name}` cannot be used with `{other}`",
+    ),
+    (
+        "missing-requires",
+        "error: the argument `{name}` requires the following arguments:",
+    ),
+    ("ambiguous-argument", "error: ambiguous argument `{name}`"),
+    ("possible-arguments", "[possible arguments: {names}]"),
+    (
+        "similar-subcommand",
+        "tip: a similar subcommand exists: '{value}'",
+    ),
+    (
+        "similar-argument",
+        "tip: a similar argument exists: '{value}'",
+    ),
+    ("similar-value", "tip: a similar value exists: '{value}'"),
+];
+
+const DE: [(&str, &str); 39] = [
+    ("usage", "VERWENDUNG:"),
+    ("args", "ARGUMENTE:"),
+    ("options", "OPTIONEN:"),
+    ("commands", "BEFEHLE:"),
+    ("external-commands", "EXTERNE BEFEHLE:"),
+    ("environments", "UMGEBUNGSVARIABLEN:"),
+    ("examples", "BEISPIELE:"),
+    ("print-help", "Hilfe anzeigen"),
+    ("print-version", "Version anzeigen"),
+    ("possible-values", "[mögliche Werte: {values}]"),
+    ("default-value", "[Standard: {value}]"),
+    ("env-value", "[Umgebung: {env}]"),
+    ("conflicts-with", "[nicht kombinierbar mit: {names}]"),
+    ("requires", "[erfordert: {names}]"),
+    ("deprecated", "[veraltet]"),
+    ("aliases", "[Aliase: {aliases}]"),
+    ("default-subcommand", "[Standard]"),
+    (
+        "missing-subcommand",
+        "Fehler: `{cmd}` erfordert einen Unterbefehl, aber keiner wurde angegeben",
+    ),
+    (
+        "invalid-subcommand",
+        "Fehler: `{cmd}` erfordert einen Unterbefehl, aber '{arg}' ist keiner davon",
+    ),
+    ("subcommands", "[Unterbefehle: {names}]"),
+    (
+        "unexpected-argument",
+        "Fehler: unerwartetes Argument `{name}` gefunden",
+    ),
+    (
+        "missing-arguments",
+        "Fehler: die folgenden erforderlichen Argumente wurden nicht angegeben:",
+    ),
+    (
+        "missing-argument",
+        "Fehler: die erforderlichen Argumente `{name}` wurden nicht angegeben",
+    ),
+    (
+        "missing-environments",
+        "Fehler: die folgenden erforderlichen Umgebungsvariablen wurden nicht gesetzt:",
+    ),
+    (
+        "multiple-occurrences",
+        "Fehler: das Argument `{name}` darf nicht mehrfach verwendet werden",
+    ),
+    (
+        "invalid-value",
+        "Fehler: ungültiger Wert `{value}` für {target}",
+    ),
+    ("env-target", "Umgebungsvariable `{env}`"),
+    (
+        "bind-env-target",
+        "Umgebungsvariable `{env}`, gebunden an `{name}`",
+    ),
+    (
+        "invalid-env",
+        "Fehler: Umgebungsvariable `{env}` hat einen ungültigen Wert für Parameter '{name}'",
+    ),
+    ("expected", "[erwartet: {expected}]"),
+    (
+        "incorrect-values",
+        "Fehler: falsche Anzahl von Werten für `{name}`",
+    ),
+    (
+        "no-flag-value",
+        "Fehler: Flag `{name}` akzeptiert keinen Wert",
+    ),
+    (
+        "conflicting-arguments",
+        "Fehler: das Argument `{name}` kann nicht zusammen mit `{other}` verwendet werden",
+    ),
+    (
+        "missing-requires",
+        "Fehler: das Argument `{name}` erfordert die folgenden Argumente:",
+    ),
+    (
+        "ambiguous-argument",
+        "Fehler: mehrdeutiges Argument `{name}`",
+    ),
+    ("possible-arguments", "[mögliche Argumente: {names}]"),
+    (
+        "similar-subcommand",
+        "Tipp: ein ähnlicher Unterbefehl existiert: '{value}'",
+    ),
+    (
+        "similar-argument",
+        "Tipp: ein ähnliches Argument existiert: '{value}'",
+    ),
+    (
+        "similar-value",
+        "Tipp: ein ähnlicher Wert existiert: '{value}'",
+    ),
+];
+
+const JA: [(&str, &str); 39] = [
+    ("usage", "使い方:"),
+    ("args", "引数:"),
+    ("options", "オプション:"),
+    ("commands", "コマンド:"),
+    ("external-commands", "外部コマンド:"),
+    ("environments", "環境変数:"),
+    ("examples", "例:"),
+    ("print-help", "ヘルプを表示"),
+    ("print-version", "バージョンを表示"),
+    ("possible-values", "[指定可能な値: {values}]"),
+    ("default-value", "[デフォルト: {value}]"),
+    ("env-value", "[環境変数: {env}]"),
+    ("conflicts-with", "[同時に指定できない: {names}]"),
+    ("requires", "[必要: {names}]"),
+    ("deprecated", "[非推奨]"),
+    ("aliases", "[別名: {aliases}]"),
+    ("default-subcommand", "[デフォルト]"),
+    (
+        "missing-subcommand",
+        "エラー: `{cmd}` にはサブコマンドが必要ですが、指定されていません",
+    ),
+    (
+        "invalid-subcommand",
+        "エラー: `{cmd}` にはサブコマンドが必要ですが、'{arg}' はそのいずれでもありません",
+    ),
+    ("subcommands", "[サブコマンド: {names}]"),
+    (
+        "unexpected-argument",
+        "エラー: 予期しない引数 `{name}` が見つかりました",
+    ),
+    (
+        "missing-arguments",
+        "エラー: 次の必須引数が指定されていません:",
+    ),
+    (
+        "missing-argument",
+        "エラー: 必須引数 `{name}` が指定されていません",
+    ),
+    (
+        "missing-environments",
+        "エラー: 次の必須環境変数が設定されていません:",
+    ),
+    (
+        "multiple-occurrences",
+        "エラー: 引数 `{name}` は複数回指定できません",
+    ),
+    (
+        "invalid-value",
+        "エラー: {target} の値 `{value}` は無効です",
+    ),
+    ("env-target", "環境変数 `{env}`"),
+    ("bind-env-target", "`{name}` に対応する環境変数 `{env}`"),
+    (
+        "invalid-env",
+        "エラー: 環境変数 `{env}` の値はパラメータ '{name}' に対して無効です",
+    ),
+    ("expected", "[期待される形式: {expected}]"),
+    (
+        "incorrect-values",
+        "エラー: `{name}` の値の数が正しくありません",
+    ),
+    (
+        "no-flag-value",
+        "エラー: フラグ `{name}` は値を受け付けません",
+    ),
+    (
+        "conflicting-arguments",
+        "エラー: 引数 `{name}` は `{other}` と同時に使用できません",
+    ),
+    (
+        "missing-requires",
+        "エラー: 引数 `{name}` には次の引数が必要です:",
+    ),
+    ("ambiguous-argument", "エラー: 引数 `{name}` はあいまいです"),
+    ("possible-arguments", "[候補: {names}]"),
+    (
+        "similar-subcommand",
+        "ヒント: 似たサブコマンドがあります: '{value}'",
+    ),
+    ("similar-argument", "ヒント: 似た引数があります: '{value}'"),
+    ("similar-value", "ヒント: 似た値があります: '{value}'"),
+];
+
+const ZH: [(&str, &str); 39] = [
+    ("usage", "用法:"),
+    ("args", "参数:"),
+    ("options", "选项:"),
+    ("commands", "命令:"),
+    ("external-commands", "外部命令:"),
+    ("environments", "环境变量:"),
+    ("examples", "示例:"),
+    ("print-help", "打印帮助"),
+    ("print-version", "打印版本"),
+    ("possible-values", "[可选值: {values}]"),
+    ("default-value", "[默认: {value}]"),
+    ("env-value", "[环境变量: {env}]"),
+    ("conflicts-with", "[冲突: {names}]"),
+    ("requires", "[依赖: {names}]"),
+    ("deprecated", "[已弃用]"),
+    ("aliases", "[别名: {aliases}]"),
+    ("default-subcommand", "[默认]"),
+    (
+        "missing-subcommand",
+        "错误: `{cmd}` 需要一个子命令，但未提供",
+    ),
+    (
+        "invalid-subcommand",
+        "错误: `{cmd}` 需要一个子命令，但 '{arg}' 不是其中之一",
+    ),
+    ("subcommands", "[子命令: {names}]"),
+    ("unexpected-argument", "错误: 发现意外的参数 `{name}`"),
+    ("missing-arguments", "错误: 未提供以下必需参数:"),
+    ("missing-argument", "错误: 未提供必需参数 `{name}`"),
+    ("missing-environments", "错误: 未提供以下必需环境变量:"),
+    ("multiple-occurrences", "错误: 参数 `{name}` 不能多次使用"),
+    ("invalid-value", "错误: {target} 的值 `{value}` 无效"),
+    ("env-target", "环境变量 `{env}`"),
+    ("bind-env-target", "绑定到 `{name}` 的环境变量 `{env}`"),
+    (
+        "invalid-env",
+        "错误: 环境变量 `{env}` 的值对参数 '{name}' 无效",
+    ),
+    ("expected", "[期望: {expected}]"),
+    ("incorrect-values", "错误: `{name}` 的值数量不正确"),
+    ("no-flag-value", "错误: 标志 `{name}` 不接受任何值"),
+    (
+        "conflicting-arguments",
+        "错误: 参数 `{name}` 不能与 `{other}` 一起使用",
+    ),
+    ("missing-requires", "错误: 参数 `{name}` 需要以下参数:"),
+    ("ambiguous-argument", "错误: 参数 `{name}` 有歧义"),
+    ("possible-arguments", "[可能的参数: {names}]"),
+    ("similar-subcommand", "提示: 存在相似的子命令: '{value}'"),
+    ("similar-argument", "提示: 存在相似的参数: '{value}'"),
+    ("similar-value", "提示: 存在相似的值: '{value}'"),
+];
+
+/// Messages of the help and the errors, from a catalog and the overrides of `@meta message`
+#[derive(Debug, Clone)]
+pub(crate) struct Messages {
+    catalog: Catalog,
+    overrides: HashMap<String, String>,
+}
+
+impl Default for Messages {
+    fn default() -> Self {
+        Self {
+            catalog: &EN,
+            overrides: HashMap::new(),
+        }
+    }
+}
+
+impl Messages {
+    /// Switch to the catalog of a locale like `de` or `de_DE.UTF-8`, returns false if no catalog is shipped for it
+    pub(crate) fn set_locale(&mut self, locale: &str) -> bool {
+        let lang = locale
+            .split(['_', '-', '.', '@'])
+            .next()
+            .unwrap_or_default()
+            .to_lowercase();
+        match CATALOGS.iter().find(|(name, _)| *name == lang) {
+            Some((_, catalog)) => {
+                self.catalog = catalog;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Override a message, returns false if the key is unknown
+    pub(crate) fn set_message(&mut self, key: &str, text: &str) -> bool {
+        if !EN.iter().any(|(k, _)| *k == key) {
+            return false;
+        }
+        self.overrides.insert(key.to_string(), text.to_string());
+        true
+    }
+
+    pub(crate) fn get(&self, key: &str) -> &str {
+        if let Some(text) = self.overrides.get(key) {
+            return text;
+        }
+        self.catalog
+            .iter()
+            .chain(EN.iter())
+            .find(|(k, _)| *k == key)
+            .map(|(_, v)| *v)
+            .unwrap_or_default()
+    }
+
+    /// Fill the `{name}` placeholders of the message
+    pub(crate) fn render(&self, key: &str, args: &[(&str, &str)]) -> String {
+        self.render_with(key, args, |v| v.to_string())
+    }
+
+    /// Like `render`, also escape the text around the placeholders
+    pub(crate) fn render_with(
+        &self,
+        key: &str,
+        args: &[(&str, &str)],
+        escape: impl Fn(&str) -> String,
+    ) -> String {
+        let mut output = String::new();
+        let mut rest = self.get(key);
+        while let Some(start) = rest.find('{') {
+            let arg = rest[start + 1..].find('}').and_then(|len| {
+                let name = &rest[start + 1..start + 1 + len];
+                let (_, value) = args.iter().find(|(k, _)| *k == name)?;
+                Some((len, value))
+            });
+            match arg {
+                Some((len, value)) => {
+                    output.push_str(&escape(&rest[..start]));
+                    output.push_str(value);
+                    rest = &rest[start + len + 2..];
+                }
+                None => {
+                    output.push_str(&escape(&rest[..start + 1]));
+                    rest = &rest[start + 1..];
+                }
+            }
+        }
+        output.push_str(&escape(rest));
+        output
+    }
+}
+
+/// The locale of messages from `LC_ALL`, `LC_MESSAGES` or `LANG`
+#[cfg(feature = "eval")]
+pub(crate) fn locale_from_env(env_var: impl Fn(&str) -> Option<String>) -> Option<String> {
+    ["LC_ALL", "LC_MESSAGES", "LANG"]
+        .into_iter()
+        .filter_map(env_var)
+        .find(|v| !v.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn placeholders(text: &str) -> Vec<&str> {
+        let mut output: Vec<&str> = text
+            .split('{')
+            .skip(1)
+            .filter_map(|v| v.split_once('}').map(|(name, _)| name))
+            .collect();
+        output.sort();
+        output
+    }
+
+    #[test]
+    fn test_catalogs() {
+        for (name, catalog) in CATALOGS {
+            for ((key, text), (en_key, en_text)) in catalog.iter().zip(EN.iter()) {
+                assert_eq!(key, en_key, "catalog {name}");
+                assert_eq!(
+                    placeholders(text),
+                    placeholders(en_text),
+                    "catalog {name}, message {key}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_render() {
+        let mut messages = Messages::default();
+        assert_eq!(
+            messages.render("unexpected-argument", &[("name", "--foo")]),
+            "error: unexpected argument `--foo` found"
+        );
+        assert!(messages.set_locale("de_DE.UTF-8"));
+        assert_eq!(messages.get("usage"), "VERWENDUNG:");
+        assert!(!messages.set_locale("C.UTF-8"));
+        assert!(messages.set_message("usage", "Aufruf:"));
+        assert!(!messages.set_message("unknown", ""));
+        assert_eq!(messages.get("usage"), "Aufruf:");
+        assert_eq!(
+            messages.render_with("invalid-value", &[("value", "$v")], |v| v
+                .replace('`', "\\`")),
+            "Fehler: ungültiger Wert \\`$v\\` für {target}"
+        );
+    }
+}
@@ -0,0 +1,174 @@
+/// (tag, syntax, description), used for hover and completion of comment tags
+pub(crate) const TAG_DOCS: [(&str, &str, &str); 17] = [
+    (
+        "describe",
+        "@describe <text>",
+        "Sets the description for the command.",
+    ),
+    ("version", "@version <text>", "Sets the version for the command."),
+    (
+        "cmd",
+        "@cmd <text>",
+        "Defines a subcommand, the function on the next lines implements it.",
+    ),
+    (
+        "alias",
+        "@alias <name>(,<name>)*",
+        "Adds aliases for the subcommand.",
+    ),
+    (
+        "dep",
+        "@dep <name>(,<name>)*",
+        "Sets recipes that run before the subcommand.",
+    ),
+    (
+        "sources",
+        "@sources <pattern>...",
+        "Sets the input files of the subcommand, it is skipped when they are up to date.",
+    ),
+    (
+        "generates",
+        "@generates <path>...",
+        "Sets the output files of the subcommand, used with `@sources`.",
+    ),
+    (
+        "include",
+        "@include <path>",
+        "Merges the tags and functions of another script at this point, the path is relative to the script.",
+    ),
+    (
+        "group",
+        "@group <name>?",
+        "Shows the following flags, options and subcommands under a heading in help, an empty name ends the group.",
+    ),
+    (
+        "example",
+        "@example <command line>  <description>?",
+        "Adds a usage example to the help of the command, two spaces separate the description.",
+    ),
+    (
+        "arg",
+        "@arg <name>[modifier][=default] <notation>? <description>",
+        "Defines a positional argument.",
+    ),
+    (
+        "option",
+        "@option [-<short>] [--]<long>[modifier][=default] <notation>... <description>",
+        "Defines an option argument.\n\nModifiers: `!` required, `*` multiple, `+` required and multiple, \
+`[a|b]` choices, ``[`_choice_fn`]`` choices from a function, ``=`_default_fn` `` default from a function.",
+    ),
+    (
+        "flag",
+        "@flag [-<short>] [--]<long>[*] <description>",
+        "Defines a flag argument, `*` allows it to be repeated.",
+    ),
+    (
+        "env",
+        "@env <NAME>[modifier][=default] <description>",
+        "Defines an environment variable.",
+    ),
+    (
+        "conflicts",
+        "@conflicts <name>(,<name>)+",
+        "Declares flags or options that cannot be used together.",
+    ),
+    (
+        "requires",
+        "@requires <name> <name>(,<name>)*",
+        "Declares flags or options required by another one.",
+    ),
+    (
+        "meta",
+        "@meta <name> <value>?",
+        "Adds metadata, e.g. `dotenv`, `symbol` or `default-subcommand`.",
+    ),
+];
+
+/// (key, syntax, description), used for hover and completion of `@meta` keys
+pub(crate) const META_DOCS: [(&str, &str, &str); 17] = [
+    (
+        "version",
+        "@meta version <text>",
+        "Sets the version for the command.",
+    ),
+    (
+        "binname",
+        "@meta binname <name>",
+        "Sets the binary name shown in usage and completions.",
+    ),
+    (
+        "dotenv",
+        "@meta dotenv <path>?",
+        "Loads a dotenv file from a custom path, `.env` by default, if present.",
+    ),
+    (
+        "default-subcommand",
+        "@meta default-subcommand",
+        "Sets the current subcommand as the default.",
+    ),
+    (
+        "inherit-flag-options",
+        "@meta inherit-flag-options",
+        "Subcommands inherit the flags and options of their parent.",
+    ),
+    (
+        "symbol",
+        "@meta symbol <char><name>[`_choice_fn`]?",
+        "Defines a symbol parameter, e.g. `+toolchain`.",
+    ),
+    (
+        "combine-shorts",
+        "@meta combine-shorts",
+        "Allows combining short flags, e.g. `-xf` for `-x -f`.",
+    ),
+    (
+        "abbreviate-long-options",
+        "@meta abbreviate-long-options",
+        "Long flags/options can be abbreviated to an unambiguous prefix.",
+    ),
+    (
+        "argfile",
+        "@meta argfile",
+        "Expands `@path` arguments with the arguments read from that file.",
+    ),
+    (
+        "external-subcommands",
+        "@meta external-subcommands",
+        "Enables external subcommands: scripts named `<cmd>-<name>.sh` in the same directory.",
+    ),
+    (
+        "man-section",
+        "@meta man-section <1-8>",
+        "Overrides the section for the man page, defaulting to 1.",
+    ),
+    (
+        "require-tools",
+        "@meta require-tools <tool>...",
+        "Requires certain tools to be available on the system.",
+    ),
+    (
+        "hidden",
+        "@meta hidden",
+        "Hides the preceding param or the command from help, man pages and completions.",
+    ),
+    (
+        "deprecated",
+        "@meta deprecated [message]",
+        "Marks the preceding param or the command as deprecated, using it prints a warning.",
+    ),
+    (
+        "help-template",
+        "@meta help-template [path]",
+        "Lays out the help with a template, e.g. `{usage}` or `{options}`, read from the path or the comment lines below.",
+    ),
+    (
+        "locale",
+        "@meta locale <lang>",
+        "Shows the help and the errors in a language, e.g. `de`, `ja` or `zh`, instead of `LANG`.",
+    ),
+    (
+        "message",
+        "@meta message <key> <text>",
+        "Overrides a message of the help or the errors, e.g. `usage` or `unexpected-argument`.",
+    ),
+];
@@ -0,0 +1,453 @@
+mod docs;
+
+use self::docs::{META_DOCS, TAG_DOCS};
+use crate::command::Command;
+use crate::include::lint_with_includes;
+use crate::lint::{lint, Diagnostic, Severity};
+use crate::parser::{parse_lenient, EventData, Position};
+
+use anyhow::{Context, Result};
+use indexmap::IndexMap;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::io::{BufRead, Write};
+
+const COMPLETION_KIND_FUNCTION: u8 = 3;
+const COMPLETION_KIND_PROPERTY: u8 = 10;
+const COMPLETION_KIND_KEYWORD: u8 = 14;
+const SYMBOL_KIND_FUNCTION: u8 = 12;
+
+/// Serve the language server protocol over stdio-like streams, until `exit` or the end of input.
+///
+/// Positions follow the protocol default, lines are 0-based and characters count UTF-16 code units.
+pub fn serve_lsp<R: BufRead, W: Write>(mut reader: R, mut writer: W) -> Result<()> {
+    let mut documents: HashMap<String, String> = HashMap::new();
+    while let Some(message) = read_message(&mut reader)? {
+        let message = match message {
+            Ok(message) => message,
+            Err(err) => {
+                let response = json!({
+                    "jsonrpc": "2.0",
+                    "id": Value::Null,
+                    "error": { "code": -32700, "message": format!("Parse error: {err}") },
+                });
+                write_message(&mut writer, &response)?;
+                continue;
+            }
+        };
+        let method = message["method"].as_str().unwrap_or_default();
+        let params = &message["params"];
+        let uri = params["textDocument"]["uri"]
+            .as_str()
+            .unwrap_or_default()
+            .to_string();
+        let line = params["position"]["line"].as_u64().unwrap_or_default() as usize;
+        let character = params["position"]["character"].as_u64().unwrap_or_default() as usize;
+        let document = |uri: &str| documents.get(uri).cloned().unwrap_or_default();
+        let result = match method {
+            "initialize" => Some(json!({
+                "capabilities": {
+                    "textDocumentSync": 1,
+                    "hoverProvider": true,
+                    "completionProvider": { "triggerCharacters": ["@", "`"] },
+                    "definitionProvider": true,
+                    "documentSymbolProvider": true,
+                },
+                "serverInfo": { "name": "argc", "version": env!("CARGO_PKG_VERSION") },
+            })),
+            "shutdown" => Some(Value::Null),
+            "exit" => break,
+            "textDocument/didOpen" => {
+                let text = params["textDocument"]["text"]
+                    .as_str()
+                    .unwrap_or_default()
+                    .to_string();
+                publish_diagnostics(&mut writer, &uri, &text)?;
+                documents.insert(uri, text);
+                None
+            }
+            "textDocument/didChange" => {
+                let changes = params["contentChanges"].as_array();
+                if let Some(text) = changes.and_then(|v| v.last()?["text"].as_str()) {
+                    publish_diagnostics(&mut writer, &uri, text)?;
+                    documents.insert(uri, text.to_string());
+                }
+                None
+            }
+            "textDocument/didClose" => {
+                documents.remove(&uri);
+                publish_diagnostics(&mut writer, &uri, "")?;
+                None
+            }
+            "textDocument/hover" => {
+                Some(hover(&document(&uri), line, character).unwrap_or(Value::Null))
+            }
+            "textDocument/completion" => {
+                Some(Value::Array(completion(&document(&uri), line, character)))
+            }
+            "textDocument/definition" => {
+                Some(definition(&uri, &document(&uri), line, character).unwrap_or(Value::Null))
+            }
+            "textDocument/documentSymbol" => Some(Value::Array(document_symbols(&document(&uri)))),
+            _ => None,
+        };
+        if let Some(id) = message.get("id") {
+            let response = match result {
+                Some(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+                None => json!({
+                    "jsonrpc": "2.0",
+                    "id": id,
+                    "error": { "code": -32601, "message": format!("Method not found: {method}") },
+                }),
+            };
+            write_message(&mut writer, &response)?;
+        }
+    }
+    Ok(())
+}
+
+/// Read the next message, a body that is not valid JSON is returned as the inner error
+fn read_message<R: BufRead>(reader: &mut R) -> Result<Option<serde_json::Result<Value>>> {
+    let mut content_length = None;
+    loop {
+        let mut header = String::new();
+        if reader.read_line(&mut header)? == 0 {
+            return Ok(None);
+        }
+        let header = header.trim_end();
+        if header.is_empty() {
+            if content_length.is_some() {
+                break;
+            }
+            continue;
+        }
+        if let Some((name, value)) = header.split_once(':') {
+            if name.eq_ignore_ascii_case("content-length") {
+                let value = value.trim();
+                content_length = Some(
+                    value
+                        .parse::<usize>()
+                        .with_context(|| format!("Invalid Content-Length '{value}'"))?,
+                );
+            }
+        }
+    }
+    let mut body = vec![0; content_length.unwrap_or_default()];
+    reader.read_exact(&mut body)?;
+    Ok(Some(serde_json::from_slice(&body)))
+}
+
+fn write_message<W: Write>(writer: &mut W, message: &Value) -> Result<()> {
+    let body = message.to_string();
+    write!(writer, "Content-Length: {}\r\n\r\n{}", body.len(), body)?;
+    writer.flush()?;
+    Ok(())
+}
+
+/// Lint a document with its includes resolved, keeping only the problems located in the document itself
+fn lint_document(uri: &str, text: &str) -> Vec<Diagnostic> {
+    let diagnostics = uri
+        .strip_prefix("file://")
+        .and_then(|path| lint_with_includes(text, &decode_uri_path(path)).ok());
+    match diagnostics {
+        Some(diagnostics) => diagnostics
+            .into_iter()
+            .filter(|v| v.file.is_none())
+            .collect(),
+        None => lint(text),
+    }
+}
+
+fn decode_uri_path(path: &str) -> String {
+    let bytes = path.as_bytes();
+    let mut output = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let hex = bytes
+            .get(i + 1..i + 3)
+            .and_then(|v| std::str::from_utf8(v).ok());
+        match hex.and_then(|v| u8::from_str_radix(v, 16).ok()) {
+            Some(byte) if bytes[i] == b'%' => {
+                output.push(byte);
+                i += 3;
+            }
+            _ => {
+                output.push(bytes[i]);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&output).into_owned()
+}
+
+fn publish_diagnostics<W: Write>(writer: &mut W, uri: &str, text: &str) -> Result<()> {
+    let lines: Vec<&str> = text.lines().collect();
+    let diagnostics: Vec<Value> = lint_document(uri, text)
+        .into_iter()
+        .map(|v| {
+            let line = lines.get(v.line - 1).copied().unwrap_or_default();
+            let start = line
+                .char_indices()
+                .nth(v.column - 1)
+                .map(|(i, _)| i)
+                .unwrap_or(line.len());
+            let severity = match v.severity {
+                Severity::Error => 1,
+                Severity::Warning => 2,
+            };
+            json!({
+                "range": range(line, v.line - 1, start, line.len()),
+                "severity": severity,
+                "code": v.code,
+                "source": "argc",
+                "message": v.message,
+            })
+        })
+        .collect();
+    let notification = json!({
+        "jsonrpc": "2.0",
+        "method": "textDocument/publishDiagnostics",
+        "params": { "uri": uri, "diagnostics": diagnostics },
+    });
+    write_message(writer, &notification)
+}
+
+fn hover(text: &str, line_no: usize, character: usize) -> Option<Value> {
+    let line = text.lines().nth(line_no)?;
+    let cursor = byte_offset(line, character);
+    let (start, end) = tag_span(line)?;
+    let tag = &line[start..end];
+    let (doc, span) = if cursor + 1 >= start && cursor <= end {
+        let doc = TAG_DOCS.iter().find(|(name, _, _)| *name == tag)?;
+        (doc, (start - 1, end))
+    } else if tag == "meta" {
+        let (key_start, key_end) = meta_key_span(line, end)?;
+        if cursor < key_start || cursor > key_end {
+            return None;
+        }
+        let key = &line[key_start..key_end];
+        let doc = META_DOCS.iter().find(|(name, _, _)| *name == key)?;
+        (doc, (key_start, key_end))
+    } else {
+        return None;
+    };
+    let (_, syntax, describe) = doc;
+    Some(json!({
+        "contents": { "kind": "markdown", "value": format!("```\n{syntax}\n```\n\n{describe}") },
+        "range": range(line, line_no, span.0, span.1),
+    }))
+}
+
+fn completion(text: &str, line_no: usize, character: usize) -> Vec<Value> {
+    let line = text.lines().nth(line_no).unwrap_or_default();
+    let cursor = byte_offset(line, character);
+    let before = &line[..cursor];
+    let Some((start, end)) = tag_span(line) else {
+        return vec![];
+    };
+    if before.matches('`').count() % 2 == 1 {
+        let from = before.rfind('`').unwrap_or_default() + 1;
+        return functions(text)
+            .keys()
+            .map(|name| {
+                json!({
+                    "label": name,
+                    "kind": COMPLETION_KIND_FUNCTION,
+                    "textEdit": { "range": range(line, line_no, from, cursor), "newText": name },
+                })
+            })
+            .collect();
+    }
+    if cursor + 1 >= start && cursor <= end {
+        return TAG_DOCS
+            .iter()
+            .map(|(name, syntax, describe)| {
+                let label = format!("@{name}");
+                json!({
+                    "label": label,
+                    "kind": COMPLETION_KIND_KEYWORD,
+                    "detail": syntax,
+                    "documentation": { "kind": "markdown", "value": describe },
+                    "textEdit": { "range": range(line, line_no, start - 1, cursor), "newText": label },
+                })
+            })
+            .collect();
+    }
+    if &line[start..end] == "meta" && cursor > end {
+        let key = before[end..].trim_start();
+        if before[end..].len() > key.len() && !key.contains(char::is_whitespace) {
+            let from = cursor - key.len();
+            return META_DOCS
+                .iter()
+                .map(|(name, syntax, describe)| {
+                    json!({
+                        "label": name,
+                        "kind": COMPLETION_KIND_PROPERTY,
+                        "detail": syntax,
+                        "documentation": { "kind": "markdown", "value": describe },
+                        "textEdit": { "range": range(line, line_no, from, cursor), "newText": name },
+                    })
+                })
+                .collect();
+        }
+    }
+    vec![]
+}
+
+fn definition(uri: &str, text: &str, line_no: usize, character: usize) -> Option<Value> {
+    let line = text.lines().nth(line_no)?;
+    tag_span(line)?;
+    let cursor = byte_offset(line, character);
+    let is_word_char = |c: char| {
+        !c.is_whitespace()
+            && !matches!(
+                c,
+                '"' | '\''
+                    | '`'
+                    | '('
+                    | ')'
+                    | '['
+                    | ']'
+                    | '{'
+                    | '}'
+                    | '<'
+                    | '>'
+                    | '$'
+                    | '&'
+                    | '\\'
+                    | ';'
+                    | '|'
+                    | ','
+                    | '='
+            )
+    };
+    let start = line[..cursor]
+        .rfind(|c| !is_word_char(c))
+        .map(|i| i + 1)
+        .unwrap_or_default();
+    let end = line[cursor..]
+        .find(|c| !is_word_char(c))
+        .map(|i| cursor + i)
+        .unwrap_or(line.len());
+    let name = line.get(start..end).filter(|v| !v.is_empty())?;
+    let position = *functions(text).get(name)?;
+    let fn_line = text.lines().nth(position - 1)?;
+    let fn_start = fn_line.find(name).unwrap_or_default();
+    Some(json!({
+        "uri": uri,
+        "range": range(fn_line, position - 1, fn_start, fn_start + name.len()),
+    }))
+}
+
+fn document_symbols(text: &str) -> Vec<Value> {
+    let (mut events, _) = parse_lenient(text);
+    // Unknown tags are already reported as diagnostics, keep the outline while they are being typed
+    events.retain(|v| !matches!(v.data, EventData::Unknown(_)));
+    let Ok(root) = Command::new_from_events(&events) else {
+        return vec![];
+    };
+    let lines: Vec<&str> = text.lines().collect();
+    let fns = root.share.borrow().fns.clone();
+    root.subcommands
+        .iter()
+        .filter_map(|v| command_symbol(v, &fns, &lines).map(|(symbol, _)| symbol))
+        .collect()
+}
+
+/// Build the symbol of a subcommand, its range covers the function and all nested subcommands
+fn command_symbol(
+    cmd: &Command,
+    fns: &HashMap<String, Position>,
+    lines: &[&str],
+) -> Option<(Value, Position)> {
+    let match_fn = cmd.match_fn.as_ref()?;
+    let position = *fns.get(match_fn)?;
+    let line = lines.get(position - 1)?;
+    let start = line.find(match_fn.as_str()).unwrap_or_default();
+    let mut children = vec![];
+    let mut last = (position, *line);
+    for subcmd in &cmd.subcommands {
+        if let Some((symbol, child_last)) = command_symbol(subcmd, fns, lines) {
+            children.push(symbol);
+            if child_last > last.0 {
+                last = (child_last, lines[child_last - 1]);
+            }
+        }
+    }
+    let full_range = json!({
+        "start": { "line": position - 1, "character": 0 },
+        "end": { "line": last.0 - 1, "character": utf16_len(last.1, last.1.len()) },
+    });
+    let symbol = json!({
+        "name": cmd.name.clone().unwrap_or_default(),
+        "detail": cmd.describe_oneline(),
+        "kind": SYMBOL_KIND_FUNCTION,
+        "range": full_range,
+        "selectionRange": range(line, position - 1, start, start + match_fn.len()),
+        "children": children,
+    });
+    Some((symbol, last.0))
+}
+
+/// Shell functions defined in the script, with the line where they are defined
+fn functions(text: &str) -> IndexMap<String, Position> {
+    let (events, _) = parse_lenient(text);
+    events
+        .into_iter()
+        .filter_map(|event| match event.data {
+            EventData::Func(name) => Some((name, event.position)),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Locate the tag of a comment line like `# @option`, return the byte range of its name
+fn tag_span(line: &str) -> Option<(usize, usize)> {
+    let rest = line.trim_start_matches('#');
+    if rest.len() == line.len() {
+        return None;
+    }
+    let rest = rest.trim_start_matches([' ', '\t']).strip_prefix('@')?;
+    let start = line.len() - rest.len();
+    let end = rest
+        .find(|c: char| !(c.is_ascii_alphanumeric() || c == '-' || c == '_'))
+        .map(|i| start + i)
+        .unwrap_or(line.len());
+    Some((start, end))
+}
+
+fn meta_key_span(line: &str, tag_end: usize) -> Option<(usize, usize)> {
+    let rest = &line[tag_end..];
+    let key = rest.trim_start();
+    if key.len() == rest.len() || key.is_empty() {
+        return None;
+    }
+    let start = line.len() - key.len();
+    let end = key
+        .find(char::is_whitespace)
+        .map(|i| start + i)
+        .unwrap_or(line.len());
+    Some((start, end))
+}
+
+fn range(line: &str, line_no: usize, start: usize, end: usize) -> Value {
+    json!({
+        "start": { "line": line_no, "character": utf16_len(line, start) },
+        "end": { "line": line_no, "character": utf16_len(line, end) },
+    })
+}
+
+fn utf16_len(line: &str, byte_offset: usize) -> usize {
+    line[..byte_offset].encode_utf16().count()
+}
+
+fn byte_offset(line: &str, utf16_offset: usize) -> usize {
+    let mut count = 0;
+    for (i, c) in line.char_indices() {
+        if count >= utf16_offset {
+            return i;
+        }
+        count += c.len_utf16();
+    }
+    line.len()
+}
@@ -1,6 +1,11 @@
-use crate::{command::Command, param::Param, utils::META_MAN_SECTION};
+use crate::{
+    command::Command,
+    param::{FlagOptionParam, Param},
+    utils::META_MAN_SECTION,
+};
 
 use anyhow::Result;
+use indexmap::IndexMap;
 use roff::{bold, italic, roman, Inline, Roff};
 
 pub fn mangen(source: &str, root_name: &str) -> Result<Vec<(String, String)>> {
@@ -15,7 +20,7 @@ fn manpage_impl(output: &mut Vec<(String, String)>, cmd: &Command, section: &str
     let filename = format!("{}.{}", cmd.full_name(), section);
     let page = render_manpage(cmd, section);
     output.push((filename, page));
-    for subcmd in &cmd.subcommands {
+    for subcmd in cmd.subcommands.iter().filter(|v| !v.hidden()) {
         manpage_impl(output, subcmd, section);
     }
 }
@@ -29,6 +34,7 @@ fn render_manpage(cmd: &Command, section: &str) -> String {
     render_options_section(&mut roff, cmd);
     render_subcommands_section(&mut roff, cmd, section);
     render_envs_section(&mut roff, cmd);
+    render_examples_section(&mut roff, cmd);
     render_version_section(&mut roff, cmd);
     roff.to_roff()
 }
@@ -61,7 +67,7 @@ fn render_synopsis_section(roff: &mut Roff, cmd: &Command) {
     if !cmd.subcommands.is_empty() {
         line.push(roman(" <COMMAND>"))
     } else {
-        for param in &cmd.positional_params {
+        for param in cmd.positional_params.iter().filter(|v| !v.hidden()) {
             line.push(roman(format!(" {}", param.render_notation())))
         }
     }
@@ -83,46 +89,15 @@ fn render_options_section(roff: &mut Roff, cmd: &Command) {
         return;
     }
     roff.control("SH", ["OPTIONS"]);
-    for param in cmd.all_flag_options() {
-        let mut header = vec![];
-        if let Some(short) = param.short() {
-            header.push(bold(short));
-            header.push(roman(", "));
-        }
-        header.push(bold(param.long_name()));
-        let notations = param.notations();
-        if notations.len() == 1 {
-            header.push(roman("="));
-            let notation = &notations[0];
-            let parts = match (param.required(), param.multiple_occurs()) {
-                (true, true) => vec![roman("<"), italic(notation), roman(">...")],
-                (false, true) => vec![roman("["), italic(notation), roman("]...")],
-                (true, false) => vec![roman("<"), italic(notation), roman(">")],
-                (false, false) => vec![italic(notation)],
-            };
-            header.extend(parts);
-        } else {
-            for notation in notations {
-                header.push(roman(" "));
-                header.push(italic(notation));
-            }
-        }
-        if let Some(value) = param.default_value() {
-            header.push(roman(format!(" [default: {value}]")));
-        }
-        let mut body = vec![];
-        let mut has_help_written = false;
-        if !param.describe().is_empty() {
-            has_help_written = true;
-            render_describe(&mut body, param.describe());
+    let mut groups: IndexMap<&str, Vec<&FlagOptionParam>> = IndexMap::new();
+    for param in cmd.all_flag_options().into_iter().filter(|v| !v.hidden()) {
+        match param.group() {
+            Some(group) => groups.entry(group).or_default().push(param),
+            None => render_flag_option(roff, param),
         }
-        roff.control("TP", []);
-        roff.text(header);
-        roff.text(body);
-        render_choices(roff, param, has_help_written);
     }
 
-    for param in &cmd.positional_params {
+    for param in cmd.positional_params.iter().filter(|v| !v.hidden()) {
         let notation = param.notation();
         let mut header = match (param.required(), param.multiple_values()) {
             (true, true) => vec![roman("<"), italic(notation), roman(">...")],
@@ -133,6 +108,9 @@ fn render_options_section(roff: &mut Roff, cmd: &Command) {
         if let Some(value) = param.default_value() {
             header.push(roman(format!(" [default: {value}]")));
         }
+        if param.deprecated().is_some() {
+            header.push(roman(" [deprecated]"));
+        }
         let mut body = vec![];
         let mut has_help_written = false;
         if !param.describe().is_empty() {
@@ -144,6 +122,55 @@ fn render_options_section(roff: &mut Roff, cmd: &Command) {
         roff.text(body);
         render_choices(roff, param, has_help_written);
     }
+
+    for (group, params) in groups {
+        roff.control("SS", [group]);
+        for param in params {
+            render_flag_option(roff, param);
+        }
+    }
+}
+
+fn render_flag_option(roff: &mut Roff, param: &FlagOptionParam) {
+    let mut header = vec![];
+    if let Some(short) = param.short() {
+        header.push(bold(short));
+        header.push(roman(", "));
+    }
+    header.push(bold(param.render_long_name()));
+    let notations = param.notations();
+    if notations.len() == 1 {
+        header.push(roman("="));
+        let notation = &notations[0];
+        let parts = match (param.required(), param.multiple_occurs()) {
+            (true, true) => vec![roman("<"), italic(notation), roman(">...")],
+            (false, true) => vec![roman("["), italic(notation), roman("]...")],
+            (true, false) => vec![roman("<"), italic(notation), roman(">")],
+            (false, false) => vec![italic(notation)],
+        };
+        header.extend(parts);
+    } else {
+        for notation in notations {
+            header.push(roman(" "));
+            header.push(italic(notation));
+        }
+    }
+    if let Some(value) = param.default_value() {
+        header.push(roman(format!(" [default: {value}]")));
+    }
+    if param.deprecated().is_some() {
+        header.push(roman(" [deprecated]"));
+    }
+    let mut body = vec![];
+    let mut has_help_written = false;
+    if !param.describe().is_empty() {
+        has_help_written = true;
+        render_describe(&mut body, param.describe());
+    }
+    roff.control("TP", []);
+    roff.text(header);
+    roff.text(body);
+    render_choices(roff, param, has_help_written);
 }
 
 fn render_subcommands_section(roff: &mut Roff, cmd: &Command, section: &str) {
@@ -151,14 +178,28 @@ fn render_subcommands_section(roff: &mut Roff, cmd: &Command, section: &str) {
         return;
     }
     roff.control("SH", ["SUBCOMMANDS"]);
-    for subcmd in &cmd.subcommands {
-        roff.control("TP", []);
-        let name = subcmd.full_name();
-        roff.text([roman(format!("{name}({section})"))]);
-        for line in subcmd.describe.lines() {
-            roff.text([roman(line)]);
+    let mut groups: IndexMap<&str, Vec<&Command>> = IndexMap::new();
+    for subcmd in cmd.subcommands.iter().filter(|v| !v.hidden()) {
+        match subcmd.group.as_deref() {
+            Some(group) => groups.entry(group).or_default().push(subcmd),
+            None => render_subcommand(roff, subcmd, section),
         }
     }
+    for (group, subcmds) in groups {
+        roff.control("SS", [group]);
+        for subcmd in subcmds {
+            render_subcommand(roff, subcmd, section);
+        }
+    }
+}
+
+fn render_subcommand(roff: &mut Roff, subcmd: &Command, section: &str) {
+    roff.control("TP", []);
+    let name = subcmd.full_name();
+    roff.text([roman(format!("{name}({section})"))]);
+    for line in subcmd.describe.lines() {
+        roff.text([roman(line)]);
+    }
 }
 
 fn render_envs_section(roff: &mut Roff, cmd: &Command) {
@@ -188,6 +229,20 @@ fn render_envs_section(roff: &mut Roff, cmd: &Command) {
     }
 }
 
+fn render_examples_section(roff: &mut Roff, cmd: &Command) {
+    if cmd.examples.is_empty() {
+        return;
+    }
+    roff.control("SH", ["EXAMPLES"]);
+    for (value, describe, _) in &cmd.examples {
+        roff.control("TP", []);
+        roff.text([bold(value)]);
+        if !describe.is_empty() {
+            roff.text([roman(describe)]);
+        }
+    }
+}
+
 fn render_version_section(roff: &mut Roff, cmd: &Command) {
     if let Some(version) = &cmd.version {
         roff.control("SH", ["VERSION"]);
@@ -207,6 +262,17 @@ fn render_choices<T: Param>(roff: &mut Roff, param: &T, has_help_written: bool)
         if has_help_written {
             roff.text([Inline::LineBreak]);
         }
+        let values: Vec<String> = values
+            .iter()
+            .map(|v| {
+                let aliases = param.data().choice_aliases_of(v);
+                if aliases.is_empty() {
+                    v.to_string()
+                } else {
+                    format!("{v} ({})", aliases.join(", "))
+                }
+            })
+            .collect();
         let text: Vec<Inline> = vec![
             Inline::LineBreak,
             roman("["),
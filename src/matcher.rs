@@ -5,11 +5,16 @@ use std::collections::{HashMap, HashSet};
 use crate::{
     argc_value::ArgcValue,
     command::{Command, ExternalSubcommandInfo, SymbolParam},
-    param::{ChoiceValue, FlagOptionParam, Param, ParamData, PositionalParam},
+    param::{ChoiceValue, FlagOptionParam, Param, ParamData, PositionalParam, ValueType},
     runtime::Runtime,
-    utils::{argc_var_name, is_true_value, META_COMBINE_SHORTS},
+    utils::{
+        argc_var_name, is_bool_value, is_true_value, render_deprecated_warning,
+        META_ABBREVIATE_LONG_OPTIONS, META_COMBINE_SHORTS,
+    },
 };
 
+#[cfg(feature = "eval")]
+use crate::color::HelpPainter;
 #[cfg(feature = "compgen")]
 use crate::{
     compgen::{CompColor, CompItem},
@@ -33,8 +38,11 @@ pub(crate) struct Matcher<'a: 'b, 'b, T> {
     script_path: Option<String>,
     envs: HashMap<String, String>,
     wrap_width: Option<usize>,
+    #[cfg(feature = "eval")]
+    help_painter: Option<HelpPainter>,
     split_last_arg_at: Option<usize>,
     comp_option: Option<&'a str>,
+    example: bool,
     pub(crate) detected_external_subcommand: Option<(&'b str, &'b [String])>,
 }
 
@@ -70,8 +78,12 @@ pub(crate) enum MatchError {
     InvalidValue(usize, String, String, Vec<String>),
     InvalidBindEnvironment(usize, String, String, String, Vec<String>),
     InvalidEnvironment(usize, String, String, Vec<String>),
+    InvalidTypedValue(usize, String, String, String),
     MismatchValues(usize, String),
     NoFlagValue(usize, String),
+    ConflictingArguments(usize, String, String),
+    MissingRequiredByArgument(usize, String, Vec<String>),
+    AmbiguousArgument(usize, String, Vec<String>),
 }
 
 impl<'a: 'b, 'b, T: Runtime> Matcher<'a, 'b, T> {
@@ -82,6 +94,7 @@ impl<'a: 'b, 'b, T: Runtime> Matcher<'a, 'b, T> {
         compgen: bool,
     ) -> Self {
         let combine_shorts = root_cmd.has_metadata(META_COMBINE_SHORTS);
+        let abbreviate = root_cmd.has_metadata(META_ABBREVIATE_LONG_OPTIONS);
         let mut cmds: Vec<&'a Command> = vec![root_cmd];
         let mut cmd_level = 0;
         let mut cmd_arg_indexes = vec![0];
@@ -140,8 +153,16 @@ impl<'a: 'b, 'b, T: Runtime> Matcher<'a, 'b, T> {
                         cmd,
                     );
                 } else if arg.len() > 1 && maybe_flag_option(arg, &signs) {
+                    let abbreviation = if abbreviate && !(compgen && is_last_arg) {
+                        find_abbreviation(cmd, arg.split_once('=').map(|(k, _)| k).unwrap_or(arg))
+                    } else {
+                        None
+                    };
                     if let Some((k, v)) = arg.split_once('=') {
-                        if let Some(param) = cmd.find_flag_option(k) {
+                        if let Some(param) = cmd
+                            .find_flag_option(k)
+                            .or_else(|| abbreviation.and_then(|v| v.left()))
+                        {
                             add_param_choice_fn(&mut choice_fns, param);
                             let split_at = if let Some(prefix) = param.match_prefix(arg) {
                                 prefix.len()
@@ -175,7 +196,10 @@ impl<'a: 'b, 'b, T: Runtime> Matcher<'a, 'b, T> {
                             }
                             flag_option_args[cmd_level].push((k, vec![v], None));
                         }
-                    } else if let Some(param) = cmd.find_flag_option(arg) {
+                    } else if let Some(param) = cmd
+                        .find_flag_option(arg)
+                        .or_else(|| abbreviation.and_then(|v| v.left()))
+                    {
                         add_param_choice_fn(&mut choice_fns, param);
                         match_flag_option(
                             &mut flag_option_args[cmd_level],
@@ -261,6 +285,8 @@ impl<'a: 'b, 'b, T: Runtime> Matcher<'a, 'b, T> {
                             arg_comp = ArgComp::Symbol(ch);
                             split_last_arg_at = Some(1);
                         }
+                    } else if let Some(name) = abbreviation.and_then(|v| v.right()) {
+                        flag_option_args[cmd_level].push((name, vec![], None));
                     } else {
                         if positional_args.is_empty()
                             && !cmd.help_flags.contains(&arg)
@@ -397,9 +423,12 @@ impl<'a: 'b, 'b, T: Runtime> Matcher<'a, 'b, T> {
             choice_fns,
             script_path: None,
             wrap_width: None,
+            #[cfg(feature = "eval")]
+            help_painter: None,
             split_last_arg_at,
             comp_option,
             envs,
+            example: false,
             detected_external_subcommand,
         }
     }
@@ -412,6 +441,36 @@ impl<'a: 'b, 'b, T: Runtime> Matcher<'a, 'b, T> {
         self.wrap_width = Some(wrap_width);
     }
 
+    /// Print the help with the painter through `ArgcValue::Help` instead of an error
+    #[cfg(feature = "eval")]
+    pub(crate) fn set_help_painter(&mut self, help_painter: HelpPainter) {
+        self.help_painter = Some(help_painter);
+    }
+
+    /// Check the args of an `@example`, skipping the checks that depend on the environment or the file system
+    #[cfg(feature = "eval")]
+    pub(crate) fn check_example(&mut self, cmd_paths: &[String]) -> Option<String> {
+        self.envs.clear();
+        self.example = true;
+        let bind_envs = self.build_bind_envs();
+        match self.validate(&bind_envs) {
+            None
+            | Some(MatchError::DisplayHelp)
+            | Some(MatchError::DisplaySubcommandHelp(_))
+            | Some(MatchError::DisplayVersion)
+            | Some(MatchError::MissingRequiredEnvironments(_)) => {}
+            Some(err) => {
+                let (message, _) = self.stringify_match_error(&err);
+                let message = message.lines().next().unwrap_or_default();
+                return Some(message.trim_start_matches("error: ").to_string());
+            }
+        }
+        if !self.last_cmd().paths.starts_with(cmd_paths) {
+            return Some(format!("it does not run `{}`", cmd_paths.join(" ")));
+        }
+        None
+    }
+
     #[cfg(feature = "eval")]
     pub(crate) fn to_arg_values(&self) -> Vec<ArgcValue> {
         if let Some((detected_name, detected_args)) = self.detected_external_subcommand {
@@ -431,10 +490,17 @@ impl<'a: 'b, 'b, T: Runtime> Matcher<'a, 'b, T> {
         }
         let bind_envs = self.build_bind_envs();
         if let Some(err) = self.validate(&bind_envs) {
+            if let Some(help) = self
+                .help_painter
+                .and_then(|painter| self.render_help(&err, painter))
+            {
+                return vec![ArgcValue::Help(help)];
+            }
             return vec![ArgcValue::Error(self.stringify_match_error(&err))];
         }
         let last_cmd = self.last_cmd();
-        let mut output = self.to_arg_values_base(&bind_envs);
+        let mut output = self.deprecated_warnings();
+        output.extend(self.to_arg_values_base(&bind_envs));
         if last_cmd.positional_params.is_empty() && !self.positional_args.is_empty() {
             output.push(ArgcValue::ExtraPositionalMultiple(
                 self.positional_args.iter().map(|v| v.to_string()).collect(),
@@ -446,11 +512,49 @@ impl<'a: 'b, 'b, T: Runtime> Matcher<'a, 'b, T> {
             ));
         }
         if let Some(command_fn) = &last_cmd.command_fn {
+            if !last_cmd.dep_fns.is_empty() {
+                output.push(ArgcValue::CommandDeps(last_cmd.dep_fns.clone()));
+            }
+            if !last_cmd.sources.is_empty() {
+                output.push(ArgcValue::CommandRecipe(
+                    last_cmd.cmd_name(),
+                    last_cmd.sources.clone(),
+                    last_cmd.generates.clone(),
+                ));
+            }
             output.push(ArgcValue::CommandFn(command_fn.clone()));
         }
         output
     }
 
+    /// Warn about the deprecated subcommands, flags, options and args that are used
+    fn deprecated_warnings(&self) -> Vec<ArgcValue> {
+        let mut output = vec![];
+        for (level, cmd) in self.cmds.iter().enumerate() {
+            if let (true, Some(message)) = (level > 0, cmd.deprecated()) {
+                output.push(deprecated_warning("command", &cmd.cmd_name(), message));
+            }
+            let mut ids = HashSet::new();
+            for (_, _, param_name) in &self.flag_option_args[level] {
+                let Some(param) = param_name.and_then(|v| cmd.find_flag_option_by_id(v)) else {
+                    continue;
+                };
+                if let (true, Some(message)) = (ids.insert(param.id()), param.deprecated()) {
+                    let kind = if param.is_flag() { "flag" } else { "option" };
+                    output.push(deprecated_warning(kind, &param.long_name(), message));
+                }
+            }
+        }
+        let positional_values = self.match_positionals();
+        for (i, param) in self.last_cmd().positional_params.iter().enumerate() {
+            let used = positional_values.get(i).is_some_and(|v| !v.is_empty());
+            if let (true, Some(message)) = (used, param.deprecated()) {
+                output.push(deprecated_warning("argument", param.notation(), message));
+            }
+        }
+        output
+    }
+
     #[cfg(feature = "eval")]
     pub(crate) fn to_arg_values_for_param_fn(&self) -> Vec<ArgcValue> {
         let bind_envs = self.build_bind_envs();
@@ -617,10 +721,12 @@ impl<'a: 'b, 'b, T: Runtime> Matcher<'a, 'b, T> {
         }
 
         for param in &last_cmd.env_params {
-            if !self.envs.contains_key(param.id()) {
-                if let Some(value) = param.get_env_value() {
-                    output.push(value);
-                }
+            let value = match self.envs.get(param.id()) {
+                Some(value) => param.normalize_env_value(value),
+                None => param.get_env_value(),
+            };
+            if let Some(value) = value {
+                output.push(value);
             }
         }
 
@@ -654,7 +760,7 @@ impl<'a: 'b, 'b, T: Runtime> Matcher<'a, 'b, T> {
                     if !skip_bind_env {
                         if let Some(env_values) = bind_envs.flag_options[level].get(param.id()) {
                             if param.is_flag() {
-                                if is_true_value(env_values[0]) {
+                                if param.negatable() || is_true_value(env_values[0]) {
                                     args = vec![("", env_values.as_slice())];
                                 }
                             } else {
@@ -688,6 +794,140 @@ impl<'a: 'b, 'b, T: Runtime> Matcher<'a, 'b, T> {
         output
     }
 
+    #[cfg(all(feature = "eval", feature = "export"))]
+    pub(crate) fn to_json_value(&self) -> serde_json::Value {
+        use serde_json::{json, Map, Value};
+
+        let last_cmd = self.last_cmd();
+        let find_flag_option = |id: &str| -> Option<&FlagOptionParam> {
+            self.cmds
+                .iter()
+                .rev()
+                .find_map(|cmd| cmd.find_flag_option_by_id(id))
+        };
+        let find_positional = |id: &str| -> Option<&PositionalParam> {
+            last_cmd.positional_params.iter().find(|v| v.id() == id)
+        };
+        let to_json_list = |value_type: Option<&ValueType>, values: &[String]| -> Value {
+            Value::Array(values.iter().map(|v| to_json(value_type, v)).collect())
+        };
+
+        let mut output = Map::new();
+        output.insert("command".into(), json!(last_cmd.cmd_paths()));
+        let mut command_fn = Value::Null;
+        let mut deps = vec![];
+        let mut flags = Map::new();
+        let mut options = Map::new();
+        let mut positionals = Map::new();
+        let mut extra_positionals = vec![];
+        let mut envs = Map::new();
+        let mut fns = Map::new();
+        let mut args = vec![];
+        let mut warnings = vec![];
+        for value in self.to_arg_values() {
+            match value {
+                ArgcValue::Single(id, value) => match find_flag_option(&id) {
+                    Some(param) if param.is_flag() => {
+                        let value = if param.counted() {
+                            json!(value.parse::<usize>().unwrap_or_default())
+                        } else {
+                            json!(is_true_value(&value))
+                        };
+                        flags.insert(id, value);
+                    }
+                    param => {
+                        let value = to_json(param.and_then(|v| v.value_type()), &value);
+                        options.insert(id, value);
+                    }
+                },
+                ArgcValue::Multiple(id, values) => {
+                    if id == "_args" {
+                        args = values;
+                    } else {
+                        let value_type = find_flag_option(&id).and_then(|v| v.value_type());
+                        options.insert(id, to_json_list(value_type, &values));
+                    }
+                }
+                ArgcValue::Map(id, map) => {
+                    options.insert(id, json!(map));
+                }
+                ArgcValue::PositionalSingle(id, value) => {
+                    let value = to_json(find_positional(&id).and_then(|v| v.value_type()), &value);
+                    positionals.insert(id, value);
+                }
+                ArgcValue::PositionalMultiple(id, values) => {
+                    let value_type = find_positional(&id).and_then(|v| v.value_type());
+                    positionals.insert(id, to_json_list(value_type, &values));
+                }
+                ArgcValue::ExtraPositionalMultiple(values) => {
+                    extra_positionals = values;
+                }
+                ArgcValue::Env(name, value) => {
+                    envs.insert(name, json!(value));
+                }
+                ArgcValue::SingleFn(id, fn_name)
+                | ArgcValue::PositionalSingleFn(id, fn_name)
+                | ArgcValue::EnvFn(id, fn_name) => {
+                    fns.insert(id, json!(fn_name));
+                }
+                ArgcValue::CommandDeps(names) => {
+                    deps = names;
+                }
+                ArgcValue::CommandFn(name) | ArgcValue::ParamFn(name) => {
+                    command_fn = json!(name);
+                }
+                ArgcValue::ExternalSubcommand(script_path, args, subcommand_args_index) => {
+                    let external = json!({
+                        "path": script_path,
+                        "args": &args[subcommand_args_index + 1..],
+                    });
+                    output.insert("external".into(), external);
+                    return Value::Object(output);
+                }
+                ArgcValue::Warning(message) => {
+                    warnings.push(message);
+                }
+                ArgcValue::Error((message, exit)) => {
+                    output.insert("error".into(), json!({ "message": message, "exit": exit }));
+                    return Value::Object(output);
+                }
+                ArgcValue::Help(message) => {
+                    output.insert("error".into(), json!({ "message": message, "exit": 0 }));
+                    return Value::Object(output);
+                }
+                ArgcValue::Hook(_)
+                | ArgcValue::Dotenv(_)
+                | ArgcValue::RequireTools(_)
+                | ArgcValue::CommandRecipe(..) => {}
+            }
+        }
+        for param in &last_cmd.env_params {
+            if let Some(value) = self.envs.get(param.id()) {
+                envs.entry(param.id()).or_insert_with(|| json!(value));
+            }
+        }
+
+        output.insert("fn".into(), command_fn);
+        if !deps.is_empty() {
+            output.insert("deps".into(), json!(deps));
+        }
+        output.insert("flags".into(), Value::Object(flags));
+        output.insert("options".into(), Value::Object(options));
+        output.insert("positionals".into(), Value::Object(positionals));
+        if !extra_positionals.is_empty() {
+            output.insert("extra_positionals".into(), json!(extra_positionals));
+        }
+        output.insert("envs".into(), Value::Object(envs));
+        if !fns.is_empty() {
+            output.insert("fns".into(), Value::Object(fns));
+        }
+        output.insert("args".into(), json!(args));
+        if !warnings.is_empty() {
+            output.insert("warnings".into(), json!(warnings));
+        }
+        Value::Object(output)
+    }
+
     #[cfg(feature = "eval")]
     fn build_bind_envs<'x: 'a>(&'x self) -> BindEnvs<'a, 'x> {
         let cmds_len = self.cmds.len();
@@ -751,9 +991,23 @@ impl<'a: 'b, 'b, T: Runtime> Matcher<'a, 'b, T> {
                         check_flag_option_bind_envs.swap_remove(name);
                         flag_option_map.entry(name).or_insert(vec![]).push(i);
                     }
-                    None => return Some(MatchError::UnknownArgument(level, key.to_string())),
+                    None => {
+                        if self.cmds[0].has_metadata(META_ABBREVIATE_LONG_OPTIONS) {
+                            let names = list_abbreviation_names(cmd, key);
+                            if names.len() > 1 {
+                                return Some(MatchError::AmbiguousArgument(
+                                    level,
+                                    key.to_string(),
+                                    names,
+                                ));
+                            }
+                        }
+                        return Some(MatchError::UnknownArgument(level, key.to_string()));
+                    }
                 }
             }
+            let mut present_flag_options: IndexSet<&str> =
+                flag_option_map.keys().copied().collect();
             for (name, indexes) in flag_option_map {
                 if let Some(param) = cmd.flag_option_params.iter().find(|v| v.id() == name) {
                     let values_list: Vec<&[&str]> = indexes
@@ -773,7 +1027,7 @@ impl<'a: 'b, 'b, T: Runtime> Matcher<'a, 'b, T> {
                         if let Some(choices) = get_param_choice(param.choice(), &choices_fn_values)
                         {
                             for value in values.iter() {
-                                if !choices.contains(&value.to_string()) {
+                                if param.data().resolve_choice(choices, value).is_none() {
                                     return Some(MatchError::InvalidValue(
                                         level,
                                         value.to_string(),
@@ -783,8 +1037,15 @@ impl<'a: 'b, 'b, T: Runtime> Matcher<'a, 'b, T> {
                                 }
                             }
                         }
+                        if let Some(err) =
+                            self.validate_value_type(level, param.value_type(), values, || {
+                                format!("`{}`", param.render_first_notation())
+                            })
+                        {
+                            return Some(err);
+                        }
                     }
-                    if !param.multiple_occurs() && values_list.len() > 1 {
+                    if !param.multiple_occurs() && !param.negatable() && values_list.len() > 1 {
                         return Some(MatchError::NotMultipleArgument(level, param.long_name()));
                     }
                 }
@@ -797,6 +1058,7 @@ impl<'a: 'b, 'b, T: Runtime> Matcher<'a, 'b, T> {
             {
                 if self.parent_has_explicit_flag_option(level, param.id()) {
                     check_flag_option_bind_envs.swap_remove(param.id());
+                    present_flag_options.insert(param.id());
                 }
             }
 
@@ -825,7 +1087,7 @@ impl<'a: 'b, 'b, T: Runtime> Matcher<'a, 'b, T> {
                     if let Some(choices) = get_param_choice(param.choice(), &choices_fn_values) {
                         choice_values = choices.to_vec();
                         for value in values.iter() {
-                            if !choices.contains(&value.to_string()) {
+                            if param.data().resolve_choice(choices, value).is_none() {
                                 return Some(MatchError::InvalidBindEnvironment(
                                     level,
                                     value.to_string(),
@@ -836,6 +1098,59 @@ impl<'a: 'b, 'b, T: Runtime> Matcher<'a, 'b, T> {
                             }
                         }
                     }
+                    if let Some(err) =
+                        self.validate_value_type(level, param.value_type(), values, || {
+                            self.cmds[0].message(
+                                "bind-env-target",
+                                &[
+                                    ("env", &param.bind_env().unwrap_or_default()),
+                                    ("name", &param.render_name_notations()),
+                                ],
+                            )
+                        })
+                    {
+                        return Some(err);
+                    }
+                    if param.is_option() || param.negatable() || is_true_value(values[0]) {
+                        present_flag_options.insert(name);
+                    }
+                }
+            }
+
+            for (ids, _) in &cmd.conflicts {
+                let used: Vec<&FlagOptionParam> = ids
+                    .iter()
+                    .filter(|v| present_flag_options.contains(v.as_str()))
+                    .filter_map(|v| cmd.find_flag_option_by_id(v))
+                    .collect();
+                if used.len() > 1 {
+                    return Some(MatchError::ConflictingArguments(
+                        level,
+                        used[0].long_name(),
+                        used[1].long_name(),
+                    ));
+                }
+            }
+
+            for (id, required_ids, _) in &cmd.requires {
+                if !present_flag_options.contains(id.as_str()) {
+                    continue;
+                }
+                let missing: Vec<String> = required_ids
+                    .iter()
+                    .filter(|v| !present_flag_options.contains(v.as_str()))
+                    .filter_map(|v| cmd.find_flag_option_by_id(v))
+                    .map(|v| v.render_name_notations())
+                    .collect();
+                if missing.is_empty() {
+                    continue;
+                }
+                if let Some(param) = cmd.find_flag_option_by_id(id) {
+                    return Some(MatchError::MissingRequiredByArgument(
+                        level,
+                        param.long_name(),
+                        missing,
+                    ));
                 }
             }
 
@@ -905,7 +1220,7 @@ impl<'a: 'b, 'b, T: Runtime> Matcher<'a, 'b, T> {
                 get_param_choice(param.choice(), &choices_fn_values),
             ) {
                 for value in values.iter() {
-                    if !choices.contains(&value.to_string()) {
+                    if param.data().resolve_choice(choices, value).is_none() {
                         return Some(MatchError::InvalidValue(
                             level,
                             value.to_string(),
@@ -915,6 +1230,15 @@ impl<'a: 'b, 'b, T: Runtime> Matcher<'a, 'b, T> {
                     }
                 }
             }
+            if let Some(values) = positional_values.get(i) {
+                if let Some(err) =
+                    self.validate_value_type(level, param.value_type(), values, || {
+                        format!("`{}`", param.render_notation())
+                    })
+                {
+                    return Some(err);
+                }
+            }
         }
         if positional_params_len > positional_values_len {
             let mut missing_positionals = vec![];
@@ -922,7 +1246,7 @@ impl<'a: 'b, 'b, T: Runtime> Matcher<'a, 'b, T> {
                 if let Some(values) = bind_envs.positionals.get(param.id()) {
                     if let Some(choices) = get_param_choice(param.choice(), &choices_fn_values) {
                         for value in values.iter() {
-                            if !choices.contains(&value.to_string()) {
+                            if param.data().resolve_choice(choices, value).is_none() {
                                 return Some(MatchError::InvalidBindEnvironment(
                                     level,
                                     value.to_string(),
@@ -933,6 +1257,19 @@ impl<'a: 'b, 'b, T: Runtime> Matcher<'a, 'b, T> {
                             }
                         }
                     }
+                    if let Some(err) =
+                        self.validate_value_type(level, param.value_type(), values, || {
+                            self.cmds[0].message(
+                                "bind-env-target",
+                                &[
+                                    ("env", &param.bind_env().unwrap_or_default()),
+                                    ("name", &param.render_notation()),
+                                ],
+                            )
+                        })
+                    {
+                        return Some(err);
+                    }
                 } else if param.required() {
                     missing_positionals.push(param.render_notation())
                 }
@@ -960,7 +1297,7 @@ impl<'a: 'b, 'b, T: Runtime> Matcher<'a, 'b, T> {
                 get_param_choice(param.choice(), &choices_fn_values),
                 self.envs.get(param.id()),
             ) {
-                if !choices.contains(&value.to_string()) {
+                if param.data().resolve_choice(choices, value).is_none() {
                     return Some(MatchError::InvalidEnvironment(
                         level,
                         value.to_string(),
@@ -969,12 +1306,48 @@ impl<'a: 'b, 'b, T: Runtime> Matcher<'a, 'b, T> {
                     ));
                 }
             }
+            if let Some(value) = self.envs.get(param.id()) {
+                if let Some(err) =
+                    self.validate_value_type(level, param.value_type(), &[value.as_str()], || {
+                        self.cmds[0].message("env-target", &[("env", param.id())])
+                    })
+                {
+                    return Some(err);
+                }
+            }
         }
 
         None
     }
 
     #[cfg(feature = "eval")]
+    fn validate_value_type(
+        &self,
+        level: usize,
+        value_type: Option<&ValueType>,
+        values: &[&str],
+        target: impl FnOnce() -> String,
+    ) -> Option<MatchError> {
+        let value_type = value_type?;
+        if self.example
+            && matches!(
+                value_type,
+                ValueType::Path | ValueType::File | ValueType::Dir
+            )
+        {
+            return None;
+        }
+        let value = values
+            .iter()
+            .find(|value| !value_type.validate(self.runtime, value))?;
+        Some(MatchError::InvalidTypedValue(
+            level,
+            value.to_string(),
+            target(),
+            value_type.expected(),
+        ))
+    }
+
     fn execute_choices_fns<'x>(
         &'x self,
         bind_envs: &BindEnvs<'a, 'x>,
@@ -1046,19 +1419,30 @@ impl<'a: 'b, 'b, T: Runtime> Matcher<'a, 'b, T> {
         output
     }
 
+    #[cfg(feature = "eval")]
+    fn render_help(&self, err: &MatchError, painter: HelpPainter) -> Option<String> {
+        let cmd = self.last_cmd();
+        let cmd = match err {
+            MatchError::DisplayHelp => cmd,
+            MatchError::DisplaySubcommandHelp(name) => cmd.find_subcommand(name)?,
+            _ => return None,
+        };
+        Some(cmd.render_help(self.wrap_width, painter))
+    }
+
     #[cfg(feature = "eval")]
     fn stringify_match_error(&self, err: &MatchError) -> (String, i32) {
+        let root = &self.cmds[0];
+        let render_list = |heading: String, values: &[String]| -> String {
+            let mut output = vec![heading];
+            output.extend(values.iter().map(|v| format!("  {v}")));
+            output.join("\n")
+        };
         let mut exit = 0;
         let message = match err {
-            MatchError::DisplayHelp => {
-                let cmd = self.last_cmd();
-                cmd.render_help(self.wrap_width)
-            }
-            MatchError::DisplaySubcommandHelp(name) => {
-                let cmd = self.last_cmd();
-                let cmd = cmd.find_subcommand(name).unwrap();
-                cmd.render_help(self.wrap_width)
-            }
+            MatchError::DisplayHelp | MatchError::DisplaySubcommandHelp(_) => self
+                .render_help(err, HelpPainter::default())
+                .unwrap_or_default(),
             MatchError::DisplayVersion => {
                 let cmd = self.last_cmd();
                 cmd.render_version()
@@ -1068,90 +1452,106 @@ impl<'a: 'b, 'b, T: Runtime> Matcher<'a, 'b, T> {
                 let cmd = self.last_cmd();
                 let cmd_str = cmd.cmd_paths().join("-");
                 let mut names: Vec<String> = cmd.list_subcommand_names();
-                for info in self.cmds[0].external_subcommands.iter() {
+                for info in root.external_subcommands.iter() {
                     if !names.contains(&info.name) {
                         names.push(info.name.clone());
                     }
                 }
-                let names = names.join(", ");
-                let details = match arg {
-                    Some(arg) => format!("but '{arg}' is not one of them"),
-                    None => "but one was not provided".to_string(),
+                let (error, tip) = match arg {
+                    Some(arg) => (
+                        root.message("invalid-subcommand", &[("cmd", &cmd_str), ("arg", arg)]),
+                        render_tip(root, "subcommand", arg, &names),
+                    ),
+                    None => (
+                        root.message("missing-subcommand", &[("cmd", &cmd_str)]),
+                        String::new(),
+                    ),
                 };
-                format!(
-                    r###"error: `{cmd_str}` requires a subcommand {details}
-  [subcommands: {names}]"###
-                )
+                let names = root.message("subcommands", &[("names", &names.join(", "))]);
+                format!("{error}\n  {names}{tip}")
             }
-            MatchError::UnknownArgument(_level, name) => {
+            MatchError::UnknownArgument(level, name) => {
                 exit = 1;
-                format!(r###"error: unexpected argument `{name}` found"###)
+                let tip = if name.starts_with(['-', '+']) {
+                    let names: Vec<String> = self.cmds[*level]
+                        .all_flag_options()
+                        .iter()
+                        .flat_map(|v| v.list_names())
+                        .collect();
+                    render_tip(root, "argument", name, &names)
+                } else {
+                    String::new()
+                };
+                let error = root.message("unexpected-argument", &[("name", name)]);
+                format!("{error}{tip}")
             }
             MatchError::MissingRequiredArguments(_level, values) => {
                 exit = 1;
-                let list = values
-                    .iter()
-                    .map(|v| format!("  {v}"))
-                    .collect::<Vec<String>>()
-                    .join("\n");
-                format!(
-                    r###"error: the following required arguments were not provided:
-{list}"###
-                )
+                render_list(root.message("missing-arguments", &[]), values)
             }
             MatchError::MissingRequiredEnvironments(values) => {
                 exit = 1;
-                let list = values
-                    .iter()
-                    .map(|v| format!("  {v}"))
-                    .collect::<Vec<String>>()
-                    .join("\n");
-                format!(
-                    r###"error: the following required environments were not provided:
-{list}"###
-                )
+                render_list(root.message("missing-environments", &[]), values)
             }
             MatchError::NotMultipleArgument(_level, name) => {
                 exit = 1;
-                format!(r###"error: the argument `{name}` cannot be used multiple times"###)
+                root.message("multiple-occurrences", &[("name", name)])
             }
             MatchError::InvalidValue(_level, value, name, choices) => {
                 exit = 1;
-                let list = choices.join(", ");
-                format!(
-                    r###"error: invalid value `{value}` for `{name}`
-  [possible values: {list}]"###
-                )
+                let target = format!("`{name}`");
+                let error = root.message("invalid-value", &[("value", value), ("target", &target)]);
+                let list = root.message("possible-values", &[("values", &choices.join(", "))]);
+                let tip = render_tip(root, "value", value, choices);
+                format!("{error}\n  {list}{tip}")
             }
             MatchError::InvalidBindEnvironment(_level, value, env_name, name, choices) => {
                 exit = 1;
                 if choices.is_empty() {
-                    format!(
-                        r###"error: environment variable `{env_name}` has invalid value for param '{name}'"###
-                    )
+                    root.message("invalid-env", &[("env", env_name), ("name", name)])
                 } else {
-                    let list = choices.join(", ");
-                    format!(
-                        r###"error: invalid value `{value}` for environment variable `{env_name}` that bound to `{name}`
-  [possible values: {list}]"###
-                    )
+                    let target =
+                        root.message("bind-env-target", &[("env", env_name), ("name", name)]);
+                    let error =
+                        root.message("invalid-value", &[("value", value), ("target", &target)]);
+                    let list = root.message("possible-values", &[("values", &choices.join(", "))]);
+                    format!("{error}\n  {list}")
                 }
             }
             MatchError::InvalidEnvironment(_level, value, name, choices) => {
                 exit = 1;
-                let list = choices.join(", ");
-                format!(
-                    r###"error: invalid value `{value}` for environment variable `{name}`
-  [possible values: {list}]"###
-                )
+                let target = root.message("env-target", &[("env", name)]);
+                let error = root.message("invalid-value", &[("value", value), ("target", &target)]);
+                let list = root.message("possible-values", &[("values", &choices.join(", "))]);
+                format!("{error}\n  {list}")
+            }
+            MatchError::InvalidTypedValue(_level, value, target, expected) => {
+                exit = 1;
+                let error = root.message("invalid-value", &[("value", value), ("target", target)]);
+                let expected = root.message("expected", &[("expected", expected)]);
+                format!("{error}\n  {expected}")
             }
             MatchError::MismatchValues(_level, value) => {
                 exit = 1;
-                format!(r###"error: incorrect number of values for `{value}`"###)
+                root.message("incorrect-values", &[("name", value)])
             }
             MatchError::NoFlagValue(_level, name) => {
                 exit = 1;
-                format!(r###"error: flag `{name}` don't accept any value"###)
+                root.message("no-flag-value", &[("name", name)])
+            }
+            MatchError::ConflictingArguments(_level, name, other) => {
+                exit = 1;
+                root.message("conflicting-arguments", &[("name", name), ("other", other)])
+            }
+            MatchError::MissingRequiredByArgument(_level, name, values) => {
+                exit = 1;
+                render_list(root.message("missing-requires", &[("name", name)]), values)
+            }
+            MatchError::AmbiguousArgument(_level, name, names) => {
+                exit = 1;
+                let error = root.message("ambiguous-argument", &[("name", name)]);
+                let list = root.message("possible-arguments", &[("names", &names.join(", "))]);
+                format!("{error}\n  {list}")
             }
         };
         (message, exit)
@@ -1172,7 +1572,7 @@ impl<'a: 'b, 'b, T: Runtime> Matcher<'a, 'b, T> {
         } else {
             last_cmd.all_flag_options()
         };
-        for param in params.iter() {
+        for param in params.iter().filter(|v| !v.hidden()) {
             let mut exist = args.contains(param.id());
             if !last.is_empty() && param.is_match(last) {
                 exist = false;
@@ -1379,6 +1779,51 @@ fn match_flag_option<'a, 'b>(
     }
 }
 
+/// Resolve an unambiguous prefix of a long flag/option name, or of `--help`/`--version`
+fn find_abbreviation<'a>(
+    cmd: &'a Command,
+    name: &str,
+) -> Option<Either<&'a FlagOptionParam, &'static str>> {
+    let mut found = None;
+    for (candidate, target) in abbreviation_candidates(cmd) {
+        if candidate.starts_with(name) {
+            if found.is_some() {
+                return None;
+            }
+            found = Some(target);
+        }
+    }
+    found
+}
+
+fn list_abbreviation_names(cmd: &Command, name: &str) -> Vec<String> {
+    abbreviation_candidates(cmd)
+        .into_iter()
+        .map(|(candidate, _)| candidate)
+        .filter(|candidate| candidate.starts_with(name))
+        .collect()
+}
+
+fn abbreviation_candidates(cmd: &Command) -> Vec<(String, Either<&FlagOptionParam, &'static str>)> {
+    let mut output = vec![];
+    for param in cmd.flag_option_params.iter() {
+        if param.prefixed() {
+            continue;
+        }
+        output.push((param.long_name(), Either::Left(param)));
+        if let Some(negated_name) = param.negated_name() {
+            output.push((negated_name, Either::Left(param)));
+        }
+    }
+    for name in cmd.help_flags.iter().chain(cmd.version_flags.iter()) {
+        if output.iter().all(|(v, _)| v != name) {
+            output.push((name.to_string(), Either::Right(*name)));
+        }
+    }
+    output.retain(|(v, _)| v.starts_with("--"));
+    output
+}
+
 fn match_command<'a>(
     cmds: &mut Vec<&'a Command>,
     cmd_level: &mut usize,
@@ -1431,6 +1876,10 @@ fn comp_subcommands_positional(
     output
 }
 
+fn deprecated_warning(kind: &str, name: &str, message: &str) -> ArgcValue {
+    ArgcValue::Warning(render_deprecated_warning(kind, name, message))
+}
+
 #[cfg(feature = "compgen")]
 fn comp_subcomands(
     cmd: &Command,
@@ -1441,7 +1890,7 @@ fn comp_subcomands(
     let mut has_help_subcmd = false;
     let mut describe_help_subcmd = false;
     let signs = cmd.flag_option_signs();
-    for subcmd in cmd.subcommands.iter() {
+    for subcmd in cmd.subcommands.iter().filter(|v| !v.hidden()) {
         let describe = subcmd.describe_oneline();
         for (i, v) in subcmd.list_names().into_iter().enumerate() {
             if i > 0 && v.len() < 2 {
@@ -1558,6 +2007,11 @@ fn comp_param(describe: &str, value_name: &str, data: &ParamData) -> Vec<CompIte
             )],
         }
     } else {
+        let value_name = match data.value_type {
+            Some(ValueType::Path | ValueType::File) => "path",
+            Some(ValueType::Dir) => "dir",
+            _ => value_name,
+        };
         let value = format!("__argc_value={value_name}");
         vec![(value, describe.into(), false, CompColor::of_value())]
     };
@@ -1575,6 +2029,17 @@ fn comp_param(describe: &str, value_name: &str, data: &ParamData) -> Vec<CompIte
     output
 }
 
+#[cfg(feature = "eval")]
+fn render_tip(cmd: &Command, kind: &str, value: &str, candidates: &[String]) -> String {
+    match crate::utils::suggest_similar(value, candidates.iter().map(|v| v.as_str())) {
+        Some(similar) => {
+            let tip = cmd.message(&format!("similar-{kind}"), &[("value", similar)]);
+            format!("\n\n  {tip}")
+        }
+        None => String::new(),
+    }
+}
+
 fn get_param_choice<'a, 'b: 'a>(
     choice: Option<&'a ChoiceValue>,
     choices_fn_values: &'a HashMap<&str, Vec<String>>,
@@ -1600,10 +2065,6 @@ fn delimit_arg_values<'x, T: Param>(param: &T, values: &[&'x str]) -> Vec<&'x st
     }
 }
 
-fn is_bool_value(value: &str) -> bool {
-    matches!(value, "true" | "false" | "0" | "1")
-}
-
 fn maybe_flag_option(arg: &str, signs: &IndexSet<char>) -> bool {
     let cond = if signs.contains(&'+') && arg.starts_with('+') {
         !arg.starts_with("++")
@@ -1624,3 +2085,23 @@ fn maybe_flag_option(arg: &str, signs: &IndexSet<char>) -> bool {
     }
     true
 }
+
+#[cfg(all(feature = "eval", feature = "export"))]
+fn to_json(value_type: Option<&ValueType>, value: &str) -> serde_json::Value {
+    use serde_json::Value;
+
+    match value_type {
+        Some(ValueType::Int) | Some(ValueType::Range { .. }) => value
+            .parse::<i64>()
+            .map(Value::from)
+            .unwrap_or_else(|_| value.into()),
+        Some(ValueType::Float) => value
+            .parse::<f64>()
+            .ok()
+            .and_then(serde_json::Number::from_f64)
+            .map(Value::Number)
+            .unwrap_or_else(|| value.into()),
+        Some(ValueType::Bool) if is_bool_value(value) => is_true_value(value).into(),
+        _ => value.into(),
+    }
+}
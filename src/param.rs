@@ -1,5 +1,10 @@
+use crate::locale::Messages;
 use crate::parser::{is_choice_value_terminate, is_default_value_terminate};
-use crate::utils::{argc_var_name, escape_shell_words, sanitize_var_name, to_cobol_case, MAX_ARGS};
+use crate::runtime::Runtime;
+use crate::utils::{
+    argc_var_name, escape_shell_words, is_bool_value, is_true_value, sanitize_var_name,
+    to_cobol_case, MAX_ARGS,
+};
 use crate::ArgcValue;
 
 use anyhow::{bail, Result};
@@ -14,8 +19,18 @@ pub(crate) trait Param {
     fn tag_name(&self) -> &str;
     fn guard(&self) -> Result<()>;
     fn multiple_values(&self) -> bool;
+    /// Render the source of the tag body as `[short, name, notations, describe]` columns
     #[allow(unused)]
-    fn render_source(&self) -> String;
+    fn render_source_columns(&self) -> [String; 4];
+
+    #[allow(unused)]
+    fn render_source(&self) -> String {
+        self.render_source_columns()
+            .into_iter()
+            .filter(|v| !v.is_empty())
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
 
     fn describe_oneline(&self) -> &str {
         match self.describe().split_once('\n') {
@@ -23,8 +38,9 @@ pub(crate) trait Param {
             None => self.describe(),
         }
     }
-    fn render_describe(&self) -> String {
-        self.data().render_describe(self.describe(), self.id())
+    fn render_describe(&self, messages: &Messages) -> String {
+        self.data()
+            .render_describe(self.describe(), self.id(), messages)
     }
     fn describe(&self) -> &str {
         &self.data().describe
@@ -44,6 +60,9 @@ pub(crate) trait Param {
     fn choice(&self) -> Option<&ChoiceValue> {
         self.data().choice.as_ref()
     }
+    fn value_type(&self) -> Option<&ValueType> {
+        self.data().value_type.as_ref()
+    }
     fn choice_fn(&self) -> Option<(&String, &bool)> {
         self.data().choice_fn()
     }
@@ -59,6 +78,12 @@ pub(crate) trait Param {
     fn default_value(&self) -> Option<&String> {
         self.data().default_value()
     }
+    fn hidden(&self) -> bool {
+        self.data().hidden
+    }
+    fn deprecated(&self) -> Option<&String> {
+        self.data().deprecated.as_ref()
+    }
     fn bind_env(&self) -> Option<String> {
         self.data().normalize_bind_env(self.id())
     }
@@ -74,7 +99,9 @@ pub(crate) struct FlagOptionParam {
     assigned: bool,
     raw_notations: Vec<String>,
     notations: Vec<String>,
+    negatable: bool,
     inherited: bool,
+    group: Option<String>,
 }
 
 impl Param for FlagOptionParam {
@@ -117,6 +144,9 @@ impl Param for FlagOptionParam {
         if self.prefixed && self.bind_env().is_some() {
             bail!("cannot bind env with prefixed options")
         }
+        if self.negatable && self.multiple_occurs() {
+            bail!("cannot combine negatable and multi-occurs")
+        }
         Ok(())
     }
 
@@ -124,11 +154,8 @@ impl Param for FlagOptionParam {
         self.data().multiple() || self.num_args().1 > 1
     }
 
-    fn render_source(&self) -> String {
-        let mut output = vec![];
-        if let Some(short) = &self.short {
-            output.push(short.to_string());
-        };
+    fn render_source_columns(&self) -> [String; 4] {
+        let short = self.short.clone().unwrap_or_default();
         let mut name_suffix = String::new();
         if self.prefixed || self.data.name.ends_with('-') {
             name_suffix.push('-');
@@ -136,28 +163,22 @@ impl Param for FlagOptionParam {
         if self.assigned || self.data.name.ends_with(':') {
             name_suffix.push(':');
         }
-        output.push(format!(
-            "{}{}",
+        let negatable = if self.negatable { "[no-]" } else { "" };
+        let mut name = format!(
+            "{}{negatable}{}",
             self.long_prefix,
             self.data.render_source_of_name_value(&name_suffix)
-        ));
-
-        if let Some(env) = &self.data.env {
-            match env {
-                Some(v) => output.push(format!("${v}")),
-                None => output.push("$$".into()),
-            }
-        }
-
-        for raw_notation in &self.raw_notations {
-            output.push(format!("<{raw_notation}>"));
-        }
+        );
+        name.push_str(&self.data.render_source_of_env());
 
-        if !self.data.describe.is_empty() {
-            output.push(self.data.describe.clone());
-        }
+        let notations = self
+            .raw_notations
+            .iter()
+            .map(|v| format!("<{v}>"))
+            .collect::<Vec<_>>()
+            .join(" ");
 
-        output.join(" ")
+        [short, name, notations, self.data.describe.clone()]
     }
 }
 
@@ -209,7 +230,9 @@ impl FlagOptionParam {
             id,
             raw_notations,
             notations,
+            negatable: false,
             inherited: false,
+            group: None,
         }
     }
 
@@ -225,6 +248,7 @@ impl FlagOptionParam {
             required: self.required(),
             multiple_values: self.multiple_values(),
             multiple_occurs: self.multiple_occurs(),
+            counted: self.counted(),
             num_args: self.num_args(),
             delimiter: self.delimiter(),
             terminated: self.terminated(),
@@ -232,8 +256,15 @@ impl FlagOptionParam {
             assigned: self.assigned,
             default: self.data().default.clone(),
             choice: self.data().choice.clone(),
+            choice_aliases: self.data().choice_aliases.clone(),
+            choice_ignore_case: self.data().choice_ignore_case,
+            value_type: self.data().value_type.clone(),
             env: self.bind_env(),
+            negated_name: self.negated_name(),
             inherited: self.inherited,
+            hidden: self.hidden(),
+            deprecated: self.deprecated().cloned(),
+            group: self.group.clone(),
         }
     }
 
@@ -269,6 +300,10 @@ impl FlagOptionParam {
         self.data().multiple()
     }
 
+    pub(crate) fn counted(&self) -> bool {
+        self.is_flag() && self.multiple_occurs()
+    }
+
     pub(crate) fn num_args(&self) -> (usize, usize) {
         let len = self.notations.len();
         if self.terminated()
@@ -300,6 +335,43 @@ impl FlagOptionParam {
         format!("{}{}", self.long_prefix, self.data.name)
     }
 
+    pub(crate) fn negatable(&self) -> bool {
+        self.negatable
+    }
+
+    pub(crate) fn set_negatable(&mut self) {
+        self.negatable = true;
+    }
+
+    pub(crate) fn negated_name(&self) -> Option<String> {
+        if self.negatable {
+            Some(format!("{}no-{}", self.long_prefix, self.data.name))
+        } else {
+            None
+        }
+    }
+
+    /// Also accepts an unambiguous abbreviation of the negated name, e.g. `--no-c` for `--no-color`
+    pub(crate) fn is_negated_name(&self, name: &str) -> bool {
+        match self.negated_name() {
+            Some(negated_name) => {
+                negated_name == name
+                    || (name.starts_with("--")
+                        && negated_name.starts_with(name)
+                        && !self.long_name().starts_with(name))
+            }
+            None => false,
+        }
+    }
+
+    pub(crate) fn render_long_name(&self) -> String {
+        if self.negatable {
+            format!("{}[no-]{}", self.long_prefix, self.data.name)
+        } else {
+            self.long_name()
+        }
+    }
+
     pub(crate) fn render_first_notation(&self) -> String {
         format!("<{}>", self.notations[0])
     }
@@ -346,6 +418,9 @@ impl FlagOptionParam {
                 output.push_str("    ")
             };
             output.push_str(&format!("{:>2}", self.long_prefix));
+            if self.negatable {
+                output.push_str("[no-]");
+            }
             output.push_str(&self.data.name);
         }
 
@@ -376,11 +451,14 @@ impl FlagOptionParam {
                         None => arg_suffix,
                     };
                     if let Some(values) = map.get_mut(key) {
-                        values.extend(value.iter().map(|v| v.to_string()));
+                        values.extend(value.iter().map(|v| self.data.normalize_choice(v)));
                     } else {
                         map.insert(
                             key.to_string(),
-                            value.iter().map(|v| v.to_string()).collect(),
+                            value
+                                .iter()
+                                .map(|v| self.data.normalize_choice(v))
+                                .collect(),
                         );
                     }
                 }
@@ -393,7 +471,17 @@ impl FlagOptionParam {
         } else {
             let values: Vec<&[&str]> = args.iter().map(|(_, value)| *value).collect();
             if self.is_flag() {
-                if values.is_empty() {
+                if self.negatable {
+                    let (arg, value) = args.last()?;
+                    let enabled = match *arg {
+                        "" => value.first().map(|v| is_true_value(v)).unwrap_or_default(),
+                        arg => !self.is_negated_name(arg),
+                    };
+                    Some(ArgcValue::Single(
+                        id,
+                        if enabled { "1" } else { "0" }.into(),
+                    ))
+                } else if values.is_empty() {
                     None
                 } else {
                     Some(ArgcValue::Single(id, values.len().to_string()))
@@ -413,16 +501,20 @@ impl FlagOptionParam {
                 if self.multiple_values() {
                     let values: Vec<String> = values
                         .iter()
-                        .flat_map(|v| v.iter().map(|v| v.to_string()))
+                        .flat_map(|v| v.iter().map(|v| self.data.normalize_choice(v)))
                         .collect();
                     Some(ArgcValue::Multiple(id, values))
                 } else if self.notations.len() > 1 {
                     Some(ArgcValue::Multiple(
                         id,
-                        values[0].iter().map(|v| v.to_string()).collect(),
+                        values[0]
+                            .iter()
+                            .map(|v| self.data.normalize_choice(v))
+                            .collect(),
                     ))
                 } else {
-                    Some(ArgcValue::Single(id, must_get_first(values[0])))
+                    let value = must_get_first(values[0]);
+                    Some(ArgcValue::Single(id, self.data.normalize_choice(&value)))
                 }
             }
         }
@@ -436,6 +528,14 @@ impl FlagOptionParam {
         self.inherited
     }
 
+    pub(crate) fn set_group(&mut self, group: Option<String>) {
+        self.group = group;
+    }
+
+    pub(crate) fn group(&self) -> Option<&str> {
+        self.group.as_deref()
+    }
+
     pub(crate) fn create_help_flag(short: Option<&str>, long_prefix: &str, describe: &str) -> Self {
         let mut param_data = ParamData::new("help");
         param_data.describe = describe.to_string();
@@ -480,6 +580,9 @@ impl FlagOptionParam {
     pub(crate) fn list_names(&self) -> Vec<String> {
         let mut output = vec![];
         output.push(self.long_name());
+        if let Some(negated_name) = self.negated_name() {
+            output.push(negated_name);
+        }
         if let Some(short) = &self.short {
             output.push(short.clone());
         }
@@ -499,6 +602,7 @@ pub struct FlagOptionValue {
     pub required: bool,
     pub multiple_values: bool,
     pub multiple_occurs: bool,
+    pub counted: bool,
     pub num_args: (usize, usize),
     pub delimiter: Option<char>,
     pub terminated: bool,
@@ -506,8 +610,15 @@ pub struct FlagOptionValue {
     pub assigned: bool,
     pub default: Option<DefaultValue>,
     pub choice: Option<ChoiceValue>,
+    pub choice_aliases: IndexMap<String, String>,
+    pub choice_ignore_case: bool,
+    pub value_type: Option<ValueType>,
     pub env: Option<String>,
+    pub negated_name: Option<String>,
     pub inherited: bool,
+    pub hidden: bool,
+    pub deprecated: Option<String>,
+    pub group: Option<String>,
 }
 
 #[derive(Debug, PartialEq, Eq, Clone)]
@@ -546,22 +657,18 @@ impl Param for PositionalParam {
         self.data.multiple() || self.terminated()
     }
 
-    fn render_source(&self) -> String {
-        let mut output = vec![self.data.render_source_of_name_value("")];
-
-        if let Some(env) = &self.data.env {
-            match env {
-                Some(v) => output.push(format!("${v}")),
-                None => output.push("$$".into()),
-            }
-        }
-        if let Some(raw_notation) = self.raw_notation.as_ref() {
-            output.push(format!("<{raw_notation}>"));
-        }
-        if !self.data.describe.is_empty() {
-            output.push(self.data.describe.clone());
-        }
-        output.join(" ")
+    fn render_source_columns(&self) -> [String; 4] {
+        let name = format!(
+            "{}{}",
+            self.data.render_source_of_name_value(""),
+            self.data.render_source_of_env()
+        );
+        let notation = self
+            .raw_notation
+            .as_ref()
+            .map(|v| format!("<{v}>"))
+            .unwrap_or_default();
+        [String::new(), name, notation, self.data.describe.clone()]
     }
 }
 
@@ -590,7 +697,12 @@ impl PositionalParam {
             terminated: self.terminated(),
             default: self.data().default.clone(),
             choice: self.data().choice.clone(),
+            choice_aliases: self.data().choice_aliases.clone(),
+            choice_ignore_case: self.data().choice_ignore_case,
+            value_type: self.data().value_type.clone(),
             env: self.bind_env(),
+            hidden: self.hidden(),
+            deprecated: self.deprecated().cloned(),
         }
     }
 
@@ -622,10 +734,17 @@ impl PositionalParam {
             }
         }
         if self.multiple_values() {
-            let values: Vec<String> = values.iter().map(|v| v.to_string()).collect();
+            let values: Vec<String> = values
+                .iter()
+                .map(|v| self.data.normalize_choice(v))
+                .collect();
             Some(ArgcValue::PositionalMultiple(id, values))
         } else {
-            Some(ArgcValue::PositionalSingle(id, must_get_first(values)))
+            let value = must_get_first(values);
+            Some(ArgcValue::PositionalSingle(
+                id,
+                self.data.normalize_choice(&value),
+            ))
         }
     }
 }
@@ -642,7 +761,12 @@ pub struct PositionalValue {
     pub terminated: bool,
     pub default: Option<DefaultValue>,
     pub choice: Option<ChoiceValue>,
+    pub choice_aliases: IndexMap<String, String>,
+    pub choice_ignore_case: bool,
+    pub value_type: Option<ValueType>,
     pub env: Option<String>,
+    pub hidden: bool,
+    pub deprecated: Option<String>,
 }
 
 #[derive(Debug, PartialEq, Eq, Clone)]
@@ -689,18 +813,18 @@ impl Param for EnvParam {
         false
     }
 
-    fn render_source(&self) -> String {
-        let mut output = vec![self.data.render_source_of_name_value("")];
-        if let Some(env) = &self.data.env {
-            match env {
-                Some(v) => output.push(format!("${v}")),
-                None => output.push("$$".into()),
-            }
-        }
-        if !self.data.describe.is_empty() {
-            output.push(self.data.describe.clone());
-        }
-        output.join(" ")
+    fn render_source_columns(&self) -> [String; 4] {
+        let name = format!(
+            "{}{}",
+            self.data.render_source_of_name_value(""),
+            self.data.render_source_of_env()
+        );
+        [
+            String::new(),
+            name,
+            String::new(),
+            self.data.describe.clone(),
+        ]
     }
 }
 
@@ -720,6 +844,9 @@ impl EnvParam {
             required: self.required(),
             default: self.data().default.clone(),
             choice: self.data().choice.clone(),
+            choice_aliases: self.data().choice_aliases.clone(),
+            choice_ignore_case: self.data().choice_ignore_case,
+            value_type: self.data().value_type.clone(),
             inherited: self.inherited,
         }
     }
@@ -729,6 +856,16 @@ impl EnvParam {
         format!("{}{}", self.id(), marker)
     }
 
+    /// Rewrite an aliased or differently-cased value to its canonical choice
+    pub(crate) fn normalize_env_value(&self, value: &str) -> Option<ArgcValue> {
+        let normalized = self.data.normalize_choice(value);
+        if normalized == value {
+            None
+        } else {
+            Some(ArgcValue::Env(self.id().to_string(), normalized))
+        }
+    }
+
     pub(crate) fn get_env_value(&self) -> Option<ArgcValue> {
         let id = self.id().to_string();
         let default = self.data.default.clone()?;
@@ -752,6 +889,9 @@ pub struct EnvValue {
     pub required: bool,
     pub default: Option<DefaultValue>,
     pub choice: Option<ChoiceValue>,
+    pub choice_aliases: IndexMap<String, String>,
+    pub choice_ignore_case: bool,
+    pub value_type: Option<ValueType>,
     pub inherited: bool,
 }
 
@@ -759,11 +899,16 @@ pub struct EnvValue {
 pub(crate) struct ParamData {
     pub(crate) name: String,
     pub(crate) describe: String,
+    pub(crate) value_type: Option<ValueType>,
     pub(crate) choice: Option<ChoiceValue>,
+    pub(crate) choice_aliases: IndexMap<String, String>,
+    pub(crate) choice_ignore_case: bool,
     pub(crate) default: Option<DefaultValue>,
     pub(crate) modifier: Modifier,
     pub(crate) env: Option<Option<String>>,
     pub(crate) root_name: Option<String>,
+    pub(crate) hidden: bool,
+    pub(crate) deprecated: Option<String>,
 }
 
 impl ParamData {
@@ -771,11 +916,16 @@ impl ParamData {
         Self {
             name: name.to_string(),
             describe: String::new(),
+            value_type: None,
             choice: None,
+            choice_aliases: IndexMap::new(),
+            choice_ignore_case: false,
             default: None,
             modifier: Modifier::Optional,
             env: None,
             root_name: None,
+            hidden: false,
+            deprecated: None,
         }
     }
 
@@ -812,6 +962,53 @@ impl ParamData {
         }
     }
 
+    /// Find the canonical value of `value` in `choices`, honoring aliases and case-insensitivity
+    pub(crate) fn resolve_choice<'x>(
+        &'x self,
+        choices: &'x [String],
+        value: &str,
+    ) -> Option<&'x str> {
+        let eq = |v: &str| {
+            if self.choice_ignore_case {
+                v.eq_ignore_ascii_case(value)
+            } else {
+                v == value
+            }
+        };
+        if let Some(choice) = choices.iter().find(|v| v.as_str() == value) {
+            return Some(choice);
+        }
+        if let Some((_, target)) = self
+            .choice_aliases
+            .iter()
+            .find(|(alias, _)| alias.as_str() == value)
+        {
+            return Some(target);
+        }
+        if let Some(choice) = choices.iter().find(|v| eq(v)) {
+            return Some(choice);
+        }
+        self.choice_aliases
+            .iter()
+            .find(|(alias, _)| eq(alias))
+            .map(|(_, target)| target.as_str())
+    }
+
+    pub(crate) fn normalize_choice(&self, value: &str) -> String {
+        self.choice_values()
+            .and_then(|choices| self.resolve_choice(choices, value))
+            .unwrap_or(value)
+            .to_string()
+    }
+
+    pub(crate) fn choice_aliases_of(&self, value: &str) -> Vec<&str> {
+        self.choice_aliases
+            .iter()
+            .filter(|(_, target)| target.as_str() == value)
+            .map(|(alias, _)| alias.as_str())
+            .collect()
+    }
+
     pub(crate) fn default_fn(&self) -> Option<&String> {
         match &self.default {
             Some(DefaultValue::Fn(f)) => Some(f),
@@ -845,13 +1042,18 @@ impl ParamData {
     #[allow(unused)]
     pub(crate) fn render_source_of_name_value(&self, name_suffix: &str) -> String {
         let mut output = format!("{}{name_suffix}", self.name);
+        if let Some(value_type) = &self.value_type {
+            output.push_str(&format!("{{{}}}", value_type.render()));
+        }
         output.push_str(&self.modifier.render());
         match (&self.choice, &self.default) {
             (Some(ChoiceValue::Values(values)), None) => {
-                output.push_str(&format!("[{}]", Self::render_choice_values(values)));
+                let prefix = if self.choice_ignore_case { "~" } else { "" };
+                output.push_str(&format!("[{prefix}{}]", self.render_choice_values(values)));
             }
             (Some(ChoiceValue::Values(values)), Some(DefaultValue::Value(_))) => {
-                output.push_str(&format!("[={}]", Self::render_choice_values(values)));
+                let prefix = if self.choice_ignore_case { "~=" } else { "=" };
+                output.push_str(&format!("[{prefix}{}]", self.render_choice_values(values)));
             }
             (Some(ChoiceValue::Fn(f, validate)), _) => {
                 let prefix = if *validate { "" } else { "?" };
@@ -868,7 +1070,16 @@ impl ParamData {
         output
     }
 
-    pub(crate) fn render_describe(&self, describe: &str, id: &str) -> String {
+    #[allow(unused)]
+    pub(crate) fn render_source_of_env(&self) -> String {
+        match &self.env {
+            Some(Some(v)) => format!(" ${v}"),
+            Some(None) => " $$".into(),
+            None => String::new(),
+        }
+    }
+
+    pub(crate) fn render_describe(&self, describe: &str, id: &str, messages: &Messages) -> String {
         let mut output = describe.to_string();
         let multiline = describe.contains('\n');
         let mut documented = false;
@@ -879,8 +1090,22 @@ impl ParamData {
                 if !output.is_empty() {
                     output.push(sep)
                 }
-                let values: Vec<String> = values.iter().map(|v| escape_shell_words(v)).collect();
-                output.push_str(&format!("[possible values: {}]", values.join(", ")));
+                let values: Vec<String> = values
+                    .iter()
+                    .map(|v| {
+                        let aliases = self.choice_aliases_of(v);
+                        if aliases.is_empty() {
+                            escape_shell_words(v)
+                        } else {
+                            let aliases: Vec<String> =
+                                aliases.iter().map(|v| escape_shell_words(v)).collect();
+                            format!("{} ({})", escape_shell_words(v), aliases.join(", "))
+                        }
+                    })
+                    .collect();
+                output.push_str(
+                    &messages.render("possible-values", &[("values", &values.join(", "))]),
+                );
             }
         }
         if !documented {
@@ -888,30 +1113,39 @@ impl ParamData {
                 if !output.is_empty() {
                     output.push(sep)
                 }
-                output.push_str(&format!("[default: {}]", escape_shell_words(value)));
+                output.push_str(
+                    &messages.render("default-value", &[("value", &escape_shell_words(value))]),
+                );
             }
         }
         if let Some(env) = self.normalize_bind_env(id) {
             if !describe.contains(&env) && !output.is_empty() {
                 output.push(sep)
             }
-            output.push_str(&format!("[env: {env}]"));
+            output.push_str(&messages.render("env-value", &[("env", &env)]));
         }
         output
     }
 
-    fn render_choice_values(values: &[String]) -> String {
-        let values: Vec<String> = values
-            .iter()
-            .map(|value| {
-                if value.chars().any(is_choice_value_terminate) {
-                    format!("\"{value}\"")
-                } else {
-                    value.to_string()
-                }
-            })
-            .collect();
-        values.join("|")
+    fn render_choice_values(&self, values: &[String]) -> String {
+        let quote = |value: &str| {
+            if value.chars().any(is_choice_value_terminate)
+                || value.contains('=')
+                || value.starts_with('~')
+            {
+                format!("\"{value}\"")
+            } else {
+                value.to_string()
+            }
+        };
+        let mut output = vec![];
+        for value in values {
+            output.push(quote(value));
+            for alias in self.choice_aliases_of(value) {
+                output.push(format!("{}={}", quote(alias), quote(value)));
+            }
+        }
+        output.join("|")
     }
 
     fn render_default_value(value: &str) -> String {
@@ -995,3 +1229,287 @@ fn must_get_first(value: &[&str]) -> String {
         value[0].to_string()
     }
 }
+
+#[derive(Debug, PartialEq, Eq, Clone, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ValueType {
+    Int,
+    Float,
+    Bool,
+    Path,
+    File,
+    Dir,
+    Url,
+    Duration,
+    Regex,
+    Range {
+        min: Option<i64>,
+        max: Option<i64>,
+        inclusive: bool,
+    },
+}
+
+impl ValueType {
+    pub(crate) fn parse(value: &str) -> Option<Self> {
+        let value_type = match value {
+            "int" => Self::Int,
+            "float" => Self::Float,
+            "bool" => Self::Bool,
+            "path" => Self::Path,
+            "file" => Self::File,
+            "dir" => Self::Dir,
+            "url" => Self::Url,
+            "duration" => Self::Duration,
+            "regex" => Self::Regex,
+            _ => {
+                let (min, max) = value.split_once("..")?;
+                let (max, inclusive) = match max.strip_prefix('=') {
+                    Some(max) => (max, true),
+                    None => (max, false),
+                };
+                let parse_bound = |v: &str| -> Option<Option<i64>> {
+                    if v.is_empty() {
+                        Some(None)
+                    } else {
+                        v.parse::<i64>().ok().map(Some)
+                    }
+                };
+                let min = parse_bound(min)?;
+                let max = parse_bound(max)?;
+                if (min.is_none() && max.is_none()) || (inclusive && max.is_none()) {
+                    return None;
+                }
+                Self::Range {
+                    min,
+                    max,
+                    inclusive,
+                }
+            }
+        };
+        Some(value_type)
+    }
+
+    pub(crate) fn render(&self) -> String {
+        match self {
+            Self::Int => "int".into(),
+            Self::Float => "float".into(),
+            Self::Bool => "bool".into(),
+            Self::Path => "path".into(),
+            Self::File => "file".into(),
+            Self::Dir => "dir".into(),
+            Self::Url => "url".into(),
+            Self::Duration => "duration".into(),
+            Self::Regex => "regex".into(),
+            Self::Range {
+                min,
+                max,
+                inclusive,
+            } => {
+                let render_bound = |v: &Option<i64>| v.map(|v| v.to_string()).unwrap_or_default();
+                let eq = if *inclusive { "=" } else { "" };
+                format!("{}..{eq}{}", render_bound(min), render_bound(max))
+            }
+        }
+    }
+
+    pub(crate) fn expected(&self) -> String {
+        match self {
+            Self::Int => "integer".into(),
+            Self::Float => "number".into(),
+            Self::Bool => "true, false, 1, 0".into(),
+            Self::Path => "existing path".into(),
+            Self::File => "existing file".into(),
+            Self::Dir => "existing directory".into(),
+            Self::Url => "url".into(),
+            Self::Duration => "duration like 500ms, 30s, 1h30m".into(),
+            Self::Regex => "regular expression".into(),
+            Self::Range { .. } => format!("integer in range {}", self.render()),
+        }
+    }
+
+    pub(crate) fn validate<T: Runtime>(&self, runtime: T, value: &str) -> bool {
+        match self {
+            Self::Int => value.parse::<i64>().is_ok(),
+            Self::Float => is_float(value),
+            Self::Bool => is_bool_value(value),
+            Self::Path => runtime.exist_path(value),
+            Self::File => matches!(runtime.metadata(value), Some((false, _, _))),
+            Self::Dir => matches!(runtime.metadata(value), Some((true, _, _))),
+            Self::Url => is_url(value),
+            Self::Duration => is_duration(value),
+            Self::Regex => runtime
+                .is_bash_regex(value)
+                .unwrap_or_else(|| is_regex(value)),
+            Self::Range {
+                min,
+                max,
+                inclusive,
+            } => {
+                let Ok(value) = value.parse::<i64>() else {
+                    return false;
+                };
+                let above_min = min.map(|min| value >= min).unwrap_or(true);
+                let below_max = match max {
+                    Some(max) if *inclusive => value <= *max,
+                    Some(max) => value < *max,
+                    None => true,
+                };
+                above_min && below_max
+            }
+        }
+    }
+}
+
+fn is_float(value: &str) -> bool {
+    let value = value.strip_prefix(['-', '+']).unwrap_or(value);
+    let (mantissa, exponent) = match value.split_once(['e', 'E']) {
+        Some((mantissa, exponent)) => (mantissa, Some(exponent)),
+        None => (value, None),
+    };
+    let is_digits = |v: &str| !v.is_empty() && v.chars().all(|c| c.is_ascii_digit());
+    let valid_mantissa = match mantissa.split_once('.') {
+        Some((int, frac)) => {
+            (is_digits(int) && (frac.is_empty() || is_digits(frac)))
+                || (int.is_empty() && is_digits(frac))
+        }
+        None => is_digits(mantissa),
+    };
+    let valid_exponent = match exponent {
+        Some(exponent) => is_digits(exponent.strip_prefix(['-', '+']).unwrap_or(exponent)),
+        None => true,
+    };
+    valid_mantissa && valid_exponent
+}
+
+fn is_url(value: &str) -> bool {
+    let Some((scheme, rest)) = value.split_once("://") else {
+        return false;
+    };
+    let mut chars = scheme.chars();
+    chars.next().is_some_and(|c| c.is_ascii_alphabetic())
+        && chars.all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '.' | '-'))
+        && !rest.is_empty()
+        && !rest.chars().any(|c| c.is_whitespace())
+}
+
+fn is_duration(value: &str) -> bool {
+    let mut rest = value;
+    while !rest.is_empty() {
+        let digits = rest.len() - rest.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+        if digits == 0 {
+            return false;
+        }
+        rest = &rest[digits..];
+        let unit = ["ms", "s", "m", "h", "d"]
+            .into_iter()
+            .find(|unit| rest.starts_with(unit));
+        match unit {
+            Some(unit) => rest = &rest[unit.len()..],
+            None => return false,
+        }
+    }
+    !value.is_empty()
+}
+
+// Approximates what bash `[[ "" =~ $pattern ]]` accepts as a POSIX extended regex, used when bash cannot be run
+fn is_regex(value: &str) -> bool {
+    let chars: Vec<char> = value.chars().collect();
+    let mut depth = 0;
+    let mut can_repeat = false;
+    let mut i = 0;
+    while i < chars.len() {
+        match chars[i] {
+            '\\' => {
+                if i + 1 >= chars.len() {
+                    return false;
+                }
+                i += 1;
+                can_repeat = true;
+            }
+            '[' => {
+                i += 1;
+                if chars.get(i) == Some(&'^') {
+                    i += 1;
+                }
+                if chars.get(i) == Some(&']') {
+                    i += 1;
+                }
+                loop {
+                    match chars.get(i) {
+                        None => return false,
+                        Some(']') => break,
+                        Some('[') if matches!(chars.get(i + 1), Some(':' | '.' | '=')) => {
+                            let delimiter = chars[i + 1];
+                            i += 2;
+                            while i + 1 < chars.len()
+                                && !(chars[i] == delimiter && chars[i + 1] == ']')
+                            {
+                                i += 1;
+                            }
+                            if i + 1 >= chars.len() {
+                                return false;
+                            }
+                            i += 2;
+                        }
+                        Some(_) => i += 1,
+                    }
+                }
+                can_repeat = true;
+            }
+            '(' => {
+                depth += 1;
+                can_repeat = false;
+            }
+            ')' => {
+                if depth > 0 {
+                    depth -= 1;
+                }
+                can_repeat = true;
+            }
+            '|' | '^' | '$' => can_repeat = false,
+            '*' | '+' | '?' => {
+                if !can_repeat {
+                    return false;
+                }
+            }
+            '{' => {
+                if !can_repeat {
+                    return false;
+                }
+                let Some(end) = chars[i..].iter().position(|c| *c == '}') else {
+                    return false;
+                };
+                let interval: String = chars[i + 1..i + end].iter().collect();
+                let (min, max) = match interval.split_once(',') {
+                    Some((min, max)) => (min, Some(max)),
+                    None => (interval.as_str(), None),
+                };
+                let parse_bound = |v: &str| -> Option<Option<u32>> {
+                    if v.is_empty() {
+                        Some(None)
+                    } else if v.chars().all(|c| c.is_ascii_digit()) {
+                        v.parse().ok().map(Some)
+                    } else {
+                        None
+                    }
+                };
+                let Some(min) = parse_bound(min) else {
+                    return false;
+                };
+                match max {
+                    Some(max) => match parse_bound(max) {
+                        Some(Some(max)) if min.is_some_and(|min| min > max) => return false,
+                        Some(_) => {}
+                        None => return false,
+                    },
+                    None if min.is_none() => return false,
+                    None => {}
+                }
+                i += end;
+            }
+            _ => can_repeat = true,
+        }
+        i += 1;
+    }
+    depth == 0
+}
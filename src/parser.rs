@@ -1,8 +1,8 @@
 use crate::param::{
     ChoiceValue, DefaultValue, EnvParam, FlagOptionParam, Modifier, Param, ParamData,
-    PositionalParam,
+    PositionalParam, ValueType,
 };
-use crate::utils::is_special_var_char;
+use crate::utils::{is_special_var_char, META_HELP_TEMPLATE};
 use crate::Result;
 
 use anyhow::bail;
@@ -13,7 +13,7 @@ use nom::{
         complete::{anychar, char, one_of, satisfy, space0, space1},
         streaming::none_of,
     },
-    combinator::{eof, fail, map, not, opt, peek, rest, success},
+    combinator::{eof, fail, map, map_opt, not, opt, peek, rest, success},
     error::ErrorKind,
     multi::{many0, many1, separated_list1},
     sequence::{delimited, pair, preceded, separated_pair, terminated},
@@ -39,6 +39,22 @@ pub(crate) enum EventData {
     Cmd(String),
     /// Define alias for a subcommand, e.g. `@alias t,tst`
     Aliases(Vec<String>),
+    /// Define recipes to run before a subcommand, e.g. `@dep build,lint`
+    Deps(Vec<String>),
+    /// Define input files of a subcommand, e.g. `@sources src/**/*.rs Cargo.toml`
+    Sources(Vec<String>),
+    /// Define output files of a subcommand, e.g. `@generates target/app`
+    Generates(Vec<String>),
+    /// Include the tags and functions of another script, e.g. `@include tasks/build.sh`
+    Include(String),
+    /// Start a help section for the following flags/options and subcommands, e.g. `@group Network`
+    Group(String),
+    /// Define a usage example, e.g. `@example prog build --release  Build for release`
+    Example(String, String),
+    /// Define mutually exclusive flags/options, e.g. `@conflicts json,table`
+    Conflicts(Vec<String>),
+    /// Define flags/options required by another one, e.g. `@requires user password`
+    Requires(String, Vec<String>),
     /// Define a env param
     Env(EnvParam),
     /// Define a flag or option parameter
@@ -61,6 +77,28 @@ pub(crate) enum EventScope {
 
 /// Tokenize shell script
 pub(crate) fn parse(source: &str) -> Result<Vec<Event>> {
+    parse_with(source, |position, err| match err {
+        Some(err) => bail!("fail to parse at line {}, {}", position, err),
+        None => bail!("syntax error at line {}", position),
+    })
+}
+
+/// Tokenize shell script, collecting every syntax error instead of stopping at the first one
+#[cfg(feature = "lint")]
+pub(crate) fn parse_lenient(source: &str) -> (Vec<Event>, Vec<(Position, Option<String>)>) {
+    let mut errors = vec![];
+    let events = parse_with(source, |position, err| {
+        errors.push((position, err));
+        Ok(())
+    })
+    .unwrap_or_default();
+    (events, errors)
+}
+
+fn parse_with<F>(source: &str, mut on_error: F) -> Result<Vec<Event>>
+where
+    F: FnMut(Position, Option<String>) -> Result<()>,
+{
     let mut result = vec![];
     let lines: Vec<&str> = source.lines().collect();
     let mut line_idx = 0;
@@ -95,16 +133,22 @@ pub(crate) fn parse(source: &str) -> Result<Vec<Event>> {
                                     take_comment_lines(&lines, line_idx + 1, param.describe_mut());
                                 EventData::Positional(param)
                             }
+                            EventData::Meta(key, mut value)
+                                if key == META_HELP_TEMPLATE && value.is_empty() =>
+                            {
+                                line_idx += take_comment_lines(&lines, line_idx + 1, &mut value);
+                                EventData::Meta(key, value)
+                            }
                             v => v,
                         };
                         result.push(Event { position, data });
                     } else {
-                        bail!("syntax error at line {}", position)
+                        on_error(position, None)?;
                     }
                 }
             }
             Err(err) => {
-                bail!("fail to parse at line {}, {}", position, err)
+                on_error(position, Some(err.to_string()))?;
             }
         }
         line_idx += 1;
@@ -146,6 +190,11 @@ fn parse_tag(input: &str) -> nom::IResult<&str, Option<EventData>> {
             parse_tag_meta,
             parse_tag_param,
             parse_tag_alias,
+            parse_tag_dep,
+            parse_tag_files,
+            parse_tag_include,
+            parse_tag_example,
+            parse_tag_relation,
             parse_tag_unknown,
         )),
     )
@@ -155,7 +204,7 @@ fn parse_tag(input: &str) -> nom::IResult<&str, Option<EventData>> {
 fn parse_tag_text(input: &str) -> nom::IResult<&str, Option<EventData>> {
     map(
         pair(
-            alt((tag("describe"), tag("version"), tag("cmd"))),
+            alt((tag("describe"), tag("version"), tag("cmd"), tag("group"))),
             parse_tail,
         ),
         |(tag, text)| {
@@ -164,6 +213,7 @@ fn parse_tag_text(input: &str) -> nom::IResult<&str, Option<EventData>> {
                 "describe" => EventData::Describe(text),
                 "version" => EventData::Version(text),
                 "cmd" => EventData::Cmd(text),
+                "group" => EventData::Group(text),
                 _ => unreachable!(),
             })
         },
@@ -217,6 +267,81 @@ fn parse_tag_alias(input: &str) -> nom::IResult<&str, Option<EventData>> {
     .parse(input)
 }
 
+fn parse_tag_dep(input: &str) -> nom::IResult<&str, Option<EventData>> {
+    map(
+        preceded(pair(tag("dep"), space1), parse_name_list),
+        |list| {
+            Some(EventData::Deps(
+                list.iter().map(|v| v.to_string()).collect(),
+            ))
+        },
+    )
+    .parse(input)
+}
+
+fn parse_tag_files(input: &str) -> nom::IResult<&str, Option<EventData>> {
+    map(
+        pair(
+            alt((tag("sources"), tag("generates"))),
+            preceded(space1, map(rest, |v: &str| v.split_whitespace())),
+        ),
+        |(tag, list)| {
+            let list: Vec<String> = list.map(|v| v.to_string()).collect();
+            if list.is_empty() {
+                return None;
+            }
+            Some(match tag {
+                "sources" => EventData::Sources(list),
+                "generates" => EventData::Generates(list),
+                _ => unreachable!(),
+            })
+        },
+    )
+    .parse(input)
+}
+
+fn parse_tag_relation(input: &str) -> nom::IResult<&str, Option<EventData>> {
+    alt((
+        map(
+            preceded(pair(tag("conflicts"), space1), parse_name_list),
+            |list| {
+                Some(EventData::Conflicts(
+                    list.iter().map(|v| v.to_string()).collect(),
+                ))
+            },
+        ),
+        map(
+            preceded(
+                pair(tag("requires"), space1),
+                separated_pair(parse_name, space1, parse_name_list),
+            ),
+            |(name, list)| {
+                Some(EventData::Requires(
+                    name.to_string(),
+                    list.iter().map(|v| v.to_string()).collect(),
+                ))
+            },
+        ),
+    ))
+    .parse(input)
+}
+
+fn parse_tag_include(input: &str) -> nom::IResult<&str, Option<EventData>> {
+    map(preceded(tag("include"), parse_tail), |path: &str| {
+        (!path.is_empty()).then(|| EventData::Include(path.to_string()))
+    })
+    .parse(input)
+}
+
+fn parse_tag_example(input: &str) -> nom::IResult<&str, Option<EventData>> {
+    map(preceded(tag("example"), parse_tail), |text: &str| {
+        let (value, describe) = text.split_once("  ").unwrap_or((text, ""));
+        (!value.is_empty())
+            .then(|| EventData::Example(value.to_string(), describe.trim().to_string()))
+    })
+    .parse(input)
+}
+
 fn parse_tag_unknown(input: &str) -> nom::IResult<&str, Option<EventData>> {
     map(parse_name, |v| Some(EventData::Unknown(v.to_string()))).parse(input)
 }
@@ -338,14 +463,19 @@ fn parse_with_long_flag_param(input: &str) -> nom::IResult<&str, FlagOptionParam
     map(
         (
             parse_with_long_head,
+            opt(tag("[no-]")),
             parse_with_long_flag_name,
             parse_zero_or_one_bind_env,
             parse_tail,
         ),
-        |((short, long_prefix), mut arg, env, describe)| {
+        |((short, long_prefix), negatable, mut arg, env, describe)| {
             arg.env = env;
             arg.describe = describe.to_string();
-            FlagOptionParam::new(arg, true, short, long_prefix, &[])
+            let mut param = FlagOptionParam::new(arg, true, short, long_prefix, &[]);
+            if negatable.is_some() {
+                param.set_negatable();
+            }
+            param
         },
     )
     .parse(input)
@@ -371,11 +501,11 @@ fn parse_no_long_flag_param(input: &str) -> nom::IResult<&str, FlagOptionParam>
 
 fn parse_with_long_flag_name(input: &str) -> nom::IResult<&str, ParamData> {
     alt((
-        map(terminated(parse_param_name, tag("*")), |mut arg| {
+        map(terminated(parse_flag_name, tag("*")), |mut arg| {
             arg.modifier = Modifier::MultipleOptional;
             arg
         }),
-        parse_param_name,
+        parse_flag_name,
     ))
     .parse(input)
 }
@@ -487,15 +617,7 @@ fn parse_param_modifier_choices_default(input: &str) -> nom::IResult<&str, Param
             parse_param_modifier,
             delimited(char('['), parse_choices_default, char(']')),
         ),
-        |(mut arg, (values, default))| {
-            arg.choice = Some(ChoiceValue::Values(
-                values.iter().map(|v| v.to_string()).collect(),
-            ));
-            if let Some(value) = default {
-                arg.default = Some(DefaultValue::Value(value.to_string()));
-            }
-            arg
-        },
+        |(arg, (ignore_case, items))| set_choice_values(arg, ignore_case, &items, true),
     )
     .parse(input)
 }
@@ -506,16 +628,45 @@ fn parse_param_modifier_choices(input: &str) -> nom::IResult<&str, ParamData> {
             parse_param_modifier,
             delimited(char('['), parse_choices, char(']')),
         ),
-        |(mut arg, values)| {
-            arg.choice = Some(ChoiceValue::Values(
-                values.iter().map(|v| v.to_string()).collect(),
-            ));
-            arg
-        },
+        |(arg, (ignore_case, items))| set_choice_values(arg, ignore_case, &items, false),
     )
     .parse(input)
 }
 
+// Split `a|b=a|c` into canonical values and aliases, `b=a` is a literal value unless `a` is listed
+fn set_choice_values(
+    mut arg: ParamData,
+    ignore_case: bool,
+    items: &[ChoiceItem],
+    default: bool,
+) -> ParamData {
+    let canonical: Vec<&str> = items
+        .iter()
+        .filter(|(_, alias)| alias.is_none())
+        .map(|(value, _)| *value)
+        .collect();
+    let mut values = vec![];
+    for (value, alias) in items {
+        match alias {
+            Some((alias, target)) if canonical.contains(target) => {
+                arg.choice_aliases
+                    .insert(alias.to_string(), target.to_string());
+            }
+            _ => values.push(value.to_string()),
+        }
+    }
+    if default {
+        let value = match items[0] {
+            (_, Some((_, target))) if canonical.contains(&target) => target,
+            (value, _) => value,
+        };
+        arg.default = Some(DefaultValue::Value(value.to_string()));
+    }
+    arg.choice = Some(ChoiceValue::Values(values));
+    arg.choice_ignore_case = ignore_case;
+    arg
+}
+
 fn parse_param_modifier_choices_fn(input: &str) -> nom::IResult<&str, ParamData> {
     map(
         pair(
@@ -530,10 +681,33 @@ fn parse_param_modifier_choices_fn(input: &str) -> nom::IResult<&str, ParamData>
     .parse(input)
 }
 
+// Parse `str` `str{type}`
 fn parse_param_name(input: &str) -> nom::IResult<&str, ParamData> {
+    map(
+        pair(parse_name, opt(parse_value_type)),
+        |(name, value_type)| {
+            let mut arg = ParamData::new(name);
+            arg.value_type = value_type;
+            arg
+        },
+    )
+    .parse(input)
+}
+
+fn parse_flag_name(input: &str) -> nom::IResult<&str, ParamData> {
     map(parse_name, ParamData::new).parse(input)
 }
 
+// Parse `{int}` `{1..=65535}`
+fn parse_value_type(input: &str) -> nom::IResult<&str, ValueType> {
+    delimited(
+        char('{'),
+        map_opt(take_till(|c| c == '}'), ValueType::parse),
+        char('}'),
+    )
+    .parse(input)
+}
+
 // Zero or many '<FOO>'
 fn parse_zero_or_many_value_notations(input: &str) -> nom::IResult<&str, Vec<&str>> {
     many0(parse_value_notation).parse(input)
@@ -557,31 +731,48 @@ fn parse_bind_env_name(input: &str) -> nom::IResult<&str, &str> {
     take_while1(is_env_name_char).parse(input)
 }
 
-// Parse `a|b|c`
-fn parse_choices(input: &str) -> nom::IResult<&str, Vec<&str>> {
-    map(separated_list1(char('|'), parse_choice_value), |choices| {
-        choices
-    })
+/// Whether to ignore case, and the choice items
+type ChoiceItems<'a> = (bool, Vec<ChoiceItem<'a>>);
+
+/// The value of a choice, and its `alias=value` split if it is unquoted and contains `=`
+type ChoiceItem<'a> = (&'a str, Option<(&'a str, &'a str)>);
+
+// Parse `a|b|c` `~a|b=a|c`
+fn parse_choices(input: &str) -> nom::IResult<&str, ChoiceItems<'_>> {
+    pair(
+        map(opt(char('~')), |v| v.is_some()),
+        separated_list1(char('|'), parse_choice_item),
+    )
     .parse(input)
 }
 
-// Parse `=a|b|c`
-fn parse_choices_default(input: &str) -> nom::IResult<&str, (Vec<&str>, Option<&str>)> {
+// Parse `=a|b|c` `~=a|b=a|c`
+fn parse_choices_default(input: &str) -> nom::IResult<&str, ChoiceItems<'_>> {
     map(
         (
+            opt(char('~')),
             char('='),
-            parse_choice_value,
-            many1(preceded(char('|'), parse_choice_value)),
+            parse_choice_item,
+            many1(preceded(char('|'), parse_choice_item)),
         ),
-        |(_, head, tail)| {
-            let mut choices = vec![head];
-            choices.extend(tail);
-            (choices, Some(head))
+        |(ignore_case, _, head, tail)| {
+            let mut items = vec![head];
+            items.extend(tail);
+            (ignore_case.is_some(), items)
         },
     )
     .parse(input)
 }
 
+// Parse `a` `b=a` `"b=a"`
+fn parse_choice_item(input: &str) -> nom::IResult<&str, ChoiceItem<'_>> {
+    alt((
+        map(parse_quoted_string, |value| (value, None)),
+        map(parse_choice_value, |value| (value, value.split_once('='))),
+    ))
+    .parse(input)
+}
+
 fn parse_tail(input: &str) -> nom::IResult<&str, &str> {
     alt((
         eof,
@@ -856,16 +1047,40 @@ mod tests {
                 )))
             )
         };
+        ($comment:literal, Deps, $text:expr) => {
+            assert_eq!(
+                parse_line($comment).unwrap().1,
+                Some(Some(EventData::Deps(
+                    $text.iter().map(|v| v.to_string()).collect()
+                )))
+            )
+        };
+        ($comment:literal, Sources, $text:expr) => {
+            assert_eq!(
+                parse_line($comment).unwrap().1,
+                Some(Some(EventData::Sources(
+                    $text.iter().map(|v| v.to_string()).collect()
+                )))
+            )
+        };
+        ($comment:literal, Generates, $text:expr) => {
+            assert_eq!(
+                parse_line($comment).unwrap().1,
+                Some(Some(EventData::Generates(
+                    $text.iter().map(|v| v.to_string()).collect()
+                )))
+            )
+        };
         ($comment:literal, $kind:ident, $text:expr) => {
             assert_eq!(
                 parse_line($comment).unwrap().1,
                 Some(Some(EventData::$kind($text.to_string())))
             )
         };
-        ($comment:literal, Meta, $key:expr, $value:expr) => {
+        ($comment:literal, $kind:ident, $key:expr, $value:expr) => {
             assert_eq!(
                 parse_line($comment).unwrap().1,
-                Some(Some(EventData::Meta($key.to_string(), $value.to_string())))
+                Some(Some(EventData::$kind($key.to_string(), $value.to_string())))
             )
         };
     }
@@ -968,6 +1183,12 @@ mod tests {
         assert_parse_option_arg!("--foo-abc <FOO>");
         assert_parse_option_arg!("--foo=\"a b\"");
         assert_parse_option_arg!("--foo[\"a|b\"|\"c]d\"]");
+        assert_parse_option_arg!("--foo[~a|b]");
+        assert_parse_option_arg!("--foo[~=a|b]");
+        assert_parse_option_arg!("--foo[a|aa=a|b]");
+        assert_parse_option_arg!("--foo[=aa=a|a|b]", "--foo[=a|aa=a|b]");
+        assert_parse_option_arg!("--foo[\"a=b\"|c]");
+        assert_parse_option_arg!("--foo[a|b=c]", "--foo[a|\"b=c\"]");
         assert_parse_option_arg!("--foo <abc>");
         assert_parse_option_arg!("--foo <abc> <def>");
         assert_parse_option_arg!("--foo <>");
@@ -976,6 +1197,11 @@ mod tests {
         assert_parse_option_arg!("--foo $$");
         assert_parse_option_arg!("--foo $FOO");
         assert_parse_option_arg!("--foo $FOO <FOO>");
+        assert_parse_option_arg!("--foo{int}");
+        assert_parse_option_arg!("--foo{1..=65535}!");
+        assert_parse_option_arg!("--foo{..10}*,");
+        assert_parse_option_arg!("--foo{duration}=30s");
+        assert_parse_option_arg!("--foo-{dir} <DIR>");
     }
 
     #[test]
@@ -1047,6 +1273,8 @@ mod tests {
         assert_parse_flag_arg!("--foo A foo flag");
         assert_parse_flag_arg!("--foo");
         assert_parse_flag_arg!("--foo*");
+        assert_parse_flag_arg!("-f --[no-]foo A foo flag");
+        assert_parse_flag_arg!("--[no-]foo $$");
     }
 
     #[test]
@@ -1105,6 +1333,7 @@ mod tests {
         assert_parse_positional_arg!("foo[a|b]");
         assert_parse_positional_arg!("foo[`_foo`]");
         assert_parse_positional_arg!("foo[=a|b]");
+        assert_parse_positional_arg!("foo[~a|aa=a|b]");
         assert_parse_positional_arg!("foo![a|b]");
         assert_parse_positional_arg!("foo![`_foo`]");
         assert_parse_positional_arg!("foo![=a|b]");
@@ -1116,6 +1345,8 @@ mod tests {
         assert_parse_positional_arg!("foo*[=a|b]");
         assert_parse_positional_arg!("foo $$");
         assert_parse_positional_arg!("foo $FOO");
+        assert_parse_positional_arg!("foo{path}+");
+        assert_parse_positional_arg!("foo{int}[=1|2|3]");
     }
 
     #[test]
@@ -1130,8 +1361,35 @@ mod tests {
         assert_token!("# @meta key", Meta, "key", "");
         assert_token!("# @meta key value", Meta, "key", "value");
         assert_token!("# @cmd A subcommand", Cmd, "A subcommand");
+        assert_token!("# @group Network", Group, "Network");
+        assert_token!("# @group", Group, "");
         assert_token!("# @alias tst", Aliases, ["tst"]);
         assert_token!("# @alias t,tst", Aliases, ["t", "tst"]);
+        assert_token!("# @dep build", Deps, ["build"]);
+        assert_token!(
+            "# @dep build, lint,test::unit",
+            Deps,
+            ["build", "lint", "test::unit"]
+        );
+        assert_token!("# @include tasks/build.sh", Include, "tasks/build.sh");
+        assert_token!("# @include  lib.sh ", Include, "lib.sh");
+        assert_token!("# @include", Error);
+        assert_token!(
+            "# @example prog build --release   Build for release",
+            Example,
+            "prog build --release",
+            "Build for release"
+        );
+        assert_token!("# @example prog -h", Example, "prog -h", "");
+        assert_token!("# @example", Error);
+        assert_token!("# @conflicts json,table", Conflicts);
+        assert_eq!(
+            parse_line("# @requires user password,token").unwrap().1,
+            Some(Some(EventData::Requires(
+                "user".into(),
+                vec!["password".into(), "token".into()]
+            )))
+        );
         assert_token!("# @flag -f --foo", FlagOption);
         assert_token!("# @option -f --foo", FlagOption);
         assert_token!("# @arg foo", Positional);
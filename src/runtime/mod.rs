@@ -36,6 +36,30 @@ where
         self.os() == "windows"
     }
 
+    /// Whether stderr, where the help is printed, is a terminal
+    fn is_terminal(&self) -> bool {
+        false
+    }
+
+    /// Whether bash `[[ "" =~ $pattern ]]` accepts the pattern, None if bash cannot be run
+    fn is_bash_regex(&self, _pattern: &str) -> Option<bool> {
+        None
+    }
+
+    /// Whether to color the help, respects `NO_COLOR`, `CLICOLOR_FORCE` and `TERM`
+    fn use_color(&self) -> bool {
+        if self.env_var("NO_COLOR").is_some_and(|v| !v.is_empty()) {
+            return false;
+        }
+        if self
+            .env_var("CLICOLOR_FORCE")
+            .is_some_and(|v| !v.is_empty() && v != "0")
+        {
+            return true;
+        }
+        self.is_terminal() && self.env_var("TERM").as_deref() != Some("dumb")
+    }
+
     fn shell_args(&self, shell_path: &str) -> Vec<String> {
         if let Some(name) = self.basename(shell_path).map(|v| v.to_lowercase()) {
             match name.as_str() {
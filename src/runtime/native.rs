@@ -1,7 +1,7 @@
 use super::Runtime;
 
 use anyhow::{anyhow, bail, Result};
-use std::{env, fs, path::Path, process, thread};
+use std::{env, fs, io::IsTerminal, path::Path, process, thread};
 
 #[derive(Debug, Clone, Copy, Default)]
 pub struct NativeRuntime;
@@ -47,6 +47,18 @@ impl Runtime for NativeRuntime {
         self.which("bash")
     }
 
+    fn is_bash_regex(&self, pattern: &str) -> Option<bool> {
+        let status = process::Command::new(self.bash_path()?)
+            .args(["--noprofile", "--norc", "-c"])
+            .arg(r#"[[ "" =~ $1 ]]; [[ $? -ne 2 ]]"#)
+            .args(["bash", pattern])
+            .stdout(process::Stdio::null())
+            .stderr(process::Stdio::null())
+            .status()
+            .ok()?;
+        Some(status.success())
+    }
+
     fn exec_bash_functions(
         &self,
         script_file: &str,
@@ -175,4 +187,8 @@ impl Runtime for NativeRuntime {
         let data = fs::read_to_string(path).ok()?;
         Some(data)
     }
+
+    fn is_terminal(&self) -> bool {
+        std::io::stderr().is_terminal()
+    }
 }
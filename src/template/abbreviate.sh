@@ -0,0 +1,24 @@
+_argc_abbreviate() {
+    local arg="$1" key name value="" matches=() list
+    shift
+    if [[ "$arg" != --?* ]]; then
+        return 1
+    fi
+    key="${arg%%=*}"
+    if [[ "$arg" == *=* ]]; then
+        value="=${arg#*=}"
+    fi
+    for name in "$@"; do
+        if [[ "$name" == "$key"* ]]; then
+            matches+=("$name")
+        fi
+    done
+    if [[ "${#matches[@]}" -eq 1 ]]; then
+        _argc_abbreviate_value="${matches[0]}$value"
+        return 0
+    elif [[ "${#matches[@]}" -gt 1 ]]; then
+        printf -v list '%s, ' "${matches[@]}"
+        _argc_die "error: ambiguous argument \`$key\`"$'\n'"  [possible arguments: ${list%, }]"
+    fi
+    return 1
+}
@@ -6,6 +6,6 @@ _argc_check_bool() {
     elif [[ "$env_value" == "false" ]] || [[ "$env_value" == "0" ]]; then
         return 1
     else
-        _argc_die "error: environment variable '$env_name' has invalid value for param '$param_name'"
+        _argc_die "error: environment variable \`$env_name\` has invalid value for param '$param_name'"
     fi
 }
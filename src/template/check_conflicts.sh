@@ -0,0 +1,13 @@
+_argc_check_conflicts() {
+    local found="" name var_name
+    while [[ $# -gt 1 ]]; do
+        name="$1" var_name="$2"
+        shift 2
+        if [[ -n "${!var_name+x}" ]]; then
+            if [[ -n "$found" ]]; then
+                _argc_die "error: the argument \`$found\` cannot be used with \`$name\`"
+            fi
+            found="$name"
+        fi
+    done
+}
@@ -0,0 +1,15 @@
+_argc_check_requires() {
+    local name="$1" var_name="$2" item missing=""
+    if [[ -z "${!var_name+x}" ]]; then
+        return
+    fi
+    for item in "${@:3}"; do
+        var_name="${item%%:*}"
+        if [[ -z "${!var_name+x}" ]]; then
+            missing="$missing"$'\n'"  ${item#*:}"
+        fi
+    done
+    if [[ -n "$missing" ]]; then
+        _argc_die "error: the argument \`$name\` requires the following arguments:$missing"
+    fi
+}
@@ -0,0 +1,18 @@
+function _argc_load_dotenv
+    for env_file in $argv
+        test -f $env_file; or continue
+        for line in (cat $env_file)
+            string match -qr '^\s*[a-zA-Z_][a-zA-Z0-9_]*=' -- $line; or continue
+            set -l key (string replace -r '^\s*([a-zA-Z_][a-zA-Z0-9_]*)=.*$' '$1' -- $line)
+            set -l value (string replace -r '^\s*[a-zA-Z_][a-zA-Z0-9_]*=' '' -- $line)
+            if string match -qr '^"' -- $value
+                set value (string replace -r '^"(.*)".*$' '$1' -- $value)
+            else if string match -qr "^'" -- $value
+                set value (string replace -r "^'(.*)'.*\$" '$1' -- $value)
+            else
+                set value (string replace -r '\s+#.*$' '' -- $value | string trim)
+            end
+            set -q $key; or set -gx $key "$value"
+        end
+    end
+end
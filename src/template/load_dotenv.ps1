@@ -0,0 +1,20 @@
+function _argc_load_dotenv {
+    foreach ($envFile in $args) {
+        if (-not (Test-Path -LiteralPath $envFile -PathType Leaf)) { continue }
+        foreach ($line in Get-Content -LiteralPath $envFile) {
+            if ($line -notmatch '^\s*([a-zA-Z_][a-zA-Z0-9_]*)=(.*)$') { continue }
+            $key = $Matches[1]
+            $value = $Matches[2]
+            if ($value -match '^"(.*)"') {
+                $value = $Matches[1]
+            } elseif ($value -match "^'(.*)'") {
+                $value = $Matches[1]
+            } else {
+                $value = ($value -replace '\s+#.*$', '').Trim()
+            }
+            if ($null -eq [Environment]::GetEnvironmentVariable($key)) {
+                [Environment]::SetEnvironmentVariable($key, $value)
+            }
+        }
+    }
+}
@@ -0,0 +1,26 @@
+_argc_print_help() {
+    local fd="${1:-1}" text lines rows pager re
+    IFS= read -r -d '' text || true
+    text="${text%$'\n'}"
+    text="${text//\\e\[/$'\e['}"
+    if [[ -n "${NO_COLOR:-}" ]] || { [[ -z "${CLICOLOR_FORCE:-}" || "$CLICOLOR_FORCE" == "0" ]] && { [[ ! -t "$fd" ]] || [[ "${TERM:-}" == "dumb" ]]; }; }; then
+        re=$'\e''\[[0-9;]*m'
+        while [[ "$text" =~ $re ]]; do
+            text="${text//"${BASH_REMATCH[0]}"/}"
+        done
+    fi
+    if [[ -t "$fd" ]]; then
+        lines="${text//[^$'\n']/}"
+        rows="${LINES:-}"
+        if [[ -z "$rows" ]]; then
+            rows="$(stty size 2>/dev/null <&"$fd")"
+            rows="${rows%% *}"
+        fi
+        pager="${PAGER:-less}"
+        if [[ -n "$rows" ]] && [[ "$((${#lines} + 1))" -ge "$rows" ]] && command -v "${pager%% *}" >/dev/null 2>&1; then
+            printf '%s\n' "$text" | LESS="${LESS:-FRX}" $pager >&"$fd"
+            return
+        fi
+    fi
+    printf '%s\n' "$text" >&"$fd"
+}
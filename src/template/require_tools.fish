@@ -0,0 +1,10 @@
+function _argc_require_tools
+    set -l missing_tools
+    for tool in $argv
+        type -q $tool; or set -a missing_tools $tool
+    end
+    if test (count $missing_tools) -gt 0
+        echo "error: missing tools: $missing_tools" >&2
+        return 1
+    end
+end
@@ -0,0 +1,7 @@
+function _argc_require_tools {
+    $missingTools = @($args | Where-Object { -not (Get-Command $_ -ErrorAction SilentlyContinue) })
+    if ($missingTools.Count -gt 0) {
+        [Console]::Error.WriteLine("error: missing tools: $($missingTools -join ' ')")
+        exit 1
+    }
+}
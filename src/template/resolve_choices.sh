@@ -0,0 +1,55 @@
+_argc_resolve_choices() {
+    local render_name="$1" raw_choices="$2" raw_aliases="$3" ignore_case="$4" choices item choice alias target value pass nocasematch=0 concated_choices=""
+    while IFS= read -r line; do
+        choices+=("$line")
+    done <<<"$raw_choices"
+    for choice in "${choices[@]}"; do
+        if [[ -z "$concated_choices" ]]; then
+            concated_choices="$choice"
+        else
+            concated_choices="$concated_choices, $choice"
+        fi
+    done
+    if shopt -q nocasematch; then
+        nocasematch=1
+    fi
+    _argc_resolve_choices_values=()
+    for item in "${@:5}"; do
+        value=""
+        for pass in 0 1; do
+            if [[ $pass -eq 0 ]]; then
+                shopt -u nocasematch
+            elif [[ "$ignore_case" -eq 1 ]]; then
+                shopt -s nocasematch
+            else
+                break
+            fi
+            for choice in "${choices[@]}"; do
+                if [[ "$item" == "$choice" ]]; then
+                    value="$choice"
+                    break
+                fi
+            done
+            if [[ -z "$value" ]] && [[ -n "$raw_aliases" ]]; then
+                while IFS= read -r alias && IFS= read -r target; do
+                    if [[ "$item" == "$alias" ]]; then
+                        value="$target"
+                        break
+                    fi
+                done <<<"$raw_aliases"
+            fi
+            if [[ -n "$value" ]]; then
+                break
+            fi
+        done
+        if [[ $nocasematch -eq 1 ]]; then
+            shopt -s nocasematch
+        else
+            shopt -u nocasematch
+        fi
+        if [[ -z "$value" ]]; then
+            _argc_die "error: invalid value \`$item\` for $render_name"$'\n'"  [possible values: $concated_choices]$(_argc_suggest value "$item" "${choices[@]}")"
+        fi
+        _argc_resolve_choices_values+=("$value")
+    done
+}
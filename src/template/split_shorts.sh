@@ -0,0 +1,20 @@
+_argc_split_shorts() {
+    local arg="$1" subcmds=" $2 " shorts=" $3 " i ch
+    _argc_split_shorts_values=()
+    if [[ "$arg" == --* ]] || [[ ${#arg} -lt 3 ]] || [[ "$arg" != -* ]]; then
+        return 1
+    fi
+    if [[ "$subcmds" == *" -${arg:1:1} "* ]]; then
+        _argc_split_shorts_values=("-${arg:1:1}" "-${arg:2}")
+        return 0
+    fi
+    for ((i = 1; i < ${#arg}; i++)); do
+        ch="${arg:i:1}"
+        if [[ "$shorts" != *" -$ch "* ]]; then
+            _argc_split_shorts_values=()
+            return 1
+        fi
+        _argc_split_shorts_values+=("-$ch")
+    done
+    return 0
+}
@@ -0,0 +1,55 @@
+_argc_suggest() {
+    local kind="$1" value="$2" candidate best="" best_distance=0 distance cost min i j
+    local len_a=${#value} len_b max_len
+    local -a prev curr
+    for candidate in "${@:3}"; do
+        len_b=${#candidate}
+        prev=()
+        for ((j = 0; j <= len_b; j++)); do
+            prev[j]=$j
+        done
+        for ((i = 1; i <= len_a; i++)); do
+            curr=("$i")
+            for ((j = 1; j <= len_b; j++)); do
+                cost=1
+                if [[ "${value:i-1:1}" == "${candidate:j-1:1}" ]]; then
+                    cost=0
+                fi
+                min=$((prev[j - 1] + cost))
+                if [[ $((prev[j] + 1)) -lt $min ]]; then
+                    min=$((prev[j] + 1))
+                fi
+                if [[ $((curr[j - 1] + 1)) -lt $min ]]; then
+                    min=$((curr[j - 1] + 1))
+                fi
+                curr[j]=$min
+            done
+            prev=("${curr[@]}")
+        done
+        distance=${prev[len_b]}
+        max_len=$len_a
+        if [[ $len_b -gt $max_len ]]; then
+            max_len=$len_b
+        fi
+        if [[ $((distance * 3)) -gt $max_len ]]; then
+            continue
+        fi
+        if [[ -z "$best" ]] || [[ $distance -lt $best_distance ]]; then
+            best="$candidate"
+            best_distance=$distance
+        fi
+    done
+    if [[ -n "$best" ]]; then
+        case "$kind" in
+        subcommand)
+            printf '\n\n  %s' "tip: a similar subcommand exists: '$best'"
+            ;;
+        argument)
+            printf '\n\n  %s' "tip: a similar argument exists: '$best'"
+            ;;
+        *)
+            printf '\n\n  %s' "tip: a similar value exists: '$best'"
+            ;;
+        esac
+    fi
+}
@@ -0,0 +1,66 @@
+_argc_validate_type() {
+    local render_name="$1" value_type="$2" expected="$3" item valid num digits min max
+    for item in "${@:4}"; do
+        valid=1
+        num=""
+        # Only digits within the 64-bit range, checked as text since bash arithmetic wraps around
+        if [[ "$item" =~ ^([-+]?)0*([0-9]+)$ ]]; then
+            digits="${BASH_REMATCH[2]}"
+            if [[ ${#digits} -lt 19 ]] || [[ ${#digits} -eq 19 && "$digits" < "9223372036854775808" ]] ||
+                [[ "${BASH_REMATCH[1]}" == "-" && "$digits" == "9223372036854775808" ]]; then
+                num="${BASH_REMATCH[1]}$digits"
+            fi
+        fi
+        case "$value_type" in
+        int)
+            [[ -n "$num" ]] || valid=0
+            ;;
+        float)
+            [[ "$item" =~ ^[-+]?([0-9]+(\.[0-9]*)?|\.[0-9]+)([eE][-+]?[0-9]+)?$ ]] || valid=0
+            ;;
+        bool)
+            [[ "$item" == "true" || "$item" == "false" || "$item" == "1" || "$item" == "0" ]] || valid=0
+            ;;
+        path)
+            [[ -e "$item" ]] || valid=0
+            ;;
+        file)
+            [[ -e "$item" && ! -d "$item" ]] || valid=0
+            ;;
+        dir)
+            [[ -d "$item" ]] || valid=0
+            ;;
+        url)
+            [[ "$item" =~ ^[a-zA-Z][a-zA-Z0-9+.-]*://[^[:space:]]+$ ]] || valid=0
+            ;;
+        duration)
+            [[ "$item" =~ ^([0-9]+(ms|s|m|h|d))+$ ]] || valid=0
+            ;;
+        regex)
+            [[ "" =~ $item ]] || [[ $? -ne 2 ]] || valid=0
+            ;;
+        *..*)
+            if [[ -n "$num" ]]; then
+                min="${value_type%%..*}"
+                max="${value_type#*..}"
+                if [[ -n "$min" ]] && ((num < min)); then
+                    valid=0
+                fi
+                if [[ "$max" == =* ]]; then
+                    max="${max:1}"
+                    if ((num > max)); then
+                        valid=0
+                    fi
+                elif [[ -n "$max" ]] && ((num >= max)); then
+                    valid=0
+                fi
+            else
+                valid=0
+            fi
+            ;;
+        esac
+        if [[ $valid -eq 0 ]]; then
+            _argc_die "error: invalid value \`$item\` for $render_name"$'\n'"  [expected: $expected]"
+        fi
+    done
+}
@@ -13,13 +13,20 @@ pub(crate) const META_DEFAULT_SUBCOMMAND: &str = "default-subcommand";
 pub(crate) const META_INHERIT_FLAG_OPTIONS: &str = "inherit-flag-options";
 pub(crate) const META_SYMBOL: &str = "symbol";
 pub(crate) const META_COMBINE_SHORTS: &str = "combine-shorts";
+pub(crate) const META_ABBREVIATE_LONG_OPTIONS: &str = "abbreviate-long-options";
+pub(crate) const META_ARGFILE: &str = "argfile";
 pub(crate) const META_EXTERNAL_SUBCOMMANDS: &str = "external-subcommands";
 pub(crate) const META_MAN_SECTION: &str = "man-section";
 pub(crate) const META_REQUIRE_TOOLS: &str = "require-tools";
+pub(crate) const META_HIDDEN: &str = "hidden";
+pub(crate) const META_DEPRECATED: &str = "deprecated";
+pub(crate) const META_HELP_TEMPLATE: &str = "help-template";
+pub(crate) const META_LOCALE: &str = "locale";
+pub(crate) const META_MESSAGE: &str = "message";
 
 pub(crate) const MAX_ARGS: usize = 32767;
 
-#[cfg(any(feature = "build", feature = "eval-bash"))]
+#[cfg(any(feature = "build", feature = "eval"))]
 pub const ARGC_REQUIRE_TOOLS: &str = include_str!("template/require_tools.sh");
 
 #[cfg(any(feature = "build", feature = "eval-bash"))]
@@ -28,6 +35,9 @@ pub const ARGC_REQUIRE_PARAMS: &str = include_str!("template/require_params.sh")
 #[cfg(any(feature = "build", feature = "eval-bash"))]
 pub const ARGC_LOAD_DOTENV: &str = include_str!("template/load_dotenv.sh");
 
+#[cfg(any(feature = "build", feature = "eval-bash"))]
+pub const ARGC_PRINT_HELP: &str = include_str!("template/print_help.sh");
+
 pub fn to_cobol_case(value: &str) -> String {
     Converter::new()
         .set_pattern(convert_case::Pattern::Uppercase)
@@ -90,6 +100,52 @@ pub fn is_true_value(value: &str) -> bool {
     matches!(value, "true" | "1")
 }
 
+pub fn is_bool_value(value: &str) -> bool {
+    matches!(value, "true" | "false" | "0" | "1")
+}
+
+/// Find the candidate closest to `value`, if it is similar enough to be a likely typo
+pub fn suggest_similar<'a>(
+    value: &str,
+    candidates: impl IntoIterator<Item = &'a str>,
+) -> Option<&'a str> {
+    let mut best: Option<(&str, usize)> = None;
+    for candidate in candidates {
+        let distance = levenshtein(value, candidate);
+        let max_len = value.chars().count().max(candidate.chars().count());
+        if distance * 3 > max_len {
+            continue;
+        }
+        if best.map(|(_, v)| distance < v).unwrap_or(true) {
+            best = Some((candidate, distance));
+        }
+    }
+    best.map(|(v, _)| v)
+}
+
+fn levenshtein(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut curr = vec![i + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let cost = if ca == *cb { 0 } else { 1 };
+            curr.push((prev[j] + cost).min(prev[j + 1] + 1).min(curr[j] + 1));
+        }
+        prev = curr;
+    }
+    prev[b.len()]
+}
+
+/// Warning printed when a deprecated item is used
+pub(crate) fn render_deprecated_warning(kind: &str, name: &str, message: &str) -> String {
+    let mut output = format!("warning: {kind} `{name}` is deprecated");
+    if !message.is_empty() {
+        output.push_str(&format!(", {message}"));
+    }
+    output
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -100,4 +156,16 @@ mod tests {
         assert_eq!("FOO-BAR".to_string(), to_cobol_case("foo-bar"));
         assert_eq!("FOO1".to_string(), to_cobol_case("foo1"));
     }
+
+    #[test]
+    fn test_suggest_similar() {
+        let candidates = ["deploy", "destroy", "list"];
+        assert_eq!(suggest_similar("dploy", candidates), Some("deploy"));
+        assert_eq!(suggest_similar("lst", candidates), Some("list"));
+        assert_eq!(suggest_similar("foo", candidates), None);
+        assert_eq!(
+            suggest_similar("--verbos", ["-v", "--verbose"]),
+            Some("--verbose")
+        );
+    }
 }
@@ -0,0 +1,35 @@
+const SCRIPT: &str = r###"
+# @meta argfile
+# @option --oa
+# @option --ob*
+# @arg args*
+"###;
+
+#[test]
+fn argfile() {
+    snapshot_eval!(
+        SCRIPT,
+        [
+            vec!["prog", "@tests/argfiles/args.txt", "e"],
+            vec!["prog", "--", "@tests/argfiles/args.txt"],
+        ]
+    );
+}
+
+#[test]
+fn argfile_errors() {
+    snapshot_eval!(
+        SCRIPT,
+        [
+            vec!["prog", "@tests/argfiles/missing.txt"],
+            vec!["prog", "@tests/argfiles/malformed.txt"],
+            vec!["prog", "@tests/argfiles/loop.txt"],
+            vec!["prog", "@tests/argfiles/many.txt"],
+        ]
+    );
+}
+
+#[test]
+fn argfile_disabled() {
+    snapshot_eval!("# @arg args*", [vec!["prog", "@tests/argfiles/args.txt"]]);
+}
@@ -201,3 +201,10 @@ eval "$(argc --argc-eval "$0" "$@")"
     );
     script_file.close().unwrap();
 }
+
+#[rstest]
+fn bind_env_flags_negatable() {
+    snapshot_bind_env!(args: ["flags"], envs: {
+        "BIND_ENVS_FE": "false",
+    });
+}
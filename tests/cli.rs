@@ -1,8 +1,10 @@
 use crate::fixtures::{
-    argc_bin, get_path_env_var, locate_script, tmpdir, tmpdir_argcfiles, tmpdir_path, SCRIPT_PATHS,
+    argc_bin, get_path_env_var, locate_script, tmpdir, tmpdir_argcfiles, tmpdir_path, LOCALE,
+    SCRIPT_PATHS,
 };
 
-use assert_fs::fixture::PathChild;
+use assert_cmd::assert::OutputAssertExt;
+use assert_fs::fixture::{FileWriteStr, PathChild};
 use std::time::Instant;
 
 #[test]
@@ -81,6 +83,53 @@ fn create_cli_with_tasks() {
     insta::assert_snapshot!(contents);
 }
 
+#[test]
+fn eval_shell() {
+    argc_bin()
+        .args([
+            "--argc-eval",
+            "--shell",
+            "fish",
+            &locate_script("examples/args.sh"),
+        ])
+        .arg("cmd_arg")
+        .arg("abc")
+        .assert()
+        .stdout(predicates::str::contains("set -g argc_val abc"))
+        .success();
+    argc_bin()
+        .args([
+            "--argc-eval",
+            "--shell",
+            "tcsh",
+            &locate_script("examples/args.sh"),
+        ])
+        .assert()
+        .stderr(predicates::str::contains(
+            "Unsupported shell `tcsh` for --argc-eval",
+        ))
+        .failure();
+}
+
+#[test]
+fn eval_locale_env() {
+    argc_bin()
+        .args(["--argc-eval", &locate_script("examples/args.sh"), "-h"])
+        .env("LC_ALL", "")
+        .env("LC_MESSAGES", "de_DE.UTF-8")
+        .env("LANG", "ja_JP.UTF-8")
+        .assert()
+        .stdout(predicates::str::contains("VERWENDUNG: args <COMMAND>"))
+        .stdout(predicates::str::contains("BEFEHLE:"))
+        .success();
+    argc_bin()
+        .args(["--argc-eval", &locate_script("examples/args.sh"), "-h"])
+        .env("LC_ALL", "C")
+        .assert()
+        .stdout(predicates::str::contains("USAGE: args <COMMAND>"))
+        .success();
+}
+
 #[test]
 fn run() {
     let path_env_var = get_path_env_var();
@@ -150,6 +199,119 @@ fn mangen() {
     assert!(script.contains(".TH DEMO 1"));
 }
 
+#[test]
+fn docgen() {
+    let path = locate_script("examples/demo.sh");
+    let tmpdir = tmpdir();
+    let outdir = tmpdir.to_path_buf();
+    argc_bin()
+        .arg("--argc-docgen")
+        .arg(&path)
+        .arg(&outdir)
+        .assert()
+        .success();
+    let page = std::fs::read_to_string(outdir.join("demo-upload.md")).unwrap();
+    insta::assert_snapshot!(page);
+}
+
+#[test]
+fn docgen_html_single_page() {
+    let path = locate_script("examples/demo.sh");
+    let tmpdir = tmpdir();
+    let outdir = tmpdir.to_path_buf();
+    argc_bin()
+        .arg("--argc-docgen")
+        .arg(&path)
+        .arg(&outdir)
+        .arg("--format")
+        .arg("html")
+        .arg("--single-page")
+        .assert()
+        .success();
+    assert_eq!(std::fs::read_dir(&outdir).unwrap().count(), 1);
+    let page = std::fs::read_to_string(outdir.join("demo.html")).unwrap();
+    assert!(page.contains(r#"<h2 id="demo">demo</h2>"#));
+    assert!(page.contains(r##"<a href="#demo-upload">upload</a>"##));
+}
+
+#[test]
+fn lint_json() {
+    let tmpdir = tmpdir();
+    let script_path = tmpdir.child("script.sh");
+    script_path
+        .write_str("# @meta colour always\n# @cmd\nfoo() { :; }\n")
+        .unwrap();
+    let output = argc_bin()
+        .args(["--argc-lint"])
+        .arg(script_path.path())
+        .args(["--format", "json"])
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(0));
+    insta::assert_snapshot!(String::from_utf8_lossy(&output.stdout));
+}
+
+#[test]
+fn fmt_check() {
+    let tmpdir = tmpdir();
+    let script_path = tmpdir.child("script.sh");
+    script_path
+        .write_str("#!/usr/bin/env bash\r\nmain() { :; }\n")
+        .unwrap();
+    argc_bin()
+        .args(["--argc-fmt"])
+        .arg(script_path.path())
+        .arg("--check")
+        .assert()
+        .stdout(predicates::str::ends_with(":2: not formatted\n"))
+        .code(1);
+    argc_bin()
+        .args(["--argc-fmt"])
+        .arg(script_path.path())
+        .assert()
+        .success();
+    argc_bin()
+        .args(["--argc-fmt"])
+        .arg(script_path.path())
+        .arg("--check")
+        .assert()
+        .stdout("")
+        .success();
+}
+
+#[test]
+fn lint_include() {
+    let tmpdir = tmpdir();
+    tmpdir
+        .child("Argcfile.sh")
+        .write_str(
+            r#"# @meta help-template docs/help.txt
+# @include tasks/build.sh
+# @meta colour always
+"#,
+        )
+        .unwrap();
+    tmpdir
+        .child("tasks/build.sh")
+        .write_str("# @cmd\n# @option --mode[`_choice_mode`]\nbuild() { :; }\n\n# @cmd\n")
+        .unwrap();
+    tmpdir
+        .child("docs/help.txt")
+        .write_str("{usage}\n")
+        .unwrap();
+    argc_bin()
+        .current_dir(tmpdir.path())
+        .args(["--argc-lint", "Argcfile.sh"])
+        .assert()
+        .stdout(
+            "tasks/build.sh:2:19: error: function '_choice_mode' is missing [missing-fn]
+tasks/build.sh:5:3: error: @cmd is not followed by a function [cmd-without-fn]
+Argcfile.sh:3:9: warning: @meta 'colour' is unknown [unknown-meta]
+",
+        )
+        .code(1);
+}
+
 #[test]
 fn completions() {
     argc_bin()
@@ -159,6 +321,85 @@ fn completions() {
         .success();
 }
 
+#[test]
+fn build_completions() {
+    let path = locate_script("examples/demo.sh");
+    let output = argc_bin()
+        .arg("--argc-build-completions")
+        .arg("bash")
+        .arg(&path)
+        .output()
+        .unwrap();
+    insta::assert_snapshot!(String::from_utf8(output.stdout).unwrap());
+}
+
+#[test]
+fn build_completions_hidden() {
+    let tmpdir = tmpdir();
+    let script = tmpdir.child("prog.sh");
+    script
+        .write_str(
+            r#"# @flag --verbose
+# @flag --secret
+# @meta hidden
+# @option --level[low|high]
+# @meta hidden
+
+# @cmd
+# @arg target[a|b]
+# @arg extra[x|y]
+# @meta hidden
+build() { :; }
+
+# @cmd
+# @meta hidden
+internal() { :; }
+"#,
+        )
+        .unwrap();
+    let output = argc_bin()
+        .args(["--argc-build-completions", "bash"])
+        .arg(script.path())
+        .output()
+        .unwrap();
+    insta::assert_snapshot!(String::from_utf8(output.stdout).unwrap());
+}
+
+#[cfg(not(windows))]
+#[test]
+fn build_completions_bash_run() {
+    let path = locate_script("examples/args.sh");
+    let path_env_var = get_path_env_var();
+    let output = argc_bin()
+        .args(["--argc-build-completions", "bash", &path, "args"])
+        .output()
+        .unwrap();
+    let tmpdir = tmpdir();
+    let completion_path = tmpdir.child("args.bash");
+    std::fs::write(&completion_path, output.stdout).unwrap();
+    let script = format!(
+        r#"
+source '{}'
+args() {{ '{path}' "$@"; }}
+comp() {{ COMP_LINE="$1"; COMP_POINT=${{#1}}; COMPREPLY=(); _args_completions; echo "${{COMPREPLY[*]}}"; }}
+comp "args cmd_arg_with_ch"
+comp "args cmd_arg_with_choices "
+comp "args cmd_arg_with_choice_fn d"
+"#,
+        completion_path.display()
+    );
+    std::process::Command::new("bash")
+        .env("LC_ALL", LOCALE)
+        .arg("-c")
+        .arg(script)
+        .env("PATH", path_env_var)
+        .assert()
+        .stdout(
+            "cmd_arg_with_choices cmd_arg_with_choices_and_default cmd_arg_with_choice_fn cmd_arg_with_choice_fn_and_skip_check\nx y z\ndef\n",
+        )
+        .success();
+}
+
 #[test]
 fn compgen_args() {
     let path = locate_script("examples/args.sh");
@@ -221,6 +462,22 @@ fn export() {
     insta::assert_snapshot!(stdout);
 }
 
+#[test]
+fn parse_json() {
+    argc_bin()
+        .args(["--argc-parse-json", &locate_script("examples/args.sh")])
+        .args(["cmd_arg", "abc"])
+        .assert()
+        .stdout(predicates::str::contains(r#""val": "abc""#))
+        .success();
+    argc_bin()
+        .args(["--argc-parse-json", &locate_script("examples/args.sh")])
+        .arg("cmd_required_arg")
+        .assert()
+        .stdout(predicates::str::contains(r#""exit": 1"#))
+        .failure();
+}
+
 #[test]
 fn parallel() {
     let tmpdir = tmpdir_argcfiles();
@@ -230,6 +487,7 @@ fn parallel() {
     let output = argc_bin()
         .current_dir(tmpdir_path(&tmpdir, "dir1"))
         .env("PATH", path_env_var)
+        .env("ARGC_PARALLEL_JOBS", "2")
         .args(args)
         .output()
         .unwrap();
@@ -254,6 +512,213 @@ STDERR:
     insta::assert_snapshot!(text);
 }
 
+#[test]
+fn parallel_jobs() {
+    let tmpdir = tmpdir();
+    let script_path = tmpdir.child("Argcfile.sh");
+    std::fs::write(
+        &script_path,
+        r#"
+# @cmd
+greet() { echo "hello $1"; echo "bye $1" >&2; }
+eval "$(argc --argc-eval "$0" "$@")"
+"#,
+    )
+    .unwrap();
+    let output = argc_bin()
+        .env("PATH", get_path_env_var())
+        .args(["--argc-parallel", "-j", "1"])
+        .arg(script_path.path())
+        .args(["greet", "a", ":::", "greet", "b"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "[job1] hello a\n[job2] hello b\n"
+    );
+    assert_eq!(
+        group_parallel_output(&output.stderr),
+        "[job1] bye a\n[job2] bye b\nSummary:\n  [job1] greet a  exit 0\n  [job2] greet b  exit 0"
+    );
+}
+
+#[test]
+fn parallel_fail_fast() {
+    let tmpdir = tmpdir();
+    let script_path = tmpdir.child("Argcfile.sh");
+    std::fs::write(
+        &script_path,
+        r#"
+# @cmd
+ok() { sleep "$1"; echo ok; }
+# @cmd
+bad() { echo bad; return 3; }
+eval "$(argc --argc-eval "$0" "$@")"
+"#,
+    )
+    .unwrap();
+    let start_time = Instant::now();
+    let output = argc_bin()
+        .env("PATH", get_path_env_var())
+        .args(["--argc-parallel", "--fail-fast", "-j", "2"])
+        .arg(script_path.path())
+        .args(["ok", "5", ":::", "bad", ":::", "ok", "0"])
+        .output()
+        .unwrap();
+    assert!(start_time.elapsed().as_millis() < 4000);
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "[job2] bad\n");
+    let stderr = group_parallel_output(&output.stderr);
+    insta::assert_snapshot!(stderr);
+}
+
+#[test]
+fn uptodate() {
+    let tmpdir = tmpdir();
+    tmpdir.child("src/a/x.rs").write_str("one").unwrap();
+    tmpdir.child("src/y.rs").write_str("two").unwrap();
+    tmpdir
+        .child("Argcfile.sh")
+        .write_str(
+            r#"
+# @cmd
+# @flag --release
+# @sources src/**/*.rs
+# @generates target/app
+build() { echo building${argc_release:+ release}; mkdir -p target; cat src/a/x.rs src/y.rs > target/app; }
+eval "$(argc --argc-eval "$0" "$@")"
+"#,
+        )
+        .unwrap();
+    let path_env_var = get_path_env_var();
+    let run = |args: &[&str]| {
+        let output = argc_bin()
+            .current_dir(tmpdir.path())
+            .env("PATH", path_env_var.clone())
+            .args(args)
+            .output()
+            .unwrap();
+        String::from_utf8_lossy(&output.stdout).to_string()
+    };
+    assert_eq!(run(&["build"]), "building\n");
+    assert_eq!(run(&["build"]), "build is up to date\n");
+    assert!(tmpdir.child(".argc-cache").exists());
+    tmpdir.child("src/a/x.rs").write_str("three").unwrap();
+    assert_eq!(run(&["build"]), "building\n");
+    assert_eq!(run(&["build"]), "build is up to date\n");
+    assert_eq!(run(&["--argc-force", "build"]), "building\n");
+    std::fs::remove_file(tmpdir.child("target/app").path()).unwrap();
+    assert_eq!(run(&["build"]), "building\n");
+    assert_eq!(run(&["build"]), "build is up to date\n");
+    assert_eq!(run(&["build", "--release"]), "building release\n");
+    assert_eq!(run(&["build", "--release"]), "build is up to date\n");
+    assert_eq!(run(&["build"]), "building\n");
+}
+
+#[test]
+fn include() {
+    let tmpdir = tmpdir();
+    tmpdir
+        .child("Argcfile.sh")
+        .write_str(
+            r#"
+# @cmd
+hi() { echo hi; }
+
+# @include tasks/build.sh
+eval "$(argc --argc-eval "$0" "$@")"
+"#,
+        )
+        .unwrap();
+    tmpdir
+        .child("tasks/build.sh")
+        .write_str(
+            r#"# @cmd
+# @option --mode[`_choice_mode`]
+build() { echo "build $argc_mode"; helper; }
+# @include util.sh
+"#,
+        )
+        .unwrap();
+    tmpdir
+        .child("tasks/util.sh")
+        .write_str("helper() { echo helper; }\n_choice_mode() { echo dev; echo prod; }\n")
+        .unwrap();
+    let path_env_var = get_path_env_var();
+    let run = |args: &[&str]| {
+        let output = argc_bin()
+            .current_dir(tmpdir.path())
+            .env("PATH", path_env_var.clone())
+            .args(args)
+            .output()
+            .unwrap();
+        String::from_utf8_lossy(&output.stdout).to_string()
+    };
+    assert_eq!(run(&["build", "--mode", "dev"]), "build dev\nhelper\n");
+    assert_eq!(
+        run(&[
+            "--argc-compgen",
+            "bash",
+            "Argcfile.sh",
+            "argc",
+            "build",
+            "--mode",
+            ""
+        ]),
+        "dev \nprod \n"
+    );
+    let built = tmpdir.child("built.sh");
+    run(&[
+        "--argc-build",
+        "Argcfile.sh",
+        built.path().to_str().unwrap(),
+    ]);
+    let output = std::process::Command::new("bash")
+        .env("LC_ALL", LOCALE)
+        .arg(built.path())
+        .args(["build", "--mode", "prod"])
+        .output()
+        .unwrap();
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "build prod\nhelper\n"
+    );
+
+    tmpdir
+        .child("tasks/util.sh")
+        .write_str("helper() { :; }\n# @foo\n")
+        .unwrap();
+    let output = argc_bin()
+        .current_dir(tmpdir.path())
+        .args(["--argc-export", "Argcfile.sh"])
+        .output()
+        .unwrap();
+    assert!(String::from_utf8_lossy(&output.stderr)
+        .starts_with("@foo(tasks/util.sh:2) is unknown tag\n"));
+}
+
+/// Group streamed lines by job, dropping durations from the summary
+fn group_parallel_output(output: &[u8]) -> String {
+    let output = String::from_utf8_lossy(output);
+    let mut lines: Vec<String> = output
+        .lines()
+        .map(|line| match line.rsplit_once("  ") {
+            Some((left, right)) if line.starts_with("  [job") && right.ends_with('s') => {
+                left.to_string()
+            }
+            _ => line.to_string(),
+        })
+        .collect();
+    lines.sort_by_key(|line| {
+        line.strip_prefix("[job")
+            .and_then(|v| v.split_once(']'))
+            .map(|(v, _)| v.to_string())
+            .unwrap_or_else(|| "~".into())
+    });
+    lines.join("\n")
+}
+
 #[test]
 fn script_path() {
     let tmpdir = tmpdir_argcfiles();
@@ -301,3 +766,95 @@ fn run_argcfile() {
         .stdout(predicates::str::contains("dir1/subdir1/Argcfile.sh"))
         .success();
 }
+
+#[test]
+fn mangen_example() {
+    let tmpdir = tmpdir();
+    let path = tmpdir.join("prog.sh");
+    std::fs::write(
+        &path,
+        "# @example prog --foo  Run with foo\n# @flag --foo\n",
+    )
+    .unwrap();
+    let outdir = tmpdir.join("man");
+    argc_bin()
+        .arg("--argc-mangen")
+        .arg(&path)
+        .arg(&outdir)
+        .assert()
+        .success();
+    let page = std::fs::read_to_string(outdir.join("prog.1")).unwrap();
+    assert!(page.contains(".SH EXAMPLES\n.TP\n\\fBprog \\-\\-foo\\fR\nRun with foo\n"));
+}
+
+#[test]
+fn mangen_group() {
+    let tmpdir = tmpdir();
+    let path = tmpdir.join("prog.sh");
+    std::fs::write(&path, "# @flag --fa\n# @group Network\n# @option --host\n").unwrap();
+    let outdir = tmpdir.join("man");
+    argc_bin()
+        .arg("--argc-mangen")
+        .arg(&path)
+        .arg(&outdir)
+        .assert()
+        .success();
+    let page = std::fs::read_to_string(outdir.join("prog.1")).unwrap();
+    assert!(page.contains(".SS Network\n.TP\n\\fB\\-\\-host\\fR"));
+}
+
+#[test]
+fn help_template_file() {
+    let tmpdir = tmpdir();
+    tmpdir
+        .child("prog.sh")
+        .write_str(
+            r#"# @describe Demo app
+# @meta help-template docs/help.txt
+# @flag --verbose  Be verbose
+eval "$(argc --argc-eval "$0" "$@")"
+"#,
+        )
+        .unwrap();
+    tmpdir
+        .child("docs/help.txt")
+        .write_str("{about}\n\nUsage: {usage}\n\n@flags:\n{options}\n")
+        .unwrap();
+    let expected = "Demo app\n\nUsage: prog [OPTIONS]\n\n@flags:\n      --verbose  Be verbose\n  -h, --help     Print help\n  -V, --version  Print version\n";
+    let path_env_var = get_path_env_var();
+    let output = std::process::Command::new("bash")
+        .env("LC_ALL", LOCALE)
+        .current_dir(tmpdir.path())
+        .env("PATH", path_env_var)
+        .args(["prog.sh", "-h"])
+        .output()
+        .unwrap();
+    assert_eq!(String::from_utf8_lossy(&output.stderr), expected);
+
+    let built = tmpdir.child("built.sh");
+    argc_bin()
+        .current_dir(tmpdir.path())
+        .args(["--argc-build", "prog.sh", built.path().to_str().unwrap()])
+        .assert()
+        .success();
+    let output = std::process::Command::new("bash")
+        .env("LC_ALL", LOCALE)
+        .arg(built.path())
+        .arg("-h")
+        .output()
+        .unwrap();
+    assert_eq!(String::from_utf8_lossy(&output.stdout), expected);
+
+    tmpdir
+        .child("prog.sh")
+        .write_str("# @meta help-template missing.txt\n")
+        .unwrap();
+    argc_bin()
+        .current_dir(tmpdir.path())
+        .args(["--argc-export", "prog.sh"])
+        .assert()
+        .failure()
+        .stderr(predicates::str::starts_with(
+            "@meta(line 1) failed to read help template 'missing.txt'",
+        ));
+}
@@ -0,0 +1,19 @@
+use rstest::rstest;
+
+#[rstest]
+fn color_help() {
+    snapshot_env!(
+        args: ["options", "-h"],
+        envs: {"CLICOLOR_FORCE": "1"}
+        script_file: "examples/options.sh"
+    );
+}
+
+#[rstest]
+fn color_help_no_color() {
+    snapshot_env!(
+        args: ["options", "-h"],
+        envs: {"CLICOLOR_FORCE": "1", "NO_COLOR": "1"}
+        script_file: "examples/options.sh"
+    );
+}
@@ -77,6 +77,23 @@ fn shorts() {
     );
 }
 
+#[test]
+fn flag_negatable() {
+    let script = r###"
+# @flag -c --[no-]cache  Use cache
+# @flag    --fa
+"###;
+
+    snapshot_compgen!(
+        script,
+        [
+            vec!["prog", "--"],
+            vec!["prog", "--no"],
+            vec!["prog", "--no-cache", "--"],
+        ]
+    );
+}
+
 #[test]
 fn symbol() {
     let script = r###"
@@ -292,6 +309,23 @@ _choice_fn() {
     snapshot_compgen!(script, [vec!["prog", "--oa", ""], vec!["prog", "--oa="],]);
 }
 
+#[test]
+fn choice_alias() {
+    let script = r#"
+# @option --oa[~prod|production=prod|dev]
+# @arg v1[local|lo=local|remote]
+"#;
+
+    snapshot_compgen!(
+        script,
+        [
+            vec!["prog", "--oa", ""],
+            vec!["prog", "--oa", "p"],
+            vec!["prog", ""]
+        ]
+    );
+}
+
 #[test]
 fn choice_check_vars() {
     let script = r###"
@@ -1185,3 +1219,21 @@ choice_val() {
         );
     }
 }
+
+#[test]
+fn hidden() {
+    let script = r###"
+# @flag --fa
+# @flag --fb
+# @meta hidden
+
+# @cmd
+foo() { :; }
+
+# @cmd
+# @meta hidden
+bar() { :; }
+"###;
+
+    snapshot_compgen!(script, [vec!["prog", ""], vec!["prog", "--"]]);
+}
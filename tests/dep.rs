@@ -0,0 +1,53 @@
+#[test]
+fn dep_diamond() {
+    let script = r###"
+# @cmd
+# @dep build,lint
+release() { echo release; }
+
+# @cmd
+# @dep gen
+build() { echo build; }
+
+# @cmd
+# @dep gen
+lint() { echo lint; }
+
+# @cmd
+gen() { echo gen; }
+"###;
+    snapshot_multi!(script, [vec!["prog", "release"], vec!["prog", "build"]]);
+}
+
+#[test]
+fn dep_with_hook() {
+    let script = r###"
+_argc_before() { echo before; }
+_argc_after() { echo after; }
+
+# @cmd
+# @dep bar
+# @arg value
+foo() { echo foo "$@"; }
+
+# @cmd
+bar() { echo bar "$@"; }
+"###;
+    snapshot_multi!(script, [vec!["prog", "foo", "abc"]]);
+}
+
+#[test]
+fn dep_nested() {
+    let script = r###"
+# @cmd
+test() { :; }
+
+# @cmd
+test::unit() { echo test::unit; }
+
+# @cmd
+# @dep test::unit
+test::e2e() { echo test::e2e; }
+"###;
+    snapshot_multi!(script, [vec!["prog", "test", "e2e"]]);
+}
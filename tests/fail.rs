@@ -215,3 +215,76 @@ fn invalid_envs() {
         "@env(line 2) is invalid, can only be a single value"
     );
 }
+
+#[test]
+fn conflicts_unknown_param() {
+    let script = r###"
+# @flag --json
+# @conflicts json,table
+"###;
+    fail!(
+        script,
+        &["prog"],
+        "@conflicts(line 3) references unknown flag or option 'table'"
+    );
+}
+
+#[test]
+fn invalid_value_type() {
+    let script = r###"
+# @option --port{integer}
+"###;
+    fail!(script, &["prog"], "syntax error at line 2");
+}
+
+#[test]
+fn dep_unknown() {
+    let script = r###"
+# @cmd
+# @dep build
+release() { :; }
+"###;
+    fail!(
+        script,
+        &["prog"],
+        "@dep(line 3) references unknown recipe 'build'"
+    );
+}
+
+#[test]
+fn dep_circular() {
+    let script = r###"
+# @cmd
+# @dep build
+release() { :; }
+
+# @cmd
+# @dep lint
+build() { :; }
+
+# @cmd
+# @dep build
+lint() { :; }
+"###;
+    fail!(
+        script,
+        &["prog"],
+        "@dep(line 11) has circular dependency 'build -> lint -> build'"
+    );
+}
+
+#[test]
+fn unsupported_locale() {
+    let script = r###"
+# @meta locale xx
+"###;
+    fail!(script, &["prog"], "@meta(line 2) unsupported locale 'xx'");
+}
+
+#[test]
+fn unknown_message() {
+    let script = r###"
+# @meta message usages Usage:
+"###;
+    fail!(script, &["prog"], "@meta(line 2) unknown message 'usages'");
+}
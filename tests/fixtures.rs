@@ -20,8 +20,58 @@ pub const SCRIPT_PATHS: [&str; 8] = [
     "dir6/ARGCFILE",
 ];
 
+/// Locale of the tests, argc falls back to English for it while bash still prints UTF-8 as is
+pub const LOCALE: &str = "C.UTF-8";
+
+/// The native runtime with `LC_ALL` pinned to `LOCALE`
+#[derive(Debug, Clone, Copy)]
+pub struct TestRuntime;
+
+macro_rules! native {
+    ($($name:ident($($arg:ident: $ty:ty),*) -> $output:ty;)*) => {
+        $(fn $name(&self, $($arg: $ty),*) -> $output {
+            argc::NativeRuntime.$name($($arg),*)
+        })*
+    };
+}
+
+impl Runtime for TestRuntime {
+    native! {
+        os() -> String;
+        shell_path() -> anyhow::Result<String>;
+        bash_path() -> Option<String>;
+        exec_bash_functions(script_file: &str, functions: &[&str], args: &[String], envs: HashMap<String, String>) -> Option<Vec<String>>;
+        current_exe() -> Option<String>;
+        current_dir() -> Option<String>;
+        which(name: &str) -> Option<String>;
+        exist_path(path: &str) -> bool;
+        parent_path(path: &str) -> Option<String>;
+        join_path(path: &str, parts: &[&str]) -> String;
+        chdir(cwd: &str, cd: &str) -> Option<String>;
+        metadata(path: &str) -> Option<(bool, bool, bool)>;
+        read_dir(path: &str) -> Option<Vec<String>>;
+        read_to_string(path: &str) -> Option<String>;
+        is_bash_regex(pattern: &str) -> Option<bool>;
+    }
+
+    fn env_vars(&self) -> HashMap<String, String> {
+        let mut env_vars = argc::NativeRuntime.env_vars();
+        env_vars.insert("LC_ALL".into(), LOCALE.into());
+        env_vars
+    }
+
+    fn env_var(&self, name: &str) -> Option<String> {
+        match name {
+            "LC_ALL" => Some(LOCALE.into()),
+            _ => argc::NativeRuntime.env_var(name),
+        }
+    }
+}
+
 pub fn argc_bin() -> assert_cmd::Command {
-    assert_cmd::Command::new(assert_cmd::cargo::cargo_bin!())
+    let mut cmd = assert_cmd::Command::new(assert_cmd::cargo::cargo_bin!());
+    cmd.env("LC_ALL", LOCALE);
+    cmd
 }
 
 pub fn locate_script(script_path: &str) -> String {
@@ -123,6 +173,7 @@ pub fn run_script<T: AsRef<Path>>(
         .arg(script_path.as_ref())
         .args(args)
         .env("PATH", path_env_var.clone())
+        .env("LC_ALL", LOCALE)
         .envs(envs)
         .output()
         .unwrap();
@@ -0,0 +1,40 @@
+use crate::SCRIPT_OPTIONS;
+
+#[test]
+fn fmt_align() {
+    let script = r###"#!/usr/bin/env bash
+#@describe   demo
+# @option    --oa
+# @option -b --ob                   short
+#@option -c    short only
+# @option --ona <PATH>   value notation
+# @flag -f --force   Force it
+#   more about force
+# @option    --oeb $OEB <PATH>      bind-named-env
+# @arg target! <FILE> File to upload
+# @env FOO=bar  An env
+
+##  @cmd   Build
+# @option --mode[=dev|prod] Mode
+# @flag -q --quiet
+build() { :; }
+"###;
+    let output = argc::fmt(script).unwrap();
+    assert_eq!(argc::fmt(&output).unwrap(), output);
+    insta::assert_snapshot!(output);
+}
+
+#[test]
+fn fmt_spec() {
+    let output = argc::fmt(SCRIPT_OPTIONS).unwrap();
+    assert_eq!(argc::fmt(&output).unwrap(), output);
+}
+
+#[test]
+fn fmt_crlf() {
+    let script = "# @flag -a  A\r\n# @option --bb <PATH>\r\nmain() { :; }\r\n";
+    assert_eq!(
+        argc::fmt(script).unwrap(),
+        "# @flag   -a             A\r\n# @option    --bb <PATH>\r\nmain() { :; }\r\n"
+    );
+}
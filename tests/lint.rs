@@ -0,0 +1,100 @@
+use assert_fs::prelude::*;
+
+fn lint_output(source: &str) -> String {
+    argc::lint(source)
+        .iter()
+        .map(|v| v.to_string())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[test]
+fn lint_problems() {
+    let script = r###"
+# @meta version 1.0
+# @meta colour always
+# @foo bar
+# @option --mode[`_choice_mode`]
+# @arg target=`_default_target`
+
+# @cmd
+# @cmd Build
+# @alias b,t
+build() { :; }
+
+# @cmd
+# @alias b
+test() { :; }
+
+# @cmd
+deploy::prod() { :; }
+
+# @cmd
+"###;
+    insta::assert_snapshot!(lint_output(script));
+}
+
+#[test]
+fn lint_fallback() {
+    let script = r###"
+# @cmd
+# @conflicts --aaa,--bbb
+lint() { :; }
+"###;
+    assert_eq!(
+        lint_output(script),
+        "3:1: error: @conflicts(line 3) references unknown flag or option '--aaa' [invalid]"
+    );
+}
+
+#[test]
+fn lint_clean() {
+    assert!(argc::lint(crate::SCRIPT_ARGS).is_empty());
+    assert!(argc::lint(crate::SCRIPT_OPTIONS).is_empty());
+}
+
+#[test]
+fn lint_include() {
+    let tmpdir = crate::fixtures::tmpdir();
+    tmpdir
+        .child("tasks.sh")
+        .write_str("_choice_mode() { :; }\n")
+        .unwrap();
+    let script = r###"
+# @option --mode[`_choice_mode`]
+# @option --env[`_choice_env`]
+# @include tasks.sh
+"###;
+    let script_path = tmpdir.path().join("Argcfile.sh");
+    let output: Vec<String> = argc::lint_with_includes(script, &script_path.to_string_lossy())
+        .unwrap()
+        .iter()
+        .map(|v| v.to_string())
+        .collect();
+    assert_eq!(
+        output,
+        ["3:18: error: function '_choice_env' is missing [missing-fn]"]
+    );
+}
+
+#[test]
+fn lint_example() {
+    let script = r###"
+# @example prog build --release
+
+# @cmd
+# @flag --release
+# @example prog build --debug
+build() { :; }
+
+# @cmd
+# @arg env![dev|prod]
+# @env TOKEN!
+# @example prog deploy staging
+# @example prog build
+# @example prog deploy "dev
+# @example prog deploy dev
+deploy() { :; }
+"###;
+    insta::assert_snapshot!(lint_output(script));
+}
@@ -0,0 +1,168 @@
+use assert_fs::prelude::*;
+use serde_json::{json, Value};
+
+const SCRIPT: &str = r###"# @meta dotenv
+# @option --mode[`_choice_mode`]
+# @foo
+
+# @cmd Build it
+# @alias b
+build() { :; }
+
+# @cmd Build docs
+build::docs() { :; }
+
+_choice_mode() { :; }
+"###;
+
+fn run_lsp(requests: &[Value]) -> Vec<Value> {
+    run_lsp_with("file:///Argcfile.sh", SCRIPT, requests)
+}
+
+fn run_lsp_with(uri: &str, text: &str, requests: &[Value]) -> Vec<Value> {
+    let mut messages = vec![json!({
+        "jsonrpc": "2.0",
+        "method": "textDocument/didOpen",
+        "params": { "textDocument": { "uri": uri, "text": text } },
+    })];
+    for (i, request) in requests.iter().enumerate() {
+        let mut request = request.clone();
+        request["jsonrpc"] = json!("2.0");
+        request["id"] = json!(i + 1);
+        request["params"]["textDocument"] = json!({ "uri": uri });
+        messages.push(request);
+    }
+    messages.push(json!({ "jsonrpc": "2.0", "method": "exit" }));
+    let input: String = messages
+        .iter()
+        .map(|v| {
+            let body = v.to_string();
+            format!("Content-Length: {}\r\n\r\n{}", body.len(), body)
+        })
+        .collect();
+    let mut output = vec![];
+    argc::serve_lsp(input.as_bytes(), &mut output).unwrap();
+    let output = String::from_utf8(output).unwrap();
+    let mut values = vec![];
+    let mut rest = output.as_str();
+    while let Some((header, body)) = rest.split_once("\r\n\r\n") {
+        let len: usize = header["Content-Length: ".len()..].parse().unwrap();
+        values.push(serde_json::from_str(&body[..len]).unwrap());
+        rest = &body[len..];
+    }
+    values
+}
+
+fn position_request(method: &str, line: usize, character: usize) -> Value {
+    json!({
+        "method": method,
+        "params": { "position": { "line": line, "character": character } },
+    })
+}
+
+fn results(requests: &[Value]) -> String {
+    let values: Vec<Value> = run_lsp(requests)
+        .into_iter()
+        .skip(1)
+        .map(|mut v| v["result"].take())
+        .collect();
+    serde_json::to_string_pretty(&values).unwrap()
+}
+
+#[test]
+fn lsp_diagnostics() {
+    let values = run_lsp(&[]);
+    insta::assert_snapshot!(serde_json::to_string_pretty(&values).unwrap());
+}
+
+#[test]
+fn lsp_diagnostics_include() {
+    let tmpdir = crate::fixtures::tmpdir();
+    tmpdir
+        .child("tasks.sh")
+        .write_str(
+            "# @cmd\n# @option --foo[`_choice_mode`]\nbuild() { :; }\n\n_choice_env() { :; }\n",
+        )
+        .unwrap();
+    let script =
+        "# @option --env[`_choice_env`]\n# @option --mode[`_choice_mode`]\n# @include tasks.sh\n";
+    let uri = format!("file://{}", tmpdir.path().join("Argcfile.sh").display());
+    let values = run_lsp_with(&uri, script, &[]);
+    let diagnostics = &values[0]["params"]["diagnostics"];
+    assert_eq!(diagnostics.as_array().unwrap().len(), 1);
+    assert_eq!(diagnostics[0]["code"], "missing-fn");
+    assert_eq!(diagnostics[0]["range"]["start"]["line"], 1);
+}
+
+#[test]
+fn lsp_hover() {
+    insta::assert_snapshot!(results(&[
+        position_request("textDocument/hover", 1, 4),
+        position_request("textDocument/hover", 0, 10),
+        position_request("textDocument/hover", 1, 20),
+    ]));
+}
+
+#[test]
+fn lsp_completion() {
+    let values = run_lsp(&[
+        position_request("textDocument/completion", 1, 3),
+        position_request("textDocument/completion", 0, 10),
+        position_request("textDocument/completion", 1, 20),
+    ]);
+    let labels: Vec<Vec<&str>> = values[1..]
+        .iter()
+        .map(|v| {
+            v["result"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|v| v["label"].as_str().unwrap())
+                .collect()
+        })
+        .collect();
+    insta::assert_snapshot!(format!("{labels:#?}"));
+}
+
+#[test]
+fn lsp_definition() {
+    insta::assert_snapshot!(results(&[
+        position_request("textDocument/definition", 1, 20),
+        position_request("textDocument/definition", 5, 9),
+    ]));
+}
+
+#[test]
+fn lsp_document_symbol() {
+    insta::assert_snapshot!(results(&[json!({
+        "method": "textDocument/documentSymbol",
+        "params": {},
+    })]));
+}
+
+#[test]
+fn lsp_unknown_method() {
+    let values = run_lsp(&[json!({ "method": "foo/bar", "params": {} })]);
+    assert_eq!(values[1]["error"]["code"], -32601);
+}
+
+#[test]
+fn lsp_parse_error() {
+    let request = json!({ "jsonrpc": "2.0", "id": 1, "method": "shutdown" }).to_string();
+    let input = format!(
+        "Content-Length: 9\r\n\r\n{{invalid}}Content-Length: {}\r\n\r\n{request}",
+        request.len()
+    );
+    let mut output = vec![];
+    argc::serve_lsp(input.as_bytes(), &mut output).unwrap();
+    let output = String::from_utf8(output).unwrap();
+    let responses: Vec<Value> = output
+        .split("Content-Length: ")
+        .filter_map(|v| serde_json::from_str(v.split_once("\r\n\r\n")?.1).ok())
+        .collect();
+    assert_eq!(responses.len(), 2);
+    assert_eq!(responses[0]["error"]["code"], -32700);
+    assert_eq!(responses[0]["id"], Value::Null);
+    assert_eq!(responses[1]["id"], 1);
+    assert_eq!(responses[1]["result"], Value::Null);
+}
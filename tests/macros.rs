@@ -20,7 +20,8 @@ macro_rules! fail {
         $err:expr
     ) => {
         let args: Vec<String> = $args.iter().map(|v| v.to_string()).collect();
-        let err = argc::eval(argc::NativeRuntime, $source, &args, None, None).unwrap_err();
+        let err =
+            argc::eval($crate::fixtures::TestRuntime, $source, &args, None, None).unwrap_err();
         assert_eq!(err.to_string().as_str(), $err);
     };
 }
@@ -40,7 +41,8 @@ macro_rules! snapshot {
 		$width:expr
     ) => {
         let args: Vec<String> = $args.iter().map(|v| v.to_string()).collect();
-        let values = argc::eval(argc::NativeRuntime, $source, &args, $path, $width).unwrap();
+        let values =
+            argc::eval($crate::fixtures::TestRuntime, $source, &args, $path, $width).unwrap();
         let shell_code = argc::ArgcValue::to_bash(&values);
         let build_script_dir = $crate::fixtures::tmpdir();
         let build_script_path = $crate::fixtures::build_script(&build_script_dir, $source, "prog");
@@ -62,6 +64,34 @@ macro_rules! snapshot {
     };
 }
 
+#[macro_export]
+macro_rules! snapshot_eval {
+    (
+		$source:expr,
+		$matrix:expr
+	) => {
+        let mut data = String::new();
+        for args in $matrix.iter() {
+            let args: Vec<String> = args.iter().map(|v| v.to_string()).collect();
+            let values =
+                argc::eval($crate::fixtures::TestRuntime, $source, &args, None, None).unwrap();
+            let shell_code = argc::ArgcValue::to_bash(&values);
+            let piece = format!(
+                r###"************ RUN ************
+{}
+
+# OUTPUT
+{}
+"###,
+                args.join(" "),
+                shell_code,
+            );
+            data.push_str(&piece);
+        }
+        insta::assert_snapshot!(data);
+    };
+}
+
 #[macro_export]
 macro_rules! snapshot_multi {
     (
@@ -78,7 +108,7 @@ macro_rules! snapshot_multi {
         for args in $matrix.iter() {
             let args: Vec<String> = args.iter().map(|v| v.to_string()).collect();
             let values = argc::eval(
-                argc::NativeRuntime,
+                $crate::fixtures::TestRuntime,
                 &script_content,
                 &args,
                 Some(script_path.as_str()),
@@ -107,6 +137,58 @@ macro_rules! snapshot_multi {
     };
 }
 
+#[macro_export]
+macro_rules! snapshot_shells {
+    (
+		$source:expr,
+		$args:expr
+	) => {
+        let args: Vec<String> = $args.iter().map(|v| v.to_string()).collect();
+        let values = argc::eval($crate::fixtures::TestRuntime, $source, &args, None, None).unwrap();
+        let data = format!(
+            r###"RUN
+{}
+
+# ZSH
+{}
+
+# FISH
+{}
+
+# POWERSHELL
+{}
+
+# NUSHELL
+{}
+"###,
+            args.join(" "),
+            argc::ArgcValue::to_zsh(&values),
+            argc::ArgcValue::to_fish(&values),
+            argc::ArgcValue::to_powershell(&values),
+            argc::ArgcValue::to_nushell(&values),
+        );
+        insta::assert_snapshot!(data);
+    };
+}
+
+#[macro_export]
+macro_rules! snapshot_json {
+    (
+		$source:expr,
+		$args:expr
+	) => {
+        let args: Vec<String> = $args.iter().map(|v| v.to_string()).collect();
+        let value =
+            argc::eval_json($crate::fixtures::TestRuntime, $source, &args, None, None).unwrap();
+        let data = format!(
+            "RUN\n{}\n\n# OUTPUT\n{}\n",
+            args.join(" "),
+            serde_json::to_string_pretty(&value).unwrap()
+        );
+        insta::assert_snapshot!(data);
+    };
+}
+
 #[macro_export]
 macro_rules! snapshot_compgen {
     (
@@ -120,7 +202,7 @@ macro_rules! snapshot_compgen {
         for args in $matrix.iter() {
             let args: Vec<String> = args.iter().map(|v| v.to_string()).collect();
             let words = match argc::compgen(
-                argc::NativeRuntime,
+                $crate::fixtures::TestRuntime,
                 $shell,
                 &script_path,
                 &script_content,
@@ -163,7 +245,7 @@ macro_rules! snapshot_compgen_shells {
         let args: Vec<String> = $args.iter().map(|v| v.to_string()).collect();
         for shell in argc::Shell::list() {
             let words = match argc::compgen(
-                argc::NativeRuntime,
+                $crate::fixtures::TestRuntime,
                 shell,
                 &script_path,
                 &script_content,
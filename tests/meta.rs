@@ -21,3 +21,35 @@ fn binname() {
 "###;
     snapshot!(script, &["prog", "-h"]);
 }
+
+const HIDDEN_DEPRECATED_SCRIPT: &str = r###"
+# @flag --fa
+# @flag --fb
+# @meta hidden
+# @option --oa
+# @meta deprecated use --ob instead
+# @option --ob
+
+# @cmd
+# @meta hidden
+foo() { :; }
+
+# @cmd
+# @meta deprecated
+# @arg val
+# @meta deprecated
+bar() { :; }
+"###;
+
+#[test]
+fn hidden_deprecated() {
+    snapshot_multi!(
+        HIDDEN_DEPRECATED_SCRIPT,
+        [
+            vec!["prog", "-h"],
+            vec!["prog", "--fb", "--oa", "1", "foo"],
+            vec!["prog", "bar", "v"],
+            vec!["prog", "baz"],
+        ]
+    );
+}
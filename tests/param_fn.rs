@@ -115,3 +115,40 @@ _choice_fn() {
         ]
     );
 }
+
+#[test]
+fn case4() {
+    let script = r###"
+# @option --oa
+# @option --ob[`_choice_fn`]
+_choice_fn() {
+	echo "oa=${argc_oa:-}"
+}
+"###;
+    snapshot_multi!(
+        script,
+        [
+            vec![
+                "prog",
+                "___internal___",
+                "_choice_fn",
+                "prog",
+                "--oa",
+                "x",
+                "--ob",
+                ""
+            ],
+            vec![
+                "prog",
+                "___internal___",
+                "_choice_fn",
+                "prog",
+                "--oa",
+                "x",
+                "--unknown",
+                "--ob",
+                ""
+            ],
+        ]
+    );
+}
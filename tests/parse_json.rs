@@ -0,0 +1,49 @@
+const SCRIPT: &str = r###"
+# @flag -v* verbose
+# @flag --[no-]color
+# @option --port{int}=8080
+# @option --ratio{float}
+# @option -D-*
+# @env FOO=bar
+# @cmd
+# @arg name=`_default_fn`
+sub() { :; }
+# @cmd
+# @option --tag*
+# @arg files*
+run() { :; }
+_default_fn() { :; }
+"###;
+
+#[test]
+fn values() {
+    snapshot_json!(
+        SCRIPT,
+        &["prog", "-v", "-v", "--port", "1", "--ratio", "0.5", "run", "--tag", "a", "x", "y"]
+    );
+}
+
+#[test]
+fn defaults() {
+    snapshot_json!(SCRIPT, &["prog", "--no-color", "-Dx=1", "-Dx=2", "sub"]);
+}
+
+#[test]
+fn extra_positionals() {
+    snapshot_json!("main() { :; }", &["prog", "a", "b"]);
+}
+
+#[test]
+fn error() {
+    snapshot_json!(SCRIPT, &["prog", "--port", "x", "sub"]);
+}
+
+#[test]
+fn deprecated() {
+    let script = r###"
+# @option --oa
+# @meta deprecated use --ob instead
+# @option --ob
+"###;
+    snapshot_json!(script, &["prog", "--oa", "1"]);
+}
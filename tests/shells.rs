@@ -0,0 +1,55 @@
+#[test]
+fn values() {
+    let script = r###"
+# @option --oa*
+# @option -D-*
+# @flag -f
+# @env FOO=bar
+# @arg name
+# @arg rest*
+"###;
+    snapshot_shells!(
+        script,
+        &["prog", "--oa", "it's", "--oa", "b", "-Dx=1", "-Dx=2", "-Dy=3", "-f", "x", "a b", "c"]
+    );
+}
+
+#[test]
+fn fn_values() {
+    let script = r###"
+# @option --oa=`_default_fn`
+# @env FOO=`_default_fn`
+# @arg name=`_default_fn`
+_default_fn() { :; }
+"###;
+    snapshot_shells!(script, &["prog"]);
+}
+
+#[test]
+fn hooks() {
+    let script = r###"
+_argc_before() { :; }
+_argc_after() { :; }
+# @cmd
+# @arg args*
+cmd() { :; }
+"###;
+    snapshot_shells!(script, &["prog", "cmd", "a", "b c"]);
+}
+
+#[test]
+fn require_tools() {
+    let script = r###"
+# @meta require-tools git
+main() { :; }
+"###;
+    snapshot_shells!(script, &["prog"]);
+}
+
+#[test]
+fn error() {
+    let script = r###"
+# @arg name!
+"###;
+    snapshot_shells!(script, &["prog"]);
+}
@@ -284,6 +284,29 @@ fn cmd_combine_shorts() {
     snapshot_multi!(script, [vec!["prog", "-A"], vec!["prog", "-AB"]]);
 }
 
+#[test]
+fn abbreviate_long_options() {
+    let script = r###"
+# @meta abbreviate-long-options
+# @meta version 1.0.0
+# @flag --verbose
+# @flag --[no-]color
+# @option --output
+# @cmd
+# @option --value
+cmd() { :; }
+"###;
+    snapshot_multi!(
+        script,
+        [
+            vec!["prog", "--verb", "--no-c", "--out", "a", "cmd", "--val=b"],
+            vec!["prog", "--col", "--out=a", "cmd"],
+            vec!["prog", "--ver", "cmd"],
+            vec!["prog", "--vers"],
+        ]
+    );
+}
+
 #[test]
 fn name_with_special_chars() {
     let script = r###"
@@ -343,6 +366,43 @@ fn plus_sign() {
     );
 }
 
+#[test]
+fn flag_counted() {
+    let script = r###"
+# @meta combine-shorts
+# @flag -v --verbose*
+# @flag -q
+# @option -o
+"###;
+    snapshot_multi!(
+        script,
+        [
+            vec!["prog", "-vvv"],
+            vec!["prog", "-v", "--verbose", "-vq"],
+            vec!["prog", "-qvo", "out"],
+            vec!["prog", "-vx"],
+        ]
+    );
+}
+
+#[test]
+fn flag_negatable() {
+    let script = r###"
+# @flag -c --[no-]cache   Use cache
+"###;
+    snapshot_multi!(
+        script,
+        [
+            vec!["prog", "-h"],
+            vec!["prog"],
+            vec!["prog", "--cache"],
+            vec!["prog", "--no-cache"],
+            vec!["prog", "-c", "--no-cache"],
+            vec!["prog", "--no-cache", "--cache"],
+        ]
+    );
+}
+
 #[test]
 fn notation_modifier() {
     let script = r###"
@@ -436,3 +496,126 @@ fn option_value_dash() {
         ]
     );
 }
+
+#[test]
+fn example() {
+    let script = r###"
+# @example prog build --release  Build for release
+# @example prog -h
+
+# @cmd
+# @flag --release
+# @example prog build --release   Build for release
+build() { :; }
+"###;
+    snapshot_multi!(script, [vec!["prog", "-h"], vec!["prog", "build", "-h"]]);
+}
+
+#[test]
+fn group() {
+    let script = r###"
+# @flag --fa
+# @group Network
+# @option --host
+# @option --port
+# @group
+# @flag --fb
+
+# @cmd
+cmda() { :; }
+
+# @group Maintenance
+# @cmd
+# @group Advanced
+# @flag --deep
+cmdb() { :; }
+
+# @cmd
+cmdc() { :; }
+"###;
+    snapshot_multi!(script, [vec!["prog", "-h"], vec!["prog", "cmdb", "-h"]]);
+}
+
+#[test]
+fn group_root_options() {
+    let script = r###"
+# @group Misc
+# @flag --verbose
+
+# @cmd
+b() { :; }
+
+# @cmd
+c() { :; }
+"###;
+    snapshot_multi!(script, [vec!["prog", "-h"]]);
+    let cmd = argc::export(script, "prog").unwrap();
+    assert!(cmd
+        .subcommands
+        .iter()
+        .all(|v| !v.extra.contains_key("group")));
+}
+
+#[test]
+fn help_template() {
+    let script = r###"
+# @describe Demo app
+# @meta version 1.0.0
+# @meta help-template
+# {about} v{version}
+#
+# Usage: {usage}
+#
+# Arguments:
+# {positionals}
+#
+# Options:
+# {options}
+#
+# Commands:
+# {subcommands}
+# {envs}
+# Unknown {placeholder} is kept.
+# @option --mode <MODE>  Build mode
+
+# @cmd Build it
+# @arg target  Target
+build() { :; }
+"###;
+    snapshot_multi!(script, [vec!["prog", "-h"], vec!["prog", "build", "-h"]]);
+}
+
+#[test]
+fn locale() {
+    let script = r###"
+# @meta locale de
+# @option --mode[dev|prod] <MODE>  Build mode
+# @option --jobs <NUM> Parallel jobs
+# @flag --force  Force it
+
+# @cmd Build it
+# @arg target!  Target
+build() { :; }
+"###;
+    snapshot_multi!(
+        script,
+        [
+            vec!["prog", "-h"],
+            vec!["prog", "--mode", "prd"],
+            vec!["prog", "--forc"],
+            vec!["prog", "bild"],
+            vec!["prog", "build"],
+        ]
+    );
+}
+
+#[test]
+fn locale_message() {
+    let script = r###"
+# @meta locale ja
+# @meta message usage Usage:
+# @meta message unexpected-argument error: unknown `{name}`
+# @flag --force  Force it
+"###;
+    snapshot_multi!(script, [vec!["prog", "-h"], vec!["prog", "--forc"]]);
+}
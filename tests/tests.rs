@@ -9,18 +9,26 @@ pub use fixtures::locate_script;
 
 #[macro_use]
 mod macros;
+mod argfile;
 mod bind_env;
 mod cli;
+mod color;
 mod compgen;
+mod dep;
 mod env;
 mod fail;
+mod fmt;
 mod hook_fn;
+mod lint;
+mod lsp;
 mod main_fn;
 mod meta;
 #[cfg(target_os = "linux")]
 mod misc;
 mod multiline;
 mod param_fn;
+mod parse_json;
+mod shells;
 mod spec;
 mod validate;
 
@@ -358,3 +358,161 @@ main() { :; }
         [vec!["prog"], vec!["prog", "cmd1"], vec!["prog", "cmd2"],]
     );
 }
+
+#[test]
+fn option_conflicts() {
+    let script = r###"
+# @flag --json
+# @flag --table
+# @option --format
+# @conflicts json,table,--format
+"###;
+    snapshot_multi!(
+        script,
+        [
+            vec!["prog", "-h"],
+            vec!["prog", "--json"],
+            vec!["prog", "--json", "--table"],
+            vec!["prog", "--table", "--format", "csv"],
+        ]
+    );
+}
+
+#[test]
+fn option_requires() {
+    let script = r###"
+# @option --user
+# @option --password
+# @option --token
+# @requires user password,token
+"###;
+    snapshot_multi!(
+        script,
+        [
+            vec!["prog", "-h"],
+            vec!["prog", "--password", "x"],
+            vec!["prog", "--user", "u"],
+            vec!["prog", "--user", "u", "--password", "x"],
+            vec!["prog", "--user", "u", "--password", "x", "--token", "t"],
+        ]
+    );
+}
+
+#[test]
+fn value_types() {
+    let script = r###"
+# @option --port{1..=65535}
+# @option --ratio{float}
+# @option --timeout{duration}
+# @option --endpoint{url}
+# @option --pattern{regex}
+# @option --dir{dir}
+# @option --file{file}
+# @option --enabled{bool}
+# @arg count{int}
+"###;
+    snapshot_multi!(
+        script,
+        [
+            vec![
+                "prog",
+                "--port",
+                "8080",
+                "--endpoint",
+                "https://example.com",
+                "--timeout",
+                "1h30m",
+                "42"
+            ],
+            vec!["prog", "--port", "0"],
+            vec!["prog", "--port", "65536"],
+            vec!["prog", "--port", "abc"],
+            vec!["prog", "--ratio", "1.5e"],
+            vec!["prog", "--timeout", "30"],
+            vec!["prog", "--endpoint", "example.com"],
+            vec!["prog", "--pattern", "^v[0-9]+$"],
+            vec!["prog", "--pattern", "(a"],
+            vec!["prog", "--dir", "src", "--file", "Cargo.toml"],
+            vec!["prog", "--dir", "Cargo.toml"],
+            vec!["prog", "--file", "not-exist"],
+            vec!["prog", "--enabled", "yes"],
+            vec!["prog", "4x"],
+            vec!["prog", "9223372036854775807"],
+            vec!["prog", "--", "-9223372036854775808"],
+            vec!["prog", "9223372036854775808"],
+            vec!["prog", "00000000000000000000042"],
+            vec!["prog", "--port", "99999999999999999999"],
+            vec!["prog", "--pattern", "a{1,2}"],
+            vec!["prog", "--pattern", "a**"],
+            vec!["prog", "--pattern", "[[:foo:]]"],
+            vec!["prog", "--pattern", "a{2,1}"],
+            vec!["prog", "--pattern", "\\"],
+        ]
+    );
+}
+
+#[test]
+fn did_you_mean() {
+    let script = r###"
+# @flag --verbose
+# @option --env[production|development]
+
+# @cmd
+deploy() { :; }
+
+# @cmd
+# @flag --force
+destroy() { :; }
+
+# @cmd
+list() { :; }
+"###;
+    snapshot_multi!(
+        script,
+        [
+            vec!["prog", "dploy"],
+            vec!["prog", "xyz"],
+            vec!["prog", "--verbos", "deploy"],
+            vec!["prog", "destroy", "--forc"],
+            vec!["prog", "--env", "prodution", "list"],
+            vec!["prog", "help", "lst"],
+        ]
+    );
+}
+
+#[test]
+fn choice_alias() {
+    let script = r###"
+# @option --env[~prod|production=prod|dev] $$
+# @option --tags*,[a|aa=a|b]
+# @arg target[=local|lo=local|remote]
+# @env LEVEL[~info|warn|warning=warn]
+"###;
+    snapshot_multi!(
+        script,
+        [
+            vec!["prog", "-h"],
+            vec!["prog", "--env", "PROD", "--tags", "aa,b", "lo"],
+            vec!["prog", "--tags", "B"],
+            vec!["prog", "--env", "Production", "--tags", "aa,b"],
+            vec!["prog", "--env", "staging"],
+        ]
+    );
+}
+
+#[test]
+fn choice_literal() {
+    let script = r###"
+# @option --opt[a=b|c]
+# @option --mode["~fast"|"x=c"|c]
+"###;
+    snapshot_multi!(
+        script,
+        [
+            vec!["prog", "-h"],
+            vec!["prog", "--opt", "a=b", "--mode", "~fast"],
+            vec!["prog", "--mode", "x=c"],
+            vec!["prog", "--mode", "x"],
+        ]
+    );
+}